@@ -0,0 +1,176 @@
+use std::io::Read;
+
+use discord_api::ApiResponse;
+use discord_api::endpoints::guilds::GET_GUILD_CHANNELS;
+use discord_api::endpoints::messages::{
+	CREATE_MESSAGE,
+	CreateMessageRequest,
+	GET_MESSAGES,
+	GetMessagesQuery,
+};
+use discord_api::endpoints::users::{
+	GET_CURRENT_USER,
+	GET_CURRENT_USER_GUILDS,
+	GetCurrentUserGuildsQuery,
+};
+use discord_api::types::channel::Channel;
+use discord_api::types::guild::UserGuild;
+use discord_api::types::message::Message;
+use discord_api::types::snowflake::Snowflake;
+use discord_api::types::user::User;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::export::{ExportOptions, export_channel};
+use crate::utils::request::{AutoHandle, BaseUrl, RequestClient};
+
+pub async fn whoami(json: bool) -> Result<(), Box<dyn std::error::Error>> {
+	let user: User = get(GET_CURRENT_USER).await?;
+
+	if json {
+		return print_json(&user);
+	}
+
+	println!("{} (@{}, {})", user.display_name(), user.username, user.id);
+	Ok(())
+}
+
+pub async fn guilds(json: bool) -> Result<(), Box<dyn std::error::Error>> {
+	let guilds: Vec<UserGuild> = get(&GET_CURRENT_USER_GUILDS(
+		&GetCurrentUserGuildsQuery::default(),
+	))
+	.await?;
+
+	if json {
+		return print_json(&guilds);
+	}
+
+	for guild in guilds {
+		println!("{}\t{}", guild.id, guild.name);
+	}
+	Ok(())
+}
+
+pub async fn channels(
+	guild_id: Snowflake,
+	json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+	let mut channels: Vec<Channel> = get(&GET_GUILD_CHANNELS(guild_id)).await?;
+
+	if json {
+		return print_json(&channels);
+	}
+
+	channels.sort_by_key(|c| c.position.unwrap_or_default());
+	for channel in channels {
+		println!(
+			"{}\t{:?}\t{}",
+			channel.id,
+			channel.r#type,
+			channel.display_name()
+		);
+	}
+	Ok(())
+}
+
+pub async fn messages(
+	channel_id: Snowflake,
+	limit: Option<u8>,
+	json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+	let query = GetMessagesQuery {
+		limit,
+		..Default::default()
+	};
+	let messages: Vec<Message> = get(&GET_MESSAGES(channel_id, &query)).await?;
+
+	if json {
+		return print_json(&messages);
+	}
+
+	// discord returns newest first, terminals read top to bottom
+	for message in messages.iter().rev() {
+		println!(
+			"[{}] {}: {}",
+			message.timestamp,
+			message.author.display_name(),
+			message.content
+		);
+	}
+	Ok(())
+}
+
+/// sends `content` to the channel, a content of `-` reads the message from stdin
+pub async fn send(
+	channel_id: Snowflake,
+	content: String,
+	json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+	let content = if content == "-" {
+		let mut stdin = String::new();
+		std::io::stdin().read_to_string(&mut stdin)?;
+		stdin.trim_end_matches(['\n', '\r']).to_owned()
+	} else {
+		content
+	};
+
+	if content.trim().is_empty() {
+		return Err("can't send an empty message".into());
+	}
+
+	let payload = CreateMessageRequest {
+		content: Some(content),
+		..Default::default()
+	};
+
+	let client = RequestClient::new(BaseUrl::Discord, false);
+	let message: Message = match client
+		.post(&CREATE_MESSAGE(channel_id), Some(&payload))
+		.await?
+		.with_auto_handle()
+		.await?
+	{
+		| ApiResponse::Success(m) => m,
+		| ApiResponse::Error(e) => return Err(format!("{e:?}").into()),
+	};
+
+	if json {
+		return print_json(&message);
+	}
+
+	println!("{}", message.id);
+	Ok(())
+}
+
+pub async fn export(
+	channel_id: Snowflake,
+	options: ExportOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+	let count = export_channel(channel_id, &options, |fetched| {
+		eprint!("\rFetched {fetched} messages");
+	})
+	.await?;
+
+	eprintln!();
+	println!("Exported {count} messages to {}", options.output.display());
+	Ok(())
+}
+
+async fn get<T: DeserializeOwned>(endpoint: &str) -> Result<T, Box<dyn std::error::Error>> {
+	let client = RequestClient::new(BaseUrl::Discord, false);
+
+	match client
+		.get::<()>(endpoint, None)
+		.await?
+		.with_auto_handle()
+		.await?
+	{
+		| ApiResponse::Success(v) => Ok(v),
+		| ApiResponse::Error(e) => Err(format!("{e:?}").into()),
+	}
+}
+
+fn print_json(value: &impl Serialize) -> Result<(), Box<dyn std::error::Error>> {
+	println!("{}", serde_json::to_string_pretty(value)?);
+	Ok(())
+}
@@ -0,0 +1,64 @@
+use fast_qr::{ECL, QRBuilder, QRCode};
+use tracing::error;
+
+use crate::utils::fingerprint::{FINGERPRINT, get_fingerprint};
+use crate::utils::token::save_token;
+use crate::ws::remote_auth::{RemoteAuthEvent, remote_auth_login};
+
+pub async fn qr_login() -> Result<(), Box<dyn std::error::Error>> {
+	// requests without a token need the fingerprint
+	if FINGERPRINT.get().is_none() {
+		let _ = FINGERPRINT.set(get_fingerprint().await?);
+	}
+
+	let token = remote_auth_login(|event| match event {
+		| RemoteAuthEvent::QrCode {
+			url,
+		} => match QRBuilder::new(url).ecl(ECL::L).build() {
+			| Ok(qr) => {
+				println!("{}", qr_to_terminal(&qr));
+				println!("Scan this with your Discord mobile app to log in");
+			},
+			| Err(e) => error!("Failed to generate qr code: {e:?}"),
+		},
+		| RemoteAuthEvent::Accepted {
+			username, ..
+		} => println!("Check your phone! Logging in as {username}"),
+		| RemoteAuthEvent::Cancelled => {
+			println!("Login request cancelled by mobile client, scan the code again to retry")
+		},
+	})
+	.await?;
+
+	save_token(&token)?;
+	println!("Logged in! Token saved to the keyring");
+
+	Ok(())
+}
+
+/// renders a qr code with unicode half blocks so two rows of modules fit in one line
+///
+/// light modules are drawn filled so the code still scans on dark terminal backgrounds
+fn qr_to_terminal(qr: &QRCode) -> String {
+	const MARGIN: isize = 2;
+
+	let size = qr.size as isize;
+	let is_dark = |x: isize, y: isize| {
+		(0..size).contains(&x) && (0..size).contains(&y) && qr[y as usize][x as usize].value()
+	};
+
+	let mut out = String::new();
+	for y in (-MARGIN..size + MARGIN).step_by(2) {
+		for x in -MARGIN..size + MARGIN {
+			out.push(match (is_dark(x, y), is_dark(x, y + 1)) {
+				| (false, false) => '█',
+				| (false, true) => '▀',
+				| (true, false) => '▄',
+				| (true, true) => ' ',
+			});
+		}
+		out.push('\n');
+	}
+
+	out
+}
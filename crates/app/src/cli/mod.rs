@@ -0,0 +1,380 @@
+use std::path::PathBuf;
+
+use discord_api::types::snowflake::Snowflake;
+use lexopt::{Arg, Parser};
+use tracing::error;
+use tracing::level_filters::LevelFilter;
+
+use crate::export::{ExportFormat, ExportOptions, parse_date};
+use crate::utils::token::load_token;
+
+mod commands;
+mod login;
+
+pub struct CliArgs {
+	pub log_level: Option<LevelFilter>,
+	pub config_dir: Option<PathBuf>,
+	/// subcommand to run instead of opening the app window
+	pub command: Option<Command>,
+	/// print api objects as json instead of human readable text
+	pub json: bool,
+}
+
+pub enum Command {
+	/// log in from the terminal without opening the app window
+	Login { qr: bool },
+	/// print the logged in user
+	Whoami,
+	/// list the guilds the user is in
+	Guilds,
+	/// list the channels in a guild
+	Channels { guild_id: Snowflake },
+	/// print the latest messages in a channel
+	Messages {
+		channel_id: Snowflake,
+		limit: Option<u8>,
+	},
+	/// send a message to a channel, `-` reads the content from stdin
+	Send {
+		channel_id: Snowflake,
+		content: String,
+	},
+	/// export the history of a channel to a file
+	Export {
+		channel_id: Snowflake,
+		options: ExportOptions,
+	},
+}
+
+/// flags that only apply to a single subcommand
+#[derive(Default)]
+struct SubcommandFlags {
+	qr: bool,
+	limit: Option<u8>,
+	format: Option<ExportFormat>,
+	after: Option<Snowflake>,
+	before: Option<Snowflake>,
+	attachments: bool,
+	output: Option<PathBuf>,
+}
+
+/// returns `None` if the full app should not be run
+pub fn parse_args() -> Option<CliArgs> {
+	let mut cli_args = CliArgs {
+		log_level: None,
+		config_dir: None,
+		command: None,
+		json: false,
+	};
+
+	let mut positionals: Vec<String> = Vec::new();
+	let mut flags = SubcommandFlags::default();
+
+	let mut parser = Parser::from_env();
+	loop {
+		let arg = match parser.next() {
+			| Ok(Some(arg)) => arg,
+			| Ok(None) => break,
+			| Err(e) => {
+				error!("{e}");
+				return None;
+			},
+		};
+
+		match arg {
+			| Arg::Long("help") | Arg::Short('h') => {
+				print_help();
+				return None;
+			},
+			| Arg::Long("log-level") => {
+				if let Ok(Some(Arg::Value(l))) = parser.next() {
+					let level = match l.to_string_lossy().to_lowercase().as_str() {
+						| "error" => LevelFilter::ERROR,
+						| "warn" => LevelFilter::WARN,
+						| "info" => LevelFilter::INFO,
+						| "debug" => LevelFilter::DEBUG,
+						| "trace" => LevelFilter::TRACE,
+						| level => {
+							error!(
+								"Unknown log level `{level}` (options are error, warn, info, debug, and trace)",
+							);
+							return None;
+						},
+					};
+					cli_args.log_level = Some(level);
+				} else {
+					error!("--log-level flag must be supplied with a logging level!");
+					return None;
+				}
+			},
+			| Arg::Long("config-dir") => {
+				if let Ok(Some(Arg::Value(d))) = parser.next() {
+					cli_args.config_dir = Some(PathBuf::from(d));
+				} else {
+					error!("--config-dir flag must be supplied with a path!");
+					return None;
+				}
+			},
+			| Arg::Long("json") => cli_args.json = true,
+			| Arg::Long("qr") => flags.qr = true,
+			| Arg::Long("attachments") => flags.attachments = true,
+			| Arg::Long("limit") => {
+				if let Ok(Some(Arg::Value(l))) = parser.next()
+					&& let Ok(l) = l.to_string_lossy().parse::<u8>()
+					&& (1..=100).contains(&l)
+				{
+					flags.limit = Some(l);
+				} else {
+					error!("--limit flag must be supplied with a number from 1 to 100!");
+					return None;
+				}
+			},
+			| Arg::Long("format") => {
+				if let Ok(Some(Arg::Value(f))) = parser.next() {
+					match f.to_string_lossy().parse() {
+						| Ok(format) => flags.format = Some(format),
+						| Err(e) => {
+							error!("{e}");
+							return None;
+						},
+					}
+				} else {
+					error!("--format flag must be supplied with a format!");
+					return None;
+				}
+			},
+			| Arg::Long(flag @ ("after" | "before")) => {
+				let flag = flag.to_owned();
+				if let Ok(Some(Arg::Value(d))) = parser.next()
+					&& let Some(timestamp) = parse_date(&d.to_string_lossy())
+				{
+					let cursor = Some(Snowflake::from_timestamp(timestamp));
+					if flag == "after" {
+						flags.after = cursor;
+					} else {
+						flags.before = cursor;
+					}
+				} else {
+					error!(
+						"--{flag} flag must be supplied with a YYYY-MM-DD date or a unix timestamp in seconds!"
+					);
+					return None;
+				}
+			},
+			| Arg::Long("output") => {
+				if let Ok(Some(Arg::Value(o))) = parser.next() {
+					flags.output = Some(PathBuf::from(o));
+				} else {
+					error!("--output flag must be supplied with a path!");
+					return None;
+				}
+			},
+			| Arg::Value(v) => positionals.push(v.to_string_lossy().into_owned()),
+			| arg => {
+				error!("{}", arg.unexpected());
+				return None;
+			},
+		}
+	}
+
+	match parse_command(positionals, flags) {
+		| Ok(command) => cli_args.command = command,
+		| Err(e) => {
+			error!("{e}");
+			return None;
+		},
+	}
+
+	Some(cli_args)
+}
+
+/// builds the subcommand from the positional args and the flags that only apply to one subcommand
+fn parse_command(
+	positionals: Vec<String>,
+	flags: SubcommandFlags,
+) -> Result<Option<Command>, String> {
+	let mut positionals = positionals.into_iter();
+	let name = positionals.next().unwrap_or_default();
+
+	let snowflake = |arg: Option<String>, what: &str| -> Result<Snowflake, String> {
+		let arg = arg.ok_or(format!("`{name}` requires a {what} id"))?;
+		arg.parse()
+			.map_err(|_| format!("`{arg}` is not a valid {what} id"))
+	};
+
+	let flag_commands = [
+		("--qr", flags.qr, "login"),
+		("--limit", flags.limit.is_some(), "messages"),
+		("--format", flags.format.is_some(), "export"),
+		("--after", flags.after.is_some(), "export"),
+		("--before", flags.before.is_some(), "export"),
+		("--attachments", flags.attachments, "export"),
+		("--output", flags.output.is_some(), "export"),
+	];
+	for (flag, used, command) in flag_commands {
+		if used && name != command {
+			return Err(format!(
+				"{flag} flag can only be used with the `{command}` subcommand"
+			));
+		}
+	}
+
+	if name.is_empty() {
+		return Ok(None);
+	}
+
+	let command = match name.as_str() {
+		| "login" => Command::Login {
+			qr: flags.qr,
+		},
+		| "whoami" => Command::Whoami,
+		| "guilds" => Command::Guilds,
+		| "channels" => Command::Channels {
+			guild_id: snowflake(positionals.next(), "guild")?,
+		},
+		| "messages" => Command::Messages {
+			channel_id: snowflake(positionals.next(), "channel")?,
+			limit: flags.limit,
+		},
+		| "send" => {
+			let channel_id = snowflake(positionals.next(), "channel")?;
+			let content = positionals.by_ref().collect::<Vec<_>>().join(" ");
+			if content.is_empty() {
+				return Err(
+					"`send` requires the message content or `-` to read it from stdin".to_owned(),
+				);
+			}
+
+			Command::Send {
+				channel_id,
+				content,
+			}
+		},
+		| "export" => {
+			let channel_id = snowflake(positionals.next(), "channel")?;
+			let format = flags.format.unwrap_or_default();
+
+			Command::Export {
+				channel_id,
+				options: ExportOptions {
+					format,
+					after: flags.after,
+					before: flags.before,
+					download_attachments: flags.attachments,
+					output: flags.output.unwrap_or_else(|| {
+						PathBuf::from(format!("{channel_id}.{}", format.extension()))
+					}),
+				},
+			}
+		},
+		| name => {
+			return Err(format!(
+				"Unknown command `{name}`, see --help for the options"
+			));
+		},
+	};
+
+	if let Some(extra) = positionals.next() {
+		return Err(format!("Unexpected argument `{extra}` for `{name}`"));
+	}
+
+	Ok(Some(command))
+}
+
+/// runs a subcommand to completion, returns the process exit code
+pub fn run_command(
+	command: Command,
+	json: bool,
+) -> i32 {
+	let runtime = match tokio::runtime::Builder::new_current_thread()
+		.enable_all()
+		.build()
+	{
+		| Ok(runtime) => runtime,
+		| Err(e) => {
+			error!("Failed to start async runtime: {e}");
+			return 1;
+		},
+	};
+
+	let result = runtime.block_on(async {
+		if !matches!(command, Command::Login { .. })
+			&& !matches!(load_token(), Ok(Some(ref token)) if token.is_valid())
+		{
+			return Err("Not logged in, run `cowcord login --qr` first".into());
+		}
+
+		match command {
+			| Command::Login {
+				qr: true,
+			} => login::qr_login().await,
+			| Command::Login {
+				qr: false,
+			} => Err(
+				"only qr code login is supported from the terminal, try `cowcord login --qr`"
+					.into(),
+			),
+			| Command::Whoami => commands::whoami(json).await,
+			| Command::Guilds => commands::guilds(json).await,
+			| Command::Channels {
+				guild_id,
+			} => commands::channels(guild_id, json).await,
+			| Command::Messages {
+				channel_id,
+				limit,
+			} => commands::messages(channel_id, limit, json).await,
+			| Command::Send {
+				channel_id,
+				content,
+			} => commands::send(channel_id, content, json).await,
+			| Command::Export {
+				channel_id,
+				options,
+			} => commands::export(channel_id, options).await,
+		}
+	});
+
+	match result {
+		| Ok(()) => 0,
+		| Err(e) => {
+			error!("{e}");
+			1
+		},
+	}
+}
+
+fn print_help() {
+	println!(
+		r#"Cowcord v{}{}
+
+Usage:
+  cowcord [OPTIONS] [COMMAND]
+
+Commands:
+  login --qr:				log in by scanning a qr code printed to the terminal
+  whoami:				print the logged in user
+  guilds:				list the guilds you are in
+  channels <GUILD_ID>:			list the channels in a guild
+  messages <CHANNEL_ID> [--limit <N>]:	print the latest messages in a channel (1-100, default 50)
+  send <CHANNEL_ID> <TEXT|->:		send a message, `-` reads it from stdin
+  export <CHANNEL_ID> [EXPORT OPTIONS]:	export the history of a channel
+
+Export options:
+  --format <FORMAT>:	json (raw api objects), html, or text (default json)
+  --after <DATE>:	only export messages after this YYYY-MM-DD date or unix timestamp in seconds
+  --before <DATE>:	only export messages before this YYYY-MM-DD date or unix timestamp in seconds
+  --attachments:	download attachments next to the export
+  --output <PATH>:	where to write the export (default <CHANNEL_ID>.<FORMAT>)
+
+Options:
+  -h --help:		prints this help message
+  --log-level <LEVEL>:	override the log level (eg. error, warn, info). using trace will clog your terminal so dont do that :)
+  --config-dir <PATH>:	override the config directory
+  --json:		print api objects as json instead of text
+"#,
+		env!("CARGO_PKG_VERSION"),
+		option_env!("PACKAGE_MANAGER")
+			.map(|pm| format!(" from {pm}"))
+			.unwrap_or_default(),
+	);
+}
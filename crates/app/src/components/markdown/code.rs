@@ -0,0 +1,111 @@
+use dioxus::prelude::*;
+use lucide_dioxus::{Check, ChevronDown, ChevronUp, Copy};
+use tailwind_fuse::tw_merge;
+
+use crate::components::markdown::highlight::highlight;
+
+/// blocks with more lines than this start collapsed
+const COLLAPSE_LINES: usize = 20;
+
+#[component]
+pub fn InlineCode(
+	code: String,
+	#[props(into, default)] class: String,
+) -> Element {
+	let class = tw_merge!(
+		"rounded-sm px-1 py-0.5 font-mono text-[0.85em] bg-muted-darker border border-border",
+		class
+	);
+
+	rsx! {
+		code { class, "{code}" }
+	}
+}
+
+/// A fenced code block, `language` is the tag after the opening fence
+///
+/// Blocks with a known language are highlighted, long blocks start collapsed
+#[component]
+pub fn CodeBlock(
+	language: Option<String>,
+	content: String,
+	#[props(into, default)] class: String,
+) -> Element {
+	let line_count = content.lines().count();
+	let mut collapsed = use_signal(|| line_count > COLLAPSE_LINES);
+	let mut copied = use_signal(|| false);
+
+	let tokens = use_memo(use_reactive!(|language, content| {
+		language.and_then(|language| highlight(&language, &content))
+	}));
+
+	let class = tw_merge!(
+		"group relative my-1 max-w-[90%] rounded-md bg-muted-darker border border-border",
+		class
+	);
+	let pre_class = tw_merge!(
+		"overflow-x-auto p-2 font-mono text-sm whitespace-pre",
+		if collapsed() {
+			"max-h-80 overflow-y-hidden"
+		} else {
+			""
+		}
+	);
+
+	let copy_content = content.clone();
+	let copy = move |_| {
+		let text = copy_content.clone();
+		async move {
+			let Ok(text) = serde_json::to_string(&text) else {
+				return;
+			};
+			if document::eval(&format!("await navigator.clipboard.writeText({text})"))
+				.await
+				.is_ok()
+			{
+				copied.set(true);
+				tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+				copied.set(false);
+			}
+		}
+	};
+
+	rsx! {
+		div { class,
+			pre { class: pre_class, "data-language": language,
+				code {
+					if let Some(tokens) = tokens() {
+						for token in tokens {
+							span { class: token.kind.class(), "{token.text}" }
+						}
+					} else {
+						"{content}"
+					}
+				}
+			}
+			button {
+				class: "absolute top-1 right-1 hidden group-hover:flex items-center justify-center size-7 rounded-md bg-muted border border-border text-muted-foreground hover:text-foreground",
+				title: if copied() { "Copied" } else { "Copy" },
+				onclick: copy,
+				if copied() {
+					Check { class: "size-4" }
+				} else {
+					Copy { class: "size-4" }
+				}
+			}
+			if line_count > COLLAPSE_LINES {
+				button {
+					class: "flex w-full items-center justify-center gap-1 border-t border-border py-1 text-xs text-muted-foreground hover:text-foreground",
+					onclick: move |_| collapsed.toggle(),
+					if collapsed() {
+						ChevronDown { class: "size-4" }
+						"Show all {line_count} lines"
+					} else {
+						ChevronUp { class: "size-4" }
+						"Collapse"
+					}
+				}
+			}
+		}
+	}
+}
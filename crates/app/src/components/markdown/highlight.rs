@@ -0,0 +1,133 @@
+use std::sync::LazyLock;
+
+use syntect::easy::ScopeRangeIterator;
+use syntect::parsing::{ParseState, Scope, ScopeStack, SyntaxSet};
+use syntect::util::LinesWithEndings;
+
+/// the bundled grammars, loaded the first time a code block is highlighted
+static SYNTAXES: LazyLock<SyntaxSet> = LazyLock::new(two_face::syntax::extra_newlines);
+
+/// scope prefixes and the token kind they map to, the first match wins so more specific scopes go first
+static SCOPES: LazyLock<Vec<(Scope, TokenKind)>> = LazyLock::new(|| {
+	[
+		("comment", TokenKind::Comment),
+		("punctuation.definition.comment", TokenKind::Comment),
+		("string", TokenKind::String),
+		("punctuation.definition.string", TokenKind::String),
+		("constant.character", TokenKind::String),
+		("constant.numeric", TokenKind::Number),
+		("constant", TokenKind::Constant),
+		("support.constant", TokenKind::Constant),
+		("keyword.operator", TokenKind::Operator),
+		("keyword", TokenKind::Keyword),
+		("storage", TokenKind::Keyword),
+		("entity.name.function", TokenKind::Function),
+		("support.function", TokenKind::Function),
+		("variable.function", TokenKind::Function),
+		("entity.name.tag", TokenKind::Tag),
+		("entity.other.attribute-name", TokenKind::Attribute),
+		("entity.name", TokenKind::Type),
+		("entity.other.inherited-class", TokenKind::Type),
+		("support.type", TokenKind::Type),
+		("support.class", TokenKind::Type),
+		("variable.parameter", TokenKind::Variable),
+		("variable.language", TokenKind::Variable),
+		("punctuation", TokenKind::Punctuation),
+	]
+	.into_iter()
+	.filter_map(|(scope, kind)| Some((Scope::new(scope).ok()?, kind)))
+	.collect()
+});
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+	Plain,
+	Comment,
+	String,
+	Number,
+	Constant,
+	Keyword,
+	Operator,
+	Function,
+	Type,
+	Tag,
+	Attribute,
+	Variable,
+	Punctuation,
+}
+
+impl TokenKind {
+	/// the tailwind classes for the token, the colors are the `--syntax-*` variables from `input.css`
+	pub const fn class(&self) -> &'static str {
+		match self {
+			| Self::Plain => "",
+			| Self::Comment => "text-syntax-comment italic",
+			| Self::String => "text-syntax-string",
+			| Self::Number => "text-syntax-number",
+			| Self::Constant => "text-syntax-constant",
+			| Self::Keyword => "text-syntax-keyword",
+			| Self::Operator => "text-syntax-operator",
+			| Self::Function => "text-syntax-function",
+			| Self::Type => "text-syntax-type",
+			| Self::Tag => "text-syntax-tag",
+			| Self::Attribute => "text-syntax-attribute",
+			| Self::Variable => "text-syntax-variable",
+			| Self::Punctuation => "text-syntax-punctuation",
+		}
+	}
+
+	fn from_stack(stack: &ScopeStack) -> Self {
+		// the innermost scope decides, outer ones are only used when it has no color
+		for scope in stack.as_slice().iter().rev() {
+			if let Some((_, kind)) = SCOPES
+				.iter()
+				.find(|(prefix, _)| prefix.is_prefix_of(*scope))
+			{
+				return *kind;
+			}
+		}
+		Self::Plain
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token {
+	pub kind: TokenKind,
+	pub text: String,
+}
+
+/// Splits the code into highlighted tokens, newlines are kept in the token text
+///
+/// `language` is matched against grammar names and file extensions, returns `None` if no grammar matches
+pub fn highlight(
+	language: &str,
+	content: &str,
+) -> Option<Vec<Token>> {
+	let syntax = SYNTAXES.find_syntax_by_token(language)?;
+
+	let mut state = ParseState::new(syntax);
+	let mut stack = ScopeStack::new();
+	let mut tokens: Vec<Token> = Vec::new();
+
+	for line in LinesWithEndings::from(content) {
+		let ops = state.parse_line(line, &SYNTAXES).ok()?;
+
+		for (range, op) in ScopeRangeIterator::new(&ops, line) {
+			stack.apply(op).ok()?;
+			if range.is_empty() {
+				continue;
+			}
+
+			let kind = TokenKind::from_stack(&stack);
+			match tokens.last_mut() {
+				| Some(last) if last.kind == kind => last.text.push_str(&line[range]),
+				| _ => tokens.push(Token {
+					kind,
+					text: line[range].to_owned(),
+				}),
+			}
+		}
+	}
+
+	Some(tokens)
+}
@@ -0,0 +1,116 @@
+use dioxus::prelude::*;
+use discord_api::types::snowflake::Snowflake;
+use discord_api::types::user::User;
+use tailwind_fuse::tw_merge;
+
+use crate::components::markdown::MentionNames;
+
+const CHIP_CLASS: &str = "inline rounded-sm px-0.5 font-medium bg-primary-muted text-link transition-colors hover:bg-primary hover:text-primary-foreground cursor-pointer";
+
+/// The highlighted pill used for mentions, `@everyone`, `@here` and slash commands
+#[component]
+pub fn MentionChip(
+	children: Element,
+	#[props(into, default)] class: String,
+	onclick: Option<EventHandler<MouseEvent>>,
+	#[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+) -> Element {
+	let class = tw_merge!(CHIP_CLASS, class);
+
+	rsx! {
+		span {
+			class,
+			onclick: move |evt| {
+				if let Some(handler) = &onclick {
+					handler.call(evt);
+				}
+			},
+			..attributes,
+			{children}
+		}
+	}
+}
+
+#[component]
+pub fn UserMention(
+	id: Snowflake,
+	/// the users mentioned by the message, used when the user isnt in the mention context
+	#[props(default)]
+	mentions: Vec<User>,
+) -> Element {
+	let names = try_use_context::<MentionNames>().unwrap_or_default();
+	let user = names
+		.users
+		.get(&id)
+		.or_else(|| mentions.iter().find(|u| u.id == id));
+
+	let name = user.map_or_else(
+		|| "unknown-user".to_owned(),
+		|u| u.display_name().to_owned(),
+	);
+	let username = user.map(|u| u.username.clone()).unwrap_or_default();
+	let on_user_click = names.on_user_click;
+
+	rsx! {
+		MentionChip {
+			title: username,
+			onclick: move |_| {
+				if let Some(on_user_click) = on_user_click {
+					on_user_click.call(id);
+				}
+			},
+			"@{name}"
+		}
+	}
+}
+
+#[component]
+pub fn RoleMention(id: Snowflake) -> Element {
+	let names = try_use_context::<MentionNames>().unwrap_or_default();
+
+	match names.roles.get(&id) {
+		// colored roles use their color instead of the default mention color
+		| Some(role) if role.color != 0 => {
+			let style = format!(
+				"color: #{color:06x}; background-color: #{color:06x}1a",
+				color = role.color
+			);
+			rsx! {
+				MentionChip { class: "hover:text-foreground", style, "@{role.name}" }
+			}
+		},
+		| Some(role) => rsx! {
+			MentionChip { "@{role.name}" }
+		},
+		| None => rsx! {
+			MentionChip { "@unknown-role" }
+		},
+	}
+}
+
+#[component]
+pub fn ChannelMention(id: Snowflake) -> Element {
+	let nav = use_navigator();
+	let names = try_use_context::<MentionNames>().unwrap_or_default();
+
+	let Some(channel) = names.channels.get(&id) else {
+		return rsx! {
+			MentionChip { "#unknown" }
+		};
+	};
+
+	let name = channel.display_name();
+	let route = match channel.guild_id {
+		| Some(guild_id) => format!("/channels/{guild_id}/{id}"),
+		| None => format!("/channels/@me/{id}"),
+	};
+
+	rsx! {
+		MentionChip {
+			onclick: move |_| {
+				nav.push(route.as_str());
+			},
+			"#{name}"
+		}
+	}
+}
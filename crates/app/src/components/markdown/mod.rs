@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+
+use cowcord_markdown::Node;
+use dioxus::prelude::*;
+use discord_api::CDN_URL;
+use discord_api::endpoints::cdn::CUSTOM_EMOJI;
+use discord_api::types::channel::Channel;
+use discord_api::types::snowflake::Snowflake;
+use discord_api::types::user::User;
+use tailwind_fuse::tw_merge;
+
+mod code;
+mod highlight;
+mod mention;
+mod spoiler;
+mod timestamp;
+
+pub use code::*;
+pub use mention::*;
+pub use spoiler::*;
+pub use timestamp::*;
+
+/// What mentions in message content resolve to, provided as context by the views that know about guilds
+///
+/// Anything missing falls back to the mentions sent with the message, then to discord's "unknown" names
+#[derive(Clone, Default, PartialEq)]
+pub struct MentionNames {
+	pub users: HashMap<Snowflake, User>,
+	pub roles: HashMap<Snowflake, MentionRole>,
+	pub channels: HashMap<Snowflake, Channel>,
+	/// called when a user mention is clicked, eg. to open their profile
+	pub on_user_click: Option<Callback<Snowflake>>,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct MentionRole {
+	pub name: String,
+	/// `0` if the role has no color
+	pub color: u32,
+}
+
+/// Renders message content, parsing its markdown, mentions, emojis and timestamps
+#[component]
+pub fn Markdown(
+	content: String,
+	/// the users mentioned by the message, used to name user mentions
+	#[props(default)]
+	mentions: Vec<User>,
+	#[props(into, default)] class: String,
+) -> Element {
+	let nodes = cowcord_markdown::parse(&content);
+	let jumbo = is_jumbo(&nodes);
+	let class = tw_merge!("whitespace-pre-wrap break-words", class);
+
+	rsx! {
+		div { class,
+			MarkdownNodes { nodes, mentions, jumbo }
+		}
+	}
+}
+
+/// Renders already parsed nodes, used for the children of formatting nodes
+#[component]
+pub fn MarkdownNodes(
+	nodes: Vec<Node>,
+	#[props(default)] mentions: Vec<User>,
+	/// whether emojis should be shown large, only for messages that are just emojis
+	#[props(default)]
+	jumbo: bool,
+) -> Element {
+	rsx! {
+		for node in nodes {
+			{render_node(node, &mentions, jumbo)}
+		}
+	}
+}
+
+fn render_node(
+	node: Node,
+	mentions: &[User],
+	jumbo: bool,
+) -> Element {
+	let mentions = mentions.to_vec();
+
+	match node {
+		| Node::Text(text) => rsx! { "{text}" },
+		| Node::Bold(nodes) => rsx! {
+			strong { MarkdownNodes { nodes, mentions: mentions.clone() } }
+		},
+		| Node::Italic(nodes) => rsx! {
+			em { MarkdownNodes { nodes, mentions: mentions.clone() } }
+		},
+		| Node::Underline(nodes) => rsx! {
+			u { MarkdownNodes { nodes, mentions: mentions.clone() } }
+		},
+		| Node::Strikethrough(nodes) => rsx! {
+			s { MarkdownNodes { nodes, mentions: mentions.clone() } }
+		},
+		| Node::Spoiler(nodes) => rsx! {
+			Spoiler { MarkdownNodes { nodes, mentions: mentions.clone() } }
+		},
+		| Node::InlineCode(code) => rsx! {
+			InlineCode { code }
+		},
+		| Node::CodeBlock {
+			language,
+			content,
+		} => rsx! {
+			CodeBlock { language, content }
+		},
+		| Node::BlockQuote(nodes) => rsx! {
+			div { class: "flex flex-row gap-x-2 my-0.5",
+				div { class: "w-1 shrink-0 rounded-sm bg-muted-lighter" }
+				div { class: "min-w-0",
+					MarkdownNodes { nodes, mentions: mentions.clone() }
+				}
+			}
+		},
+		| Node::Heading {
+			level,
+			content,
+		} => match level {
+			| 1 => rsx! {
+				h1 { class: "text-2xl font-bold my-1",
+					MarkdownNodes { nodes: content, mentions: mentions.clone() }
+				}
+			},
+			| 2 => rsx! {
+				h2 { class: "text-xl font-bold my-1",
+					MarkdownNodes { nodes: content, mentions: mentions.clone() }
+				}
+			},
+			| _ => rsx! {
+				h3 { class: "text-base font-bold my-1",
+					MarkdownNodes { nodes: content, mentions: mentions.clone() }
+				}
+			},
+		},
+		| Node::Subtext(nodes) => rsx! {
+			p { class: "text-xs text-muted-foreground",
+				MarkdownNodes { nodes, mentions: mentions.clone() }
+			}
+		},
+		| Node::List {
+			start: Some(start),
+			items,
+		} => rsx! {
+			ol { class: "list-decimal pl-6 whitespace-normal", start: "{start}",
+				for nodes in items {
+					li { class: "whitespace-pre-wrap",
+						MarkdownNodes { nodes, mentions: mentions.clone() }
+					}
+				}
+			}
+		},
+		| Node::List {
+			start: None,
+			items,
+		} => rsx! {
+			ul { class: "list-disc pl-6 whitespace-normal",
+				for nodes in items {
+					li { class: "whitespace-pre-wrap",
+						MarkdownNodes { nodes, mentions: mentions.clone() }
+					}
+				}
+			}
+		},
+		| Node::Link {
+			content,
+			url,
+			title,
+			..
+		} => rsx! {
+			a {
+				class: "text-link hover:underline",
+				href: "{url}",
+				title: title.unwrap_or_else(|| url.clone()),
+				MarkdownNodes { nodes: content, mentions: mentions.clone() }
+			}
+		},
+		| Node::Url {
+			url, ..
+		} => rsx! {
+			a { class: "text-link hover:underline", href: "{url}", "{url}" }
+		},
+		| Node::UserMention(id) => rsx! {
+			UserMention { id, mentions }
+		},
+		| Node::RoleMention(id) => rsx! {
+			RoleMention { id }
+		},
+		| Node::ChannelMention(id) => rsx! {
+			ChannelMention { id }
+		},
+		| Node::Everyone => rsx! {
+			MentionChip { "@everyone" }
+		},
+		| Node::Here => rsx! {
+			MentionChip { "@here" }
+		},
+		| Node::CustomEmoji {
+			name,
+			id,
+			animated,
+		} => rsx! {
+			CustomEmoji { name, id, animated, jumbo }
+		},
+		| Node::SlashCommand {
+			name, ..
+		} => rsx! {
+			MentionChip { "/{name}" }
+		},
+		| Node::Timestamp {
+			timestamp,
+			style,
+		} => rsx! {
+			Timestamp { timestamp, style }
+		},
+	}
+}
+
+#[component]
+pub fn CustomEmoji(
+	name: String,
+	id: Snowflake,
+	animated: bool,
+	#[props(default)] jumbo: bool,
+) -> Element {
+	let src = format!("{}{}", CDN_URL, CUSTOM_EMOJI(&id, animated));
+	let class = tw_merge!(
+		"inline-block object-contain align-bottom size-[1.375em]",
+		if jumbo { "size-12" } else { "" }
+	);
+
+	rsx! {
+		img {
+			class,
+			src,
+			alt: ":{name}:",
+			title: ":{name}:",
+			draggable: false,
+		}
+	}
+}
+
+/// Discord shows emojis larger when the message is nothing but (up to 30) emojis
+fn is_jumbo(nodes: &[Node]) -> bool {
+	let mut emojis = 0;
+	for node in nodes {
+		match node {
+			| Node::CustomEmoji {
+				..
+			} => emojis += 1,
+			| Node::Text(text) if text.trim().is_empty() => {},
+			| _ => return false,
+		}
+	}
+
+	(1..=30).contains(&emojis)
+}
@@ -0,0 +1,34 @@
+use dioxus::prelude::*;
+use tailwind_fuse::tw_merge;
+
+const HIDDEN_CLASS: &str = "bg-muted-lighter text-transparent cursor-pointer select-none hover:bg-muted-foreground/40 [&_*]:invisible";
+const REVEALED_CLASS: &str = "bg-muted";
+
+/// Content hidden behind `||spoiler||` markers until it is clicked
+#[component]
+pub fn Spoiler(
+	children: Element,
+	#[props(into, default)] class: String,
+) -> Element {
+	let mut revealed = use_signal(|| false);
+
+	let class = tw_merge!(
+		"rounded-sm px-0.5 transition-colors",
+		if revealed() {
+			REVEALED_CLASS
+		} else {
+			HIDDEN_CLASS
+		},
+		class
+	);
+
+	rsx! {
+		span {
+			class,
+			role: if !revealed() { "button" } else { "" },
+			"aria-label": if !revealed() { "Spoiler" } else { "" },
+			onclick: move |_| revealed.set(true),
+			{children}
+		}
+	}
+}
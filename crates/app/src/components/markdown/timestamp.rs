@@ -0,0 +1,101 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use cowcord_markdown::TimestampStyle;
+use dioxus::prelude::*;
+use tailwind_fuse::tw_merge;
+
+use crate::CONFIG;
+
+/// A `<t:unix:style>` timestamp, formatted in the user's locale
+///
+/// Relative timestamps update live, hovering any timestamp shows the full date
+#[component]
+pub fn Timestamp(
+	/// unix seconds
+	timestamp: i64,
+	#[props(default)] style: TimestampStyle,
+	#[props(into, default)] class: String,
+) -> Element {
+	let mut now = use_signal(unix_now);
+
+	use_future(move || async move {
+		if style != TimestampStyle::Relative {
+			return;
+		}
+
+		loop {
+			// only tick every second while the seconds are actually shown
+			let delay = match (timestamp - *now.peek()).abs() {
+				| ..60 => 1,
+				| ..3600 => 30,
+				| _ => 60,
+			};
+			tokio::time::sleep(Duration::from_secs(delay)).await;
+			now.set(unix_now());
+		}
+	});
+
+	let formatted = use_resource(move || {
+		let now = now();
+		async move { format_timestamp(timestamp, style, now).await }
+	});
+
+	let (text, full) = formatted().flatten().unwrap_or_default();
+
+	rsx! {
+		time { class: tw_merge!("rounded-sm px-0.5 bg-muted", class), title: full, "{text}" }
+	}
+}
+
+fn unix_now() -> i64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map_or(0, |d| d.as_secs() as i64)
+}
+
+/// Formats the timestamp with the webview's `Intl` apis, returns the text to show and the full date for hovering
+async fn format_timestamp(
+	timestamp: i64,
+	style: TimestampStyle,
+	now: i64,
+) -> Option<(String, String)> {
+	let locale = serde_json::to_string(&CONFIG.get()?.locale).ok()?;
+
+	let format = if style == TimestampStyle::Relative {
+		let (value, unit) = relative_unit(timestamp - now);
+		format!(
+			r#"new Intl.RelativeTimeFormat({locale}, {{ numeric: "auto" }}).format({value}, "{unit}")"#
+		)
+	} else {
+		let options = match style {
+			| TimestampStyle::ShortTime => r#"{ timeStyle: "short" }"#,
+			| TimestampStyle::LongTime => r#"{ timeStyle: "medium" }"#,
+			| TimestampStyle::ShortDate => r#"{ dateStyle: "short" }"#,
+			| TimestampStyle::LongDate => r#"{ dateStyle: "long" }"#,
+			| TimestampStyle::LongDateTime => r#"{ dateStyle: "full", timeStyle: "short" }"#,
+			| _ => r#"{ dateStyle: "long", timeStyle: "short" }"#,
+		};
+		format!("date.toLocaleString({locale}, {options})")
+	};
+
+	let script = format!(
+		r#"
+		const date = new Date({timestamp} * 1000);
+		return [{format}, date.toLocaleString({locale}, {{ dateStyle: "full", timeStyle: "short" }})];
+		"#
+	);
+
+	document::eval(&script).join().await.ok()
+}
+
+/// The largest unit that fits the difference, months and years are approximated as 30 and 365 days
+fn relative_unit(diff: i64) -> (i64, &'static str) {
+	match diff.abs() {
+		| ..60 => (diff, "second"),
+		| ..3600 => (diff / 60, "minute"),
+		| ..86400 => (diff / 3600, "hour"),
+		| ..2_592_000 => (diff / 86400, "day"),
+		| ..31_536_000 => (diff / 2_592_000, "month"),
+		| _ => (diff / 31_536_000, "year"),
+	}
+}
@@ -0,0 +1,22 @@
+use dioxus::prelude::*;
+use tailwind_fuse::tw_merge;
+
+const BASE_CLASS: &str = "inline-flex items-center justify-center min-w-4 h-4 px-1 rounded-full text-[0.625rem] font-bold leading-none bg-destructive text-destructive-foreground";
+
+/// A small count pill, like the mention count on guilds and channels
+#[component]
+pub fn Badge(
+	count: u32,
+	#[props(into, default)] class: String,
+) -> Element {
+	let class = tw_merge!(BASE_CLASS, class);
+	let count = if count > 999 {
+		"999+".to_owned()
+	} else {
+		count.to_string()
+	};
+
+	rsx! {
+		span { class, "{count}" }
+	}
+}
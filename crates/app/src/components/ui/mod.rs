@@ -1,2 +1,6 @@
+mod badge;
 mod button;
+mod role_name;
+pub use badge::*;
 pub use button::*;
+pub use role_name::*;
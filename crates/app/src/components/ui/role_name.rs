@@ -0,0 +1,40 @@
+use dioxus::prelude::*;
+use discord_api::types::guild::RoleColors;
+use tailwind_fuse::tw_merge;
+
+/// A name in the color of the member's role, gradient and holographic roles are animated across the text
+#[component]
+pub fn RoleName(
+	name: String,
+	colors: Option<RoleColors>,
+	#[props(into, default)] class: String,
+) -> Element {
+	let (style, effect) = match &colors {
+		| None => (String::new(), ""),
+		| Some(colors) => {
+			let primary = colors.primary_color.to_css();
+			match (&colors.secondary_color, &colors.tertiary_color) {
+				| (Some(secondary), Some(tertiary)) => (
+					format!(
+						"background-image: linear-gradient(to right, {primary}, {}, {}, {primary})",
+						secondary.to_css(),
+						tertiary.to_css()
+					),
+					"role-gradient role-holographic",
+				),
+				| (Some(secondary), None) => (
+					format!(
+						"background-image: linear-gradient(to right, {primary}, {}, {primary})",
+						secondary.to_css()
+					),
+					"role-gradient",
+				),
+				| _ => (format!("color: {primary}"), ""),
+			}
+		},
+	};
+
+	rsx! {
+		span { class: tw_merge!(effect, class), style, "{name}" }
+	}
+}
@@ -0,0 +1,352 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use cowcord_markdown::{Node, TimestampStyle};
+use discord_api::CDN_URL;
+use discord_api::endpoints::cdn::CUSTOM_EMOJI;
+use discord_api::types::message::{Embed, Message};
+use discord_api::types::snowflake::Snowflake;
+use discord_api::types::user::User;
+
+use crate::export::text::short_timestamp;
+use crate::export::{ExportedMessage, format_unix};
+use crate::utils::request::{BaseUrl, RequestClient};
+
+const STYLE: &str = r#"
+body { background: hsl(0, 0%, 6%); color: hsl(0, 8%, 85%); font-family: sans-serif; margin: 0; padding: 1rem; }
+a { color: hsl(230, 100%, 80%); }
+.message { display: flex; gap: 1rem; padding: 0.25rem 0; }
+.avatar { width: 40px; height: 40px; border-radius: 50%; flex-shrink: 0; }
+.author { font-weight: bold; }
+.timestamp { color: hsl(240 5% 65%); font-size: 0.75rem; margin-left: 0.5rem; }
+.content { white-space: pre-wrap; word-break: break-word; }
+.attachment img { max-width: 400px; max-height: 300px; border-radius: 0.5rem; margin-top: 0.25rem; }
+.embed { background: hsl(240 5% 14%); border-left: 4px solid hsl(240 5% 20%); border-radius: 0.25rem; padding: 0.5rem 0.75rem; margin-top: 0.25rem; max-width: 520px; }
+.embed-title { font-weight: bold; }
+.embed-field-name { font-weight: bold; font-size: 0.875rem; }
+.embed-footer { color: hsl(240 5% 65%); font-size: 0.75rem; }
+.embed img { max-width: 100%; border-radius: 0.25rem; }
+.mention, .timestamp-tag { background: hsl(235, 40%, 30%); border-radius: 0.25rem; padding: 0 0.125rem; }
+.timestamp-tag { background: hsl(240 5% 14%); }
+.spoiler { background: hsl(240 4.8% 25%); color: transparent; border-radius: 0.25rem; cursor: pointer; }
+.spoiler.revealed { background: hsl(240 5% 14%); color: inherit; cursor: auto; }
+.spoiler:not(.revealed) * { visibility: hidden; }
+code { background: hsl(240 5% 12%); border: 1px solid hsl(240 5% 20%); border-radius: 0.25rem; padding: 0 0.25rem; font-size: 0.85em; }
+pre { background: hsl(240 5% 12%); border: 1px solid hsl(240 5% 20%); border-radius: 0.375rem; padding: 0.5rem; overflow-x: auto; white-space: pre; }
+pre code { background: none; border: none; padding: 0; }
+blockquote { border-left: 4px solid hsl(240 4.8% 25%); margin: 0.125rem 0; padding-left: 0.5rem; }
+h1, h2, h3 { margin: 0.25rem 0; }
+.subtext { display: block; color: hsl(240 5% 65%); font-size: 0.75rem; }
+.content ul, .content ol { white-space: normal; margin: 0; padding-left: 1.5rem; }
+.content li { white-space: pre-wrap; }
+.emoji { width: 1.375em; height: 1.375em; vertical-align: bottom; object-fit: contain; }
+"#;
+
+pub async fn render(
+	channel_id: Snowflake,
+	messages: &[ExportedMessage],
+) -> String {
+	let avatars = fetch_avatars(messages).await;
+
+	let mut out = String::new();
+	let _ = write!(
+		out,
+		r#"<!DOCTYPE html><html><head><meta charset="utf-8"><title>Channel {channel_id}</title><style>{STYLE}</style></head><body>"#
+	);
+
+	for exported in messages {
+		let message = &exported.message;
+		let avatar = avatars.get(&message.author.id).cloned().unwrap_or_default();
+
+		let _ = write!(
+			out,
+			r#"<div class="message" id="{}"><img class="avatar" src="{}"><div><span class="author">{}</span><span class="timestamp">{}</span><div class="content">{}</div>"#,
+			message.id,
+			avatar,
+			escape(message.author.display_name()),
+			short_timestamp(&message.timestamp),
+			render_content(message)
+		);
+
+		for attachment in &message.attachments {
+			let src = escape(
+				exported
+					.local_attachments
+					.get(&attachment.id)
+					.unwrap_or(&attachment.url),
+			);
+			let is_image = attachment
+				.content_type
+				.as_deref()
+				.is_some_and(|t| t.starts_with("image/"));
+
+			if is_image {
+				let alt = escape(attachment.description.as_deref().unwrap_or_default());
+				let _ = write!(
+					out,
+					r#"<div class="attachment"><a href="{src}"><img src="{src}" alt="{alt}"></a></div>"#
+				);
+			} else {
+				let _ = write!(
+					out,
+					r#"<div class="attachment"><a href="{src}">{}</a></div>"#,
+					escape(&attachment.filename)
+				);
+			}
+		}
+
+		for embed in &message.embeds {
+			render_embed(&mut out, embed);
+		}
+
+		out.push_str("</div></div>");
+	}
+
+	out.push_str("</body></html>");
+	out
+}
+
+fn render_content(message: &Message) -> String {
+	let mut out = String::new();
+	render_nodes(
+		&mut out,
+		&cowcord_markdown::parse(&message.content),
+		&message.mentions,
+	);
+	out
+}
+
+/// Renders parsed message content as html, mentions keep their id as a tooltip
+fn render_nodes(
+	out: &mut String,
+	nodes: &[Node],
+	mentions: &[User],
+) {
+	let wrap = |out: &mut String, open: &str, nodes: &[Node], close: &str| {
+		out.push_str(open);
+		render_nodes(out, nodes, mentions);
+		out.push_str(close);
+	};
+
+	for node in nodes {
+		match node {
+			| Node::Text(text) => out.push_str(&escape(text)),
+			| Node::Bold(nodes) => wrap(out, "<strong>", nodes, "</strong>"),
+			| Node::Italic(nodes) => wrap(out, "<em>", nodes, "</em>"),
+			| Node::Underline(nodes) => wrap(out, "<u>", nodes, "</u>"),
+			| Node::Strikethrough(nodes) => wrap(out, "<s>", nodes, "</s>"),
+			| Node::Spoiler(nodes) => wrap(
+				out,
+				r#"<span class="spoiler" onclick="this.classList.add('revealed')">"#,
+				nodes,
+				"</span>",
+			),
+			| Node::InlineCode(code) => {
+				let _ = write!(out, "<code>{}</code>", escape(code));
+			},
+			| Node::CodeBlock {
+				language,
+				content,
+			} => {
+				let _ = write!(
+					out,
+					r#"<pre data-language="{}"><code>{}</code></pre>"#,
+					escape(language.as_deref().unwrap_or_default()),
+					escape(content)
+				);
+			},
+			| Node::BlockQuote(nodes) => wrap(out, "<blockquote>", nodes, "</blockquote>"),
+			| Node::Heading {
+				level,
+				content,
+			} => wrap(
+				out,
+				&format!("<h{level}>"),
+				content,
+				&format!("</h{level}>"),
+			),
+			| Node::Subtext(nodes) => wrap(out, r#"<small class="subtext">"#, nodes, "</small>"),
+			| Node::List {
+				start,
+				items,
+			} => {
+				let (open, close) = match start {
+					| Some(start) => (format!(r#"<ol start="{start}">"#), "</ol>"),
+					| None => ("<ul>".to_owned(), "</ul>"),
+				};
+				out.push_str(&open);
+				for item in items {
+					wrap(out, "<li>", item, "</li>");
+				}
+				out.push_str(close);
+			},
+			| Node::Link {
+				content,
+				url,
+				title,
+				..
+			} => {
+				let open = format!(
+					r#"<a href="{}" title="{}">"#,
+					escape(url),
+					escape(title.as_deref().unwrap_or(url))
+				);
+				wrap(out, &open, content, "</a>");
+			},
+			| Node::Url {
+				url, ..
+			} => {
+				let _ = write!(out, r#"<a href="{0}">{0}</a>"#, escape(url));
+			},
+			| Node::UserMention(id) => {
+				let name = mentions
+					.iter()
+					.find(|u| u.id == *id)
+					.map_or("unknown-user", |u| u.display_name());
+				let _ = write!(
+					out,
+					r#"<span class="mention" title="{id}">@{}</span>"#,
+					escape(name)
+				);
+			},
+			| Node::RoleMention(id) => {
+				let _ = write!(
+					out,
+					r#"<span class="mention" title="{id}">@unknown-role</span>"#
+				);
+			},
+			| Node::ChannelMention(id) => {
+				let _ = write!(out, r#"<span class="mention" title="{id}">#unknown</span>"#);
+			},
+			| Node::Everyone => out.push_str(r#"<span class="mention">@everyone</span>"#),
+			| Node::Here => out.push_str(r#"<span class="mention">@here</span>"#),
+			| Node::CustomEmoji {
+				name,
+				id,
+				animated,
+			} => {
+				let _ = write!(
+					out,
+					r#"<img class="emoji" src="{}{}" alt=":{2}:" title=":{2}:">"#,
+					CDN_URL,
+					CUSTOM_EMOJI(id, *animated),
+					escape(name)
+				);
+			},
+			| Node::SlashCommand {
+				name, ..
+			} => {
+				let _ = write!(out, r#"<span class="mention">/{}</span>"#, escape(name));
+			},
+			| Node::Timestamp {
+				timestamp,
+				style,
+			} => {
+				let full = format_unix(*timestamp);
+				// exports are static so relative timestamps are shown as dates too
+				let shown = match style {
+					| TimestampStyle::ShortTime => &full[11..16],
+					| TimestampStyle::LongTime => &full[11..],
+					| TimestampStyle::ShortDate | TimestampStyle::LongDate => &full[..10],
+					| _ => &full[..16],
+				};
+				let _ = write!(
+					out,
+					r#"<time class="timestamp-tag" title="{full} UTC">{shown}</time>"#
+				);
+			},
+		}
+	}
+}
+
+fn render_embed(
+	out: &mut String,
+	embed: &Embed,
+) {
+	let border = embed
+		.color
+		.map(|c| format!(r#" style="border-left-color: #{c:06x}""#))
+		.unwrap_or_default();
+	let _ = write!(out, r#"<div class="embed"{border}>"#);
+
+	if let Some(author) = &embed.author {
+		let _ = write!(out, "<div>{}</div>", escape(&author.name));
+	}
+	if let Some(title) = &embed.title {
+		match &embed.url {
+			| Some(url) => {
+				let _ = write!(
+					out,
+					r#"<div class="embed-title"><a href="{}">{}</a></div>"#,
+					escape(url),
+					escape(title)
+				);
+			},
+			| None => {
+				let _ = write!(out, r#"<div class="embed-title">{}</div>"#, escape(title));
+			},
+		}
+	}
+	if let Some(description) = &embed.description {
+		let _ = write!(out, r#"<div class="content">{}</div>"#, escape(description));
+	}
+	for field in &embed.fields {
+		let _ = write!(
+			out,
+			r#"<div><div class="embed-field-name">{}</div><div class="content">{}</div></div>"#,
+			escape(&field.name),
+			escape(&field.value)
+		);
+	}
+	if let Some(image) = embed.image.as_ref().or(embed.thumbnail.as_ref()) {
+		let _ = write!(out, r#"<img src="{}">"#, escape(&image.url));
+	}
+	if let Some(footer) = &embed.footer {
+		let _ = write!(
+			out,
+			r#"<div class="embed-footer">{}</div>"#,
+			escape(&footer.text)
+		);
+	}
+
+	out.push_str("</div>");
+}
+
+/// fetches every author's avatar once and inlines it so the page works offline
+async fn fetch_avatars(messages: &[ExportedMessage]) -> HashMap<Snowflake, String> {
+	let client = RequestClient::new(BaseUrl::DiscordCdn, false);
+	let mut avatars = HashMap::new();
+
+	for exported in messages {
+		let author = &exported.message.author;
+		if avatars.contains_key(&author.id) {
+			continue;
+		}
+
+		let endpoint = author.avatar_endpoint();
+		let src = match client.get_bytes(&endpoint).await {
+			| Ok(bytes) => format!("data:image/png;base64,{}", BASE64_STANDARD.encode(bytes)),
+			| Err(_) => String::new(),
+		};
+		avatars.insert(author.id, src);
+	}
+
+	avatars
+}
+
+pub fn escape(text: &str) -> String {
+	let mut escaped = String::with_capacity(text.len());
+	for c in text.chars() {
+		match c {
+			| '&' => escaped.push_str("&amp;"),
+			| '<' => escaped.push_str("&lt;"),
+			| '>' => escaped.push_str("&gt;"),
+			| '"' => escaped.push_str("&quot;"),
+			| '\'' => escaped.push_str("&#39;"),
+			| c => escaped.push(c),
+		}
+	}
+	escaped
+}
@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use discord_api::endpoints::messages::{GET_MESSAGES, GetMessagesQuery};
+use discord_api::types::message::Message;
+use discord_api::types::snowflake::Snowflake;
+use discord_api::{ApiResponse, CDN_URL, days_from_civil};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::utils::request::{AutoHandle, BaseUrl, RequestClient, with_rate_limit};
+
+mod html;
+pub mod text;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ExportFormat {
+	/// the raw api message objects
+	#[default]
+	Json,
+	/// a single html page that can be opened in a browser
+	Html,
+	/// plain text, one message per line
+	Text,
+}
+
+impl ExportFormat {
+	pub fn extension(&self) -> &'static str {
+		match self {
+			| Self::Json => "json",
+			| Self::Html => "html",
+			| Self::Text => "txt",
+		}
+	}
+}
+
+impl FromStr for ExportFormat {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			| "json" => Ok(Self::Json),
+			| "html" => Ok(Self::Html),
+			| "text" | "txt" => Ok(Self::Text),
+			| other => Err(format!(
+				"Unknown export format `{other}` (options are json, html, and text)"
+			)),
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+	pub format: ExportFormat,
+	/// only export messages sent after this (exclusive)
+	pub after: Option<Snowflake>,
+	/// only export messages sent before this (exclusive)
+	pub before: Option<Snowflake>,
+	/// download attachments into a `<output>_files` folder next to the export
+	pub download_attachments: bool,
+	pub output: PathBuf,
+}
+
+/// A message alongside the exact object discord sent, so json exports dont lose unknown fields
+pub struct ExportedMessage {
+	pub message: Message,
+	pub raw: Value,
+	/// attachment id to the path of the downloaded file, relative to the export
+	pub local_attachments: HashMap<Snowflake, String>,
+}
+
+/// Exports the history of a channel to `options.output`
+///
+/// `on_progress` is called with the amount of messages fetched so far, returns the amount of messages exported
+pub async fn export_channel(
+	channel_id: Snowflake,
+	options: &ExportOptions,
+	mut on_progress: impl FnMut(usize),
+) -> Result<usize, Box<dyn std::error::Error>> {
+	let client = RequestClient::new(BaseUrl::Discord, false);
+	let mut messages = fetch_history(&client, channel_id, options, &mut on_progress).await?;
+
+	if options.download_attachments {
+		download_attachments(&mut messages, &options.output).await?;
+	}
+
+	let output = match options.format {
+		| ExportFormat::Json => {
+			serde_json::to_string_pretty(&messages.iter().map(|m| &m.raw).collect::<Vec<_>>())?
+		},
+		| ExportFormat::Html => html::render(channel_id, &messages).await,
+		| ExportFormat::Text => text::render(&messages),
+	};
+
+	if let Some(parent) = options.output.parent() {
+		fs::create_dir_all(parent)?;
+	}
+	fs::write(&options.output, output)?;
+
+	Ok(messages.len())
+}
+
+/// Pages through the channel history between the cursors, returns the messages oldest first
+async fn fetch_history(
+	client: &RequestClient,
+	channel_id: Snowflake,
+	options: &ExportOptions,
+	on_progress: &mut impl FnMut(usize),
+) -> Result<Vec<ExportedMessage>, Box<dyn std::error::Error>> {
+	// with only a lower bound we can walk forwards from it, otherwise walk backwards from the upper bound
+	let forwards = options.after.is_some() && options.before.is_none();
+
+	let mut messages = Vec::new();
+	let mut cursor = if forwards {
+		options.after
+	} else {
+		options.before
+	};
+
+	loop {
+		let query = GetMessagesQuery {
+			before: cursor.filter(|_| !forwards),
+			after: cursor.filter(|_| forwards),
+			limit: Some(100),
+			..Default::default()
+		};
+		let endpoint = GET_MESSAGES(channel_id, &query);
+		let endpoint = endpoint.as_str();
+
+		let page: Vec<Value> = match with_rate_limit(move || client.get::<()>(endpoint, None))
+			.await?
+			.with_auto_handle()
+			.await?
+		{
+			| ApiResponse::Success(p) => p,
+			| ApiResponse::Error(e) => return Err(format!("{e:?}").into()),
+		};
+		let page_len = page.len();
+
+		let mut page = page
+			.into_iter()
+			.map(|raw| {
+				Ok(ExportedMessage {
+					message: serde_json::from_value(raw.clone())?,
+					raw,
+					local_attachments: HashMap::new(),
+				})
+			})
+			.collect::<Result<Vec<_>, serde_json::Error>>()?;
+
+		// discord returns pages newest first regardless of direction
+		page.sort_by_key(|m| m.message.id);
+		cursor = if forwards {
+			page.last().map(|m| m.message.id)
+		} else {
+			page.first().map(|m| m.message.id)
+		};
+
+		let reached_bound = !forwards
+			&& options
+				.after
+				.is_some_and(|after| page.first().is_some_and(|m| m.message.id <= after));
+		page.retain(|m| options.after.is_none_or(|after| m.message.id > after));
+
+		if forwards {
+			messages.extend(page);
+		} else {
+			page.append(&mut messages);
+			messages = page;
+		}
+		on_progress(messages.len());
+
+		if page_len < 100 || reached_bound || cursor.is_none() {
+			break;
+		}
+	}
+
+	Ok(messages)
+}
+
+async fn download_attachments(
+	messages: &mut [ExportedMessage],
+	output: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+	let dir_name = format!(
+		"{}_files",
+		output
+			.file_stem()
+			.map(|s| s.to_string_lossy())
+			.unwrap_or_default()
+	);
+	let dir = output.with_file_name(&dir_name);
+	let client = RequestClient::new(BaseUrl::DiscordCdn, false);
+
+	for exported in messages.iter_mut() {
+		for attachment in &exported.message.attachments {
+			let Some(endpoint) = attachment.url.strip_prefix(CDN_URL) else {
+				warn!(
+					"Skipping attachment not hosted on the cdn: {}",
+					attachment.url
+				);
+				continue;
+			};
+
+			let bytes = match client.get_bytes(endpoint).await {
+				| Ok(b) => b,
+				| Err(e) => {
+					warn!("Failed to download attachment {}: {e}", attachment.id);
+					continue;
+				},
+			};
+
+			let file_name = format!(
+				"{}_{}",
+				attachment.id,
+				sanitize_file_name(&attachment.filename)
+			);
+			fs::create_dir_all(&dir)?;
+			fs::write(dir.join(&file_name), bytes)?;
+
+			exported
+				.local_attachments
+				.insert(attachment.id, format!("{dir_name}/{file_name}"));
+		}
+	}
+
+	Ok(())
+}
+
+fn sanitize_file_name(name: &str) -> String {
+	name.chars()
+		.map(|c| match c {
+			| '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+			| c => c,
+		})
+		.collect()
+}
+
+/// Parses a `YYYY-MM-DD` date (UTC) or a unix timestamp in seconds into unix milliseconds
+///
+/// Timestamps need at least 9 digits, so a year on its own is rejected instead of being read as 1970.
+/// Dates past what a snowflake can hold are rejected too, since they're used as snowflake cursors
+pub fn parse_date(date: &str) -> Option<u64> {
+	let timestamp = if date.len() >= 9
+		&& let Ok(secs) = date.parse::<u64>()
+	{
+		secs.checked_mul(1000)?
+	} else {
+		let mut parts = date.splitn(3, '-').map(|p| p.parse::<i64>().ok());
+		let (year, month, day) = (parts.next()??, parts.next()??, parts.next()??);
+		if !(1970..=9999).contains(&year) || !(1..=12).contains(&month) || !(1..=31).contains(&day)
+		{
+			return None;
+		}
+
+		let days = days_from_civil(year, month, day);
+		u64::try_from(days.checked_mul(86_400_000)?).ok()?
+	};
+
+	(timestamp <= Snowflake::MAX_TIMESTAMP).then_some(timestamp)
+}
+
+/// Formats unix seconds as a `YYYY-MM-DD HH:MM:SS` date in UTC
+pub fn format_unix(timestamp: i64) -> String {
+	let days = timestamp.div_euclid(86_400);
+	let secs = timestamp.rem_euclid(86_400);
+
+	// civil from days, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+	let days = days + 719468;
+	let era = days.div_euclid(146097);
+	let day_of_era = days - era * 146097;
+	let year_of_era =
+		(day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let month_index = (5 * day_of_year + 2) / 153;
+	let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+	let month = if month_index < 10 {
+		month_index + 3
+	} else {
+		month_index - 9
+	};
+	let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+	format!(
+		"{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+		secs / 3600,
+		secs % 3600 / 60,
+		secs % 60
+	)
+}
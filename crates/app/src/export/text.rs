@@ -0,0 +1,42 @@
+use std::fmt::Write;
+
+use crate::export::ExportedMessage;
+
+pub fn render(messages: &[ExportedMessage]) -> String {
+	let mut out = String::new();
+
+	for exported in messages {
+		let message = &exported.message;
+		let _ = writeln!(
+			out,
+			"[{}] {}: {}",
+			short_timestamp(&message.timestamp),
+			message.author.display_name(),
+			message.content
+		);
+
+		for attachment in &message.attachments {
+			let location = exported
+				.local_attachments
+				.get(&attachment.id)
+				.unwrap_or(&attachment.url);
+			let _ = writeln!(out, "  [attachment] {location}");
+		}
+
+		for embed in &message.embeds {
+			if let Some(title) = embed.title.as_ref().or(embed.url.as_ref()) {
+				let _ = writeln!(out, "  [embed] {title}");
+			}
+		}
+	}
+
+	out
+}
+
+/// trims an iso 8601 timestamp down to `YYYY-MM-DD HH:MM:SS`
+pub fn short_timestamp(timestamp: &str) -> String {
+	timestamp
+		.get(..19)
+		.unwrap_or(timestamp)
+		.replacen('T', " ", 1)
+}
@@ -0,0 +1,389 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek};
+use std::path::Path;
+
+use discord_api::types::channel::{Channel, ChannelType};
+use discord_api::types::guild::UserGuild;
+use discord_api::types::message::{Attachment, Message, MessageType};
+use discord_api::types::permissions::Permissions;
+use discord_api::types::snowflake::Snowflake;
+use discord_api::types::user::User;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use tracing::warn;
+use zip::ZipArchive;
+
+/// The contents of a discord data package (`package.zip`), converted into the api model types
+///
+/// Packages only contain the messages sent by the account that requested it
+pub struct DataPackage {
+	pub user: User,
+	/// guilds from the `servers/` index, the package doesnt say which guilds the user owns or can see
+	pub guilds: Vec<UserGuild>,
+	/// every channel with messages in the package, newest activity first
+	pub channels: Vec<PackageChannel>,
+}
+
+pub struct PackageChannel {
+	pub channel: Channel,
+	/// the name discord gave the channel in `messages/index.json`, eg. `general in Some Server`
+	pub label: Option<String>,
+	/// messages oldest first
+	pub messages: Vec<Message>,
+}
+
+impl DataPackage {
+	/// Reads and parses a data package from a zip file
+	pub fn open(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+		Self::from_reader(File::open(path)?)
+	}
+
+	pub fn from_reader(reader: impl Read + Seek) -> Result<Self, Box<dyn std::error::Error>> {
+		let mut archive = ZipArchive::new(reader)?;
+
+		// older packages use lowercase folders, newer ones are capitalized
+		let files: HashMap<String, usize> = (0..archive.len())
+			.filter_map(|i| {
+				let name = archive.name_for_index(i)?;
+				Some((name.to_lowercase(), i))
+			})
+			.collect();
+
+		let mut read_json = |name: &str| -> Result<Option<Vec<u8>>, Box<dyn std::error::Error>> {
+			let Some(&index) = files.get(name) else {
+				return Ok(None);
+			};
+
+			let mut buf = Vec::new();
+			archive.by_index(index)?.read_to_end(&mut buf)?;
+			Ok(Some(buf))
+		};
+
+		let user: PackageUser =
+			parse(&read_json("account/user.json")?.ok_or("package is missing account/user.json")?)?;
+		let user = user.into_user();
+
+		let guilds: HashMap<Snowflake, String> = match read_json("servers/index.json")? {
+			| Some(index) => parse(&index)?,
+			| None => HashMap::new(),
+		};
+		let labels: HashMap<Snowflake, Option<String>> = match read_json("messages/index.json")? {
+			| Some(index) => parse(&index)?,
+			| None => HashMap::new(),
+		};
+
+		let mut channel_dirs: Vec<&str> = files
+			.keys()
+			.filter_map(|name| {
+				name.strip_prefix("messages/")?
+					.strip_suffix("/channel.json")
+			})
+			.filter(|dir| dir.starts_with('c'))
+			.collect();
+		channel_dirs.sort_unstable();
+
+		let mut channels = Vec::new();
+		for dir in channel_dirs {
+			let Some(raw) = read_json(&format!("messages/{dir}/channel.json"))? else {
+				continue;
+			};
+			let channel: PackageChannelInfo = match parse(&raw) {
+				| Ok(c) => c,
+				| Err(e) => {
+					warn!("Skipping unreadable channel {dir} in data package: {e}");
+					continue;
+				},
+			};
+
+			let messages: Vec<PackageMessage> =
+				match read_json(&format!("messages/{dir}/messages.json"))? {
+					| Some(raw) => match parse(&raw) {
+						| Ok(m) => m,
+						| Err(e) => {
+							warn!(
+								"Skipping channel {dir} with unreadable messages in data package: {e}"
+							);
+							continue;
+						},
+					},
+					| None => {
+						warn!(
+							"Skipping channel {dir} without a messages.json, csv packages are not supported"
+						);
+						continue;
+					},
+				};
+
+			let label = labels.get(&channel.id).cloned().flatten();
+			let mut messages: Vec<Message> = messages
+				.into_iter()
+				.map(|m| m.into_message(&channel, &user))
+				.collect();
+			messages.sort_by_key(|m| m.id);
+
+			channels.push(PackageChannel {
+				channel: channel.into_channel(),
+				label,
+				messages,
+			});
+		}
+
+		channels.sort_by_key(|c| std::cmp::Reverse(c.messages.last().map(|m| m.id)));
+
+		let mut guilds: Vec<UserGuild> = guilds
+			.into_iter()
+			.map(|(id, name)| UserGuild {
+				id,
+				name,
+				icon: None,
+				banner: None,
+				owner: false,
+				permissions: Permissions::NONE,
+				features: Vec::new(),
+				emojis: Vec::new(),
+				approximate_member_count: None,
+				approximate_presence_count: None,
+			})
+			.collect();
+		guilds.sort_by_key(|g| g.name.to_lowercase());
+
+		Ok(Self {
+			user,
+			guilds,
+			channels,
+		})
+	}
+
+	pub fn channel(
+		&self,
+		channel_id: Snowflake,
+	) -> Option<&PackageChannel> {
+		self.channels.iter().find(|c| c.channel.id == channel_id)
+	}
+
+	/// Case insensitive search over every message in the package, returns the matches newest first
+	///
+	/// Matches are the index of the channel and of the message in it, so they can be kept without borrowing the package
+	pub fn search(
+		&self,
+		query: &str,
+	) -> Vec<(usize, usize)> {
+		let query = query.to_lowercase();
+		let mut results: Vec<_> = self
+			.channels
+			.iter()
+			.enumerate()
+			.flat_map(|(c, channel)| {
+				channel
+					.messages
+					.iter()
+					.enumerate()
+					.filter(|(_, m)| m.content.to_lowercase().contains(&query))
+					.map(move |(m, message)| (message.id, (c, m)))
+			})
+			.collect();
+
+		results.sort_by_key(|(id, _)| std::cmp::Reverse(*id));
+		results.into_iter().map(|(_, position)| position).collect()
+	}
+}
+
+fn parse<T: DeserializeOwned>(json: &[u8]) -> Result<T, serde_json::Error> {
+	serde_json::from_slice(json)
+}
+
+/// `account/user.json`, only the fields cowcord uses
+#[derive(Deserialize)]
+struct PackageUser {
+	id: Snowflake,
+	username: String,
+	/// sent as an integer in packages
+	discriminator: u16,
+	#[serde(default)]
+	global_name: Option<String>,
+	#[serde(default)]
+	avatar_hash: Option<String>,
+	#[serde(default)]
+	email: Option<String>,
+}
+
+impl PackageUser {
+	fn into_user(self) -> User {
+		User {
+			id: self.id,
+			username: self.username,
+			// migrated accounts have a discriminator of 0, sent as "0" rather than "0000"
+			discriminator: match self.discriminator {
+				| 0 => "0".to_owned(),
+				| d => format!("{d:04}"),
+			},
+			global_name: self.global_name,
+			avatar: self.avatar_hash,
+			bot: None,
+			system: None,
+			banner: None,
+			accent_color: None,
+			public_flags: None,
+			email: Some(self.email),
+			mfa_enabled: None,
+			locale: None,
+			premium_type: None,
+		}
+	}
+}
+
+/// `messages/c<id>/channel.json`
+#[derive(Deserialize)]
+struct PackageChannelInfo {
+	id: Snowflake,
+	r#type: PackageChannelType,
+	#[serde(default)]
+	name: Option<String>,
+	#[serde(default)]
+	guild: Option<PackageGuild>,
+}
+
+impl PackageChannelInfo {
+	fn into_channel(self) -> Channel {
+		Channel {
+			id: self.id,
+			r#type: self.r#type.into_channel_type(),
+			guild_id: self.guild.map(|g| g.id),
+			position: None,
+			permission_overwrites: None,
+			name: self.name,
+			topic: None,
+			nsfw: None,
+			last_message_id: None,
+			bitrate: None,
+			user_limit: None,
+			rate_limit_per_user: None,
+			// packages only have the ids of the recipients
+			recipients: None,
+			icon: None,
+			owner_id: None,
+			parent_id: None,
+			last_pin_timestamp: None,
+			flags: None,
+		}
+	}
+}
+
+#[derive(Deserialize)]
+struct PackageGuild {
+	id: Snowflake,
+}
+
+/// older packages use the numeric channel type, newer ones use its name
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PackageChannelType {
+	Integer(ChannelType),
+	Name(String),
+}
+
+impl PackageChannelType {
+	fn into_channel_type(self) -> ChannelType {
+		match self {
+			| Self::Integer(t) => t,
+			| Self::Name(name) => match name.as_str() {
+				| "DM" => ChannelType::Dm,
+				| "GROUP_DM" => ChannelType::GroupDm,
+				| "GUILD_VOICE" => ChannelType::GuildVoice,
+				| "GUILD_CATEGORY" => ChannelType::GuildCategory,
+				| "GUILD_NEWS" | "GUILD_ANNOUNCEMENT" => ChannelType::GuildNews,
+				| "GUILD_STAGE_VOICE" => ChannelType::GuildStageVoice,
+				| "GUILD_FORUM" => ChannelType::GuildForum,
+				| "GUILD_MEDIA" => ChannelType::GuildMedia,
+				| "NEWS_THREAD" | "ANNOUNCEMENT_THREAD" => ChannelType::NewsThread,
+				| "PUBLIC_THREAD" => ChannelType::PublicThread,
+				| "PRIVATE_THREAD" => ChannelType::PrivateThread,
+				| _ => ChannelType::GuildText,
+			},
+		}
+	}
+}
+
+/// an entry in `messages/c<id>/messages.json`
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PackageMessage {
+	#[serde(rename = "ID")]
+	id: Snowflake,
+	/// `YYYY-MM-DD HH:MM:SS` in utc
+	timestamp: String,
+	#[serde(default)]
+	contents: String,
+	/// space separated attachment urls
+	#[serde(default)]
+	attachments: String,
+}
+
+impl PackageMessage {
+	fn into_message(
+		self,
+		channel: &PackageChannelInfo,
+		author: &User,
+	) -> Message {
+		let attachments = self
+			.attachments
+			.split_whitespace()
+			.map(|url| package_attachment(url, self.id))
+			.collect();
+
+		Message {
+			id: self.id,
+			channel_id: channel.id,
+			author: author.clone(),
+			content: self.contents,
+			timestamp: self.timestamp.replacen(' ', "T", 1),
+			edited_timestamp: None,
+			tts: false,
+			mention_everyone: false,
+			mentions: Vec::new(),
+			mention_roles: Vec::new(),
+			attachments,
+			embeds: Vec::new(),
+			reactions: Vec::new(),
+			nonce: None,
+			pinned: false,
+			webhook_id: None,
+			r#type: MessageType::Default,
+			flags: None,
+			message_reference: None,
+			referenced_message: None,
+			guild_id: channel.guild.as_ref().map(|g| g.id),
+		}
+	}
+}
+
+/// Builds an attachment from its cdn url, which looks like `.../attachments/<channel_id>/<attachment_id>/<filename>`
+fn package_attachment(
+	url: &str,
+	message_id: Snowflake,
+) -> Attachment {
+	let path = url.split(['?', '#']).next().unwrap_or(url);
+	let mut segments = path.rsplit('/');
+	let filename = segments.next().unwrap_or_default().to_owned();
+	let id = segments
+		.next()
+		.and_then(|id| id.parse().ok())
+		.unwrap_or(message_id);
+
+	Attachment {
+		id,
+		filename,
+		title: None,
+		description: None,
+		content_type: None,
+		size: 0,
+		url: url.to_owned(),
+		proxy_url: url.to_owned(),
+		height: None,
+		width: None,
+		duration_secs: None,
+		waveform: None,
+		flags: None,
+	}
+}
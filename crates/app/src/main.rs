@@ -9,6 +9,7 @@ use cowcord_config::{CONFIG_PATH, get_config_dir};
 use dbus_secret_service_keyring_store::Store;
 use dioxus::desktop::WindowBuilder;
 use dioxus::prelude::*;
+use discord_api::types::snowflake::Snowflake;
 use tracing::level_filters::LevelFilter;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
@@ -16,12 +17,17 @@ use tracing_subscriber::util::SubscriberInitExt;
 use windows_native_keyring_store::Store;
 
 pub mod components;
+pub mod export;
+pub mod import;
+pub mod state;
 pub mod utils;
 pub mod ws;
 
 pub mod cli;
 mod views;
-use channels::me::Me;
+use channels::guild::{GuildChannel, GuildMessage};
+use channels::layout::Channels;
+use channels::me::{Me, MeChannel, MeMessage};
 use views::*;
 
 use crate::utils::fingerprint::{FINGERPRINT, get_fingerprint};
@@ -38,8 +44,40 @@ enum Route {
 	#[route("/login")]
 	Login {},
 
-	#[route("/channels/@me")]
-	Me {},
+	#[layout(Channels)]
+		#[route("/channels/@me")]
+		Me {},
+
+		#[route("/channels/@me/:channel_id")]
+		MeChannel {
+			channel_id: Snowflake,
+		},
+
+		#[route("/channels/@me/:channel_id/:message_id")]
+		MeMessage {
+			channel_id: Snowflake,
+			message_id: Snowflake,
+		},
+
+		#[route("/channels/:guild_id/:channel_id")]
+		GuildChannel {
+			guild_id: Snowflake,
+			channel_id: Snowflake,
+		},
+
+		#[route("/channels/:guild_id/:channel_id/:message_id")]
+		GuildMessage {
+			guild_id: Snowflake,
+			channel_id: Snowflake,
+			message_id: Snowflake,
+		},
+
+		#[route("/settings/experiments")]
+		Experiments {},
+	#[end_layout]
+
+	#[route("/package")]
+	Package {},
 
     #[route("/:..route")]
     PageNotFound {
@@ -66,15 +104,6 @@ fn main() {
 			filter_handle.modify(|f| *f = log_level).unwrap();
 		}
 
-		// init dioxus config
-		let config = dioxus::desktop::Config::new()
-			.with_window(
-				WindowBuilder::new()
-					.with_maximized(true)
-					.with_title("cowcord"), // .with_decorations(false), // .with_transparent(true),
-			)
-			.with_menu(None);
-
 		// init rustls ring
 		rustls::crypto::ring::default_provider()
 			.install_default()
@@ -87,6 +116,20 @@ fn main() {
 		cowcord_config::Config::init().unwrap();
 		CONFIG.set(cowcord_config::Config::get().unwrap()).unwrap();
 
+		// subcommands run in the terminal instead of opening the app
+		if let Some(command) = args.command {
+			std::process::exit(cli::run_command(command, args.json));
+		}
+
+		// init dioxus config
+		let config = dioxus::desktop::Config::new()
+			.with_window(
+				WindowBuilder::new()
+					.with_maximized(true)
+					.with_title("cowcord"), // .with_decorations(false), // .with_transparent(true),
+			)
+			.with_menu(None);
+
 		LaunchBuilder::desktop().with_cfg(config).launch(App);
 	}
 }
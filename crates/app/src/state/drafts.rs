@@ -0,0 +1,37 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use cowcord_config::CONFIG_PATH;
+use dioxus::prelude::*;
+use discord_api::types::snowflake::Snowflake;
+
+/// Unsent message content by channel id, saved so it's still there after restarting
+pub static DRAFTS: GlobalSignal<HashMap<Snowflake, String>> = Signal::global(load_drafts);
+
+fn drafts_path() -> Option<PathBuf> {
+	CONFIG_PATH.get().map(|dir| dir.join("drafts.json"))
+}
+
+fn load_drafts() -> HashMap<Snowflake, String> {
+	drafts_path()
+		.and_then(|path| fs::read_to_string(path).ok())
+		.and_then(|raw| serde_json::from_str(&raw).ok())
+		.unwrap_or_default()
+}
+
+/// Writes the drafts to disk, empty drafts are left out
+pub fn save_drafts() -> Result<(), Box<dyn std::error::Error>> {
+	let Some(path) = drafts_path() else {
+		return Ok(());
+	};
+
+	let drafts = DRAFTS.peek();
+	let drafts: HashMap<&Snowflake, &String> = drafts
+		.iter()
+		.filter(|(_, content)| !content.trim().is_empty())
+		.collect();
+	fs::write(path, serde_json::to_string(&drafts)?)?;
+
+	Ok(())
+}
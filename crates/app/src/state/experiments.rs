@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+use dioxus::prelude::*;
+use discord_api::ApiResponse;
+use discord_api::endpoints::experiments::{
+	GET_EXPERIMENT_ASSIGNMENTS,
+	GetExperimentAssignmentsQuery,
+	GetExperimentAssignmentsResponse,
+};
+
+use crate::CONFIG;
+use crate::state::STATE;
+use crate::utils::request::{AutoHandle, BaseUrl, RequestClient};
+
+/// Buckets set on the experiments page, keyed by the experiment's hash, used instead of the assigned ones
+pub static EXPERIMENT_OVERRIDES: GlobalSignal<HashMap<i64, i64>> = Signal::global(|| {
+	CONFIG
+		.get()
+		.map(|c| c.experiment_overrides.clone())
+		.unwrap_or_default()
+});
+
+/// The bucket set locally for the experiment with the hash
+pub fn override_bucket(hash: i64) -> Option<i64> {
+	EXPERIMENT_OVERRIDES.read().get(&hash).copied()
+}
+
+/// Sets the bucket used for the experiment, or clears it with `None`, and saves it to the config
+pub fn set_override(
+	hash: i64,
+	bucket: Option<i64>,
+) -> Result<(), Box<dyn std::error::Error>> {
+	match bucket {
+		| Some(bucket) => EXPERIMENT_OVERRIDES.write().insert(hash, bucket),
+		| None => EXPERIMENT_OVERRIDES.write().remove(&hash),
+	};
+
+	let mut config = cowcord_config::Config::get()?;
+	config.experiment_overrides = EXPERIMENT_OVERRIDES.peek().clone();
+	config.set()
+}
+
+/// Fetches the user's experiment assignments and the guild experiment rollouts, replacing the ones from READY
+pub async fn fetch_experiments() -> Result<(), Box<dyn std::error::Error>> {
+	let client = RequestClient::new(BaseUrl::Discord, false);
+	let query = GetExperimentAssignmentsQuery {
+		with_guild_experiments: Some(true),
+		platform: None,
+	};
+
+	let response = client
+		.get::<()>(&GET_EXPERIMENT_ASSIGNMENTS(&query), None)
+		.await?;
+	match response
+		.with_auto_handle::<GetExperimentAssignmentsResponse>()
+		.await?
+	{
+		| ApiResponse::Success(response) => {
+			let mut state = STATE.write();
+			state.experiments = response.assignments;
+			if let Some(guild_experiments) = response.guild_experiments {
+				state.guild_experiments = guild_experiments;
+			}
+			Ok(())
+		},
+		| ApiResponse::Error(e) => Err(format!("{e:?}").into()),
+	}
+}
@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+
+use dioxus::prelude::*;
+use discord_api::ApiResponse;
+use discord_api::endpoints::messages::{
+	EDIT_MESSAGE,
+	EditMessageRequest,
+	GET_MESSAGES,
+	GetMessagesQuery,
+};
+use discord_api::types::message::Message;
+use discord_api::types::snowflake::Snowflake;
+use discord_api::types::ws::gateway::GatewayEvent;
+
+use crate::utils::request::{AutoHandle, BaseUrl, RequestClient, with_rate_limit};
+
+/// The loaded history of each channel that has been opened
+pub static MESSAGES: GlobalSignal<HashMap<Snowflake, MessageWindow>> = Signal::global(HashMap::new);
+
+pub const PAGE_SIZE: u8 = 50;
+/// the most messages kept per channel, messages furthest from the newly loaded page are dropped past this
+const MAX_LOADED: usize = 250;
+
+/// A contiguous slice of a channel's history
+#[derive(Debug, Clone, Default)]
+pub struct MessageWindow {
+	/// oldest first
+	pub messages: Vec<Message>,
+	/// whether there are older messages that aren't loaded
+	pub has_before: bool,
+	/// whether there are newer messages that aren't loaded, `false` means the window reaches the present
+	pub has_after: bool,
+}
+
+impl MessageWindow {
+	pub fn get(
+		&self,
+		id: Snowflake,
+	) -> Option<&Message> {
+		self.index_of(id).map(|i| &self.messages[i])
+	}
+
+	pub fn index_of(
+		&self,
+		id: Snowflake,
+	) -> Option<usize> {
+		self.messages.binary_search_by_key(&id, |m| m.id).ok()
+	}
+
+	pub fn contains(
+		&self,
+		id: Snowflake,
+	) -> bool {
+		self.index_of(id).is_some()
+	}
+
+	/// Adds a page of older messages, dropping the newest ones if there are too many loaded
+	fn prepend(
+		&mut self,
+		mut page: Vec<Message>,
+		has_before: bool,
+	) {
+		page.retain(|m| self.messages.first().is_none_or(|first| m.id < first.id));
+		page.append(&mut self.messages);
+		self.messages = page;
+		self.has_before = has_before;
+
+		if self.messages.len() > MAX_LOADED {
+			self.messages.truncate(MAX_LOADED);
+			self.has_after = true;
+		}
+	}
+
+	/// Adds a page of newer messages, dropping the oldest ones if there are too many loaded
+	fn append(
+		&mut self,
+		mut page: Vec<Message>,
+		has_after: bool,
+	) {
+		page.retain(|m| self.messages.last().is_none_or(|last| m.id > last.id));
+		self.messages.append(&mut page);
+		self.has_after = has_after;
+
+		if let Some(excess) = self.messages.len().checked_sub(MAX_LOADED) {
+			self.messages.drain(..excess);
+			self.has_before = true;
+		}
+	}
+}
+
+/// Which part of the history to load
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HistoryFetch {
+	/// the newest messages, replacing whatever was loaded
+	Latest,
+	/// the page before the oldest loaded message
+	Before,
+	/// the page after the newest loaded message
+	After,
+	/// the messages around a message, replacing whatever was loaded
+	Around(Snowflake),
+}
+
+/// Loads a page of the channel's history into [`MESSAGES`]
+pub async fn load_messages(
+	channel_id: Snowflake,
+	fetch: HistoryFetch,
+) -> Result<(), Box<dyn std::error::Error>> {
+	let (first, last) = MESSAGES
+		.peek()
+		.get(&channel_id)
+		.map(|w| {
+			(
+				w.messages.first().map(|m| m.id),
+				w.messages.last().map(|m| m.id),
+			)
+		})
+		.unwrap_or_default();
+
+	let query = match fetch {
+		| HistoryFetch::Latest => GetMessagesQuery::default(),
+		| HistoryFetch::Before => GetMessagesQuery {
+			before: first,
+			..Default::default()
+		},
+		| HistoryFetch::After => GetMessagesQuery {
+			after: last,
+			..Default::default()
+		},
+		| HistoryFetch::Around(id) => GetMessagesQuery {
+			around: Some(id),
+			..Default::default()
+		},
+	};
+	let query = GetMessagesQuery {
+		limit: Some(PAGE_SIZE),
+		..query
+	};
+
+	let client = RequestClient::new(BaseUrl::Discord, false);
+	let endpoint = GET_MESSAGES(channel_id, &query);
+	let endpoint = endpoint.as_str();
+
+	let mut page: Vec<Message> = match with_rate_limit(|| client.get::<()>(endpoint, None))
+		.await?
+		.with_auto_handle()
+		.await?
+	{
+		| ApiResponse::Success(p) => p,
+		| ApiResponse::Error(e) => return Err(format!("{e:?}").into()),
+	};
+	// discord returns pages newest first regardless of direction
+	page.sort_by_key(|m| m.id);
+	let full = page.len() == PAGE_SIZE as usize;
+
+	let mut messages = MESSAGES.write();
+	let window = messages.entry(channel_id).or_default();
+
+	match fetch {
+		| HistoryFetch::Latest => {
+			*window = MessageWindow {
+				messages: page,
+				has_before: full,
+				has_after: false,
+			};
+		},
+		| HistoryFetch::Before => window.prepend(page, full),
+		| HistoryFetch::After => window.append(page, full),
+		| HistoryFetch::Around(id) => {
+			// the page is split evenly around the message, so either side being short means it's the end
+			let before = page.iter().filter(|m| m.id < id).count();
+			let after = page.iter().filter(|m| m.id > id).count();
+			let half = PAGE_SIZE as usize / 2;
+			*window = MessageWindow {
+				messages: page,
+				has_before: before >= half,
+				has_after: after >= half - 1,
+			};
+		},
+	}
+
+	Ok(())
+}
+
+/// Adds a new message to the end of its channel, if the loaded messages reach the present
+pub fn insert_message(message: Message) {
+	let at_present = MESSAGES
+		.peek()
+		.get(&message.channel_id)
+		.is_some_and(|w| !w.has_after && !w.contains(message.id));
+	if at_present && let Some(window) = MESSAGES.write().get_mut(&message.channel_id) {
+		window.append(vec![message], false);
+	}
+}
+
+/// Replaces a loaded message with its updated version
+pub fn update_message(message: Message) {
+	let index = MESSAGES
+		.peek()
+		.get(&message.channel_id)
+		.and_then(|w| w.index_of(message.id));
+	if let Some(index) = index
+		&& let Some(window) = MESSAGES.write().get_mut(&message.channel_id)
+	{
+		window.messages[index] = message;
+	}
+}
+
+/// Edits one of the user's messages, updating it once discord accepts the edit
+pub async fn edit_message(
+	channel_id: Snowflake,
+	message_id: Snowflake,
+	content: String,
+) -> Result<(), Box<dyn std::error::Error>> {
+	let client = RequestClient::new(BaseUrl::Discord, false);
+	let endpoint = EDIT_MESSAGE(channel_id, message_id);
+	let request = EditMessageRequest {
+		content: Some(content),
+		..Default::default()
+	};
+
+	match with_rate_limit(|| client.patch(&endpoint, Some(&request)))
+		.await?
+		.with_auto_handle()
+		.await?
+	{
+		| ApiResponse::Success(message) => update_message(message),
+		| ApiResponse::Error(e) => return Err(format!("{e:?}").into()),
+	}
+
+	Ok(())
+}
+
+/// Keeps the loaded windows up to date with messages from the gateway
+///
+/// Only windows reaching the present get new messages, older windows pick them up when paging forwards
+pub fn apply_message_event(event: &GatewayEvent) {
+	match event {
+		| GatewayEvent::MessageCreate(message) => insert_message(*message.clone()),
+		| GatewayEvent::MessageUpdate(update) => {
+			let merged = MESSAGES
+				.peek()
+				.get(&update.channel_id)
+				.and_then(|w| w.get(update.id))
+				.map(|message| update.apply(message));
+			match merged {
+				| Some(Ok(message)) => update_message(message),
+				| Some(Err(e)) => warn!("failed to apply an update to message {}: {e}", update.id),
+				| None => {},
+			}
+		},
+		| GatewayEvent::MessageDelete(delete) => {
+			let index = MESSAGES
+				.peek()
+				.get(&delete.channel_id)
+				.and_then(|w| w.index_of(delete.id));
+			if let Some(index) = index
+				&& let Some(window) = MESSAGES.write().get_mut(&delete.channel_id)
+			{
+				window.messages.remove(index);
+			}
+		},
+		| _ => {},
+	}
+}
@@ -0,0 +1,474 @@
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use dioxus::prelude::*;
+use discord_api::endpoints::experiments::{GuildExperiment, UserExperiment};
+use discord_api::types::channel::{Channel, ChannelType};
+use discord_api::types::guild::{Guild, GuildMember};
+use discord_api::types::message::Message;
+use discord_api::types::permissions::{Permissions, channel_permissions};
+use discord_api::types::read_state::ReadState;
+use discord_api::types::rollout::{experiment_hash, guild_assignment};
+use discord_api::types::settings::{GuildFolder, UserSettings};
+use discord_api::types::snowflake::Snowflake;
+use discord_api::types::user::User;
+use discord_api::types::ws::gateway::GatewayEvent;
+use tracing::trace;
+
+use crate::state::experiments::override_bucket;
+
+pub mod drafts;
+pub mod experiments;
+pub mod messages;
+pub mod outbox;
+pub mod reactions;
+pub mod read_states;
+pub mod typing;
+pub mod uploads;
+
+/// Everything received from the gateway, kept up to date while the channels view is open
+pub static STATE: GlobalSignal<ClientState> = Signal::global(ClientState::default);
+
+#[derive(Debug, Clone, Default)]
+pub struct ClientState {
+	/// the logged in user, `None` until READY is received
+	pub user: Option<User>,
+	pub guilds: Vec<Guild>,
+	pub private_channels: Vec<Channel>,
+	pub settings: UserSettings,
+	/// read states by channel id
+	pub read_states: HashMap<Snowflake, ReadState>,
+	/// the user's experiment buckets, assigned by discord
+	pub experiments: Vec<UserExperiment>,
+	/// guild experiment rollouts, assigned locally
+	pub guild_experiments: Vec<GuildExperiment>,
+}
+
+impl ClientState {
+	pub fn is_ready(&self) -> bool {
+		self.user.is_some()
+	}
+
+	pub fn apply(
+		&mut self,
+		event: GatewayEvent,
+	) {
+		match event {
+			| GatewayEvent::Ready(ready) => {
+				let ready = *ready;
+				self.user = Some(ready.user);
+				self.guilds = ready
+					.guilds
+					.into_iter()
+					.map(with_channel_guild_ids)
+					.collect();
+				self.private_channels = ready.private_channels;
+				self.settings = ready.user_settings.unwrap_or_default();
+				self.read_states = ready.read_state.into_iter().map(|r| (r.id, r)).collect();
+				self.experiments = ready.experiments;
+				self.guild_experiments = ready.guild_experiments;
+			},
+			| GatewayEvent::GuildCreate(guild) => {
+				let guild = with_channel_guild_ids(*guild);
+				match self.guild_mut(guild.id) {
+					| Some(existing) => *existing = guild,
+					| None => self.guilds.push(guild),
+				}
+			},
+			| GatewayEvent::GuildUpdate(update) => {
+				if let Some(guild) = self.guild_mut(update.id) {
+					guild.name = update.name;
+					guild.icon = update.icon;
+					guild.owner_id = update.owner_id;
+					guild.roles = update.roles;
+					guild.features = update.features;
+					guild.premium_tier = update.premium_tier;
+					guild.vanity_url_code = update.vanity_url_code;
+					guild.hub_type = update.hub_type;
+					guild.system_channel_flags = update.system_channel_flags;
+				}
+			},
+			| GatewayEvent::GuildDelete(delete) => {
+				if delete.unavailable == Some(true) {
+					if let Some(guild) = self.guild_mut(delete.id) {
+						guild.unavailable = Some(true);
+					}
+				} else {
+					self.guilds.retain(|g| g.id != delete.id);
+				}
+			},
+			| GatewayEvent::GuildRoleCreate(update) | GatewayEvent::GuildRoleUpdate(update) => {
+				if let Some(guild) = self.guild_mut(update.guild_id) {
+					match guild.roles.iter_mut().find(|r| r.id == update.role.id) {
+						| Some(role) => *role = update.role,
+						| None => guild.roles.push(update.role),
+					}
+				}
+			},
+			| GatewayEvent::GuildRoleDelete(delete) => {
+				if let Some(guild) = self.guild_mut(delete.guild_id) {
+					guild.roles.retain(|r| r.id != delete.role_id);
+				}
+			},
+			| GatewayEvent::GuildEmojisUpdate(update) => {
+				if let Some(guild) = self.guild_mut(update.guild_id) {
+					guild.emojis = update.emojis;
+				}
+			},
+			| GatewayEvent::GuildMemberUpdate(update) => {
+				let user_id = update.member.user.as_ref().map(|u| u.id);
+				let is_me = user_id.is_some() && user_id == self.user.as_ref().map(|u| u.id);
+				if let Some(guild) = self.guild_mut(update.guild_id) {
+					match guild
+						.members
+						.iter_mut()
+						.find(|m| m.user.as_ref().map(|u| u.id) == user_id)
+					{
+						| Some(member) => *member = update.member,
+						// only the members already sent with the guild are kept track of, and the user
+						| None if is_me => guild.members.push(update.member),
+						| None => {},
+					}
+				}
+			},
+			| GatewayEvent::ChannelCreate(channel) | GatewayEvent::ChannelUpdate(channel) => {
+				let channels = match channel.guild_id {
+					| Some(guild_id) => match self.guild_mut(guild_id) {
+						| Some(guild) => &mut guild.channels,
+						| None => return,
+					},
+					| None => &mut self.private_channels,
+				};
+				match channels.iter_mut().find(|c| c.id == channel.id) {
+					| Some(existing) => *existing = *channel,
+					| None => channels.push(*channel),
+				}
+			},
+			| GatewayEvent::ChannelDelete(channel) => {
+				match channel.guild_id.and_then(|id| self.guild_mut(id)) {
+					| Some(guild) => guild.channels.retain(|c| c.id != channel.id),
+					| None => self.private_channels.retain(|c| c.id != channel.id),
+				}
+			},
+			| GatewayEvent::VoiceStateUpdate(state) => {
+				if let Some(guild) = state.guild_id.and_then(|id| self.guild_mut(id)) {
+					guild.voice_states.retain(|s| s.user_id != state.user_id);
+					if state.channel_id.is_some() {
+						guild.voice_states.push(*state);
+					}
+				}
+			},
+			| GatewayEvent::MessageCreate(message) => {
+				if let Some(channel) = self.channel_mut(message.channel_id) {
+					channel.last_message_id = Some(message.id);
+				}
+
+				// sending a message marks the channel as read
+				if self
+					.user
+					.as_ref()
+					.is_some_and(|u| u.id == message.author.id)
+				{
+					self.mark_read(message.channel_id, message.id);
+				} else if self.mentions_user(&message) {
+					self.read_state_mut(message.channel_id).mention_count += 1;
+				}
+			},
+			| GatewayEvent::MessageAck(ack) => {
+				let read_state = self.read_state_mut(ack.channel_id);
+				read_state.last_message_id = Some(ack.message_id);
+				read_state.mention_count = ack.mention_count;
+			},
+			| GatewayEvent::MessageUpdate(_)
+			| GatewayEvent::MessageDelete(_)
+			| GatewayEvent::MessageReactionAdd(_)
+			| GatewayEvent::MessageReactionRemove(_)
+			| GatewayEvent::MessageReactionRemoveAll(_)
+			| GatewayEvent::MessageReactionRemoveEmoji(_)
+			| GatewayEvent::TypingStart(_) => {},
+			| GatewayEvent::UserSettingsUpdate(settings) => {
+				if settings.guild_folders.is_some() {
+					self.settings.guild_folders = settings.guild_folders;
+				}
+				if settings.guild_positions.is_some() {
+					self.settings.guild_positions = settings.guild_positions;
+				}
+			},
+			| GatewayEvent::Resumed => {},
+			| GatewayEvent::Unknown {
+				name, ..
+			} => trace!("ignoring unhandled {name} event"),
+		}
+	}
+
+	pub fn guild(
+		&self,
+		id: Snowflake,
+	) -> Option<&Guild> {
+		self.guilds.iter().find(|g| g.id == id)
+	}
+
+	fn guild_mut(
+		&mut self,
+		id: Snowflake,
+	) -> Option<&mut Guild> {
+		self.guilds.iter_mut().find(|g| g.id == id)
+	}
+
+	/// finds a guild or private channel
+	pub fn channel(
+		&self,
+		id: Snowflake,
+	) -> Option<&Channel> {
+		self.guilds
+			.iter()
+			.flat_map(|g| &g.channels)
+			.chain(&self.private_channels)
+			.find(|c| c.id == id)
+	}
+
+	fn channel_mut(
+		&mut self,
+		id: Snowflake,
+	) -> Option<&mut Channel> {
+		self.guilds
+			.iter_mut()
+			.flat_map(|g| &mut g.channels)
+			.chain(&mut self.private_channels)
+			.find(|c| c.id == id)
+	}
+
+	/// moves the channel's read state up to the message, clearing its mentions
+	pub fn mark_read(
+		&mut self,
+		channel_id: Snowflake,
+		message_id: Snowflake,
+	) {
+		let read_state = self.read_state_mut(channel_id);
+		read_state.last_message_id = Some(message_id);
+		read_state.mention_count = 0;
+	}
+
+	fn read_state_mut(
+		&mut self,
+		channel_id: Snowflake,
+	) -> &mut ReadState {
+		self.read_states
+			.entry(channel_id)
+			.or_insert_with(|| ReadState {
+				id: channel_id,
+				last_message_id: None,
+				mention_count: 0,
+				last_pin_timestamp: None,
+			})
+	}
+
+	/// whether the message counts towards the channel's mention badge, every message in private channels does
+	fn mentions_user(
+		&self,
+		message: &Message,
+	) -> bool {
+		let Some(user) = &self.user else {
+			return false;
+		};
+		let Some(channel) = self.channel(message.channel_id) else {
+			return false;
+		};
+		let Some(guild) = channel.guild_id.and_then(|id| self.guild(id)) else {
+			return true;
+		};
+
+		message.mention_everyone
+			|| message.mentions.iter().any(|u| u.id == user.id)
+			|| self
+				.member(guild)
+				.is_some_and(|m| message.mention_roles.iter().any(|r| m.roles.contains(r)))
+	}
+
+	/// the logged in user's member in the guild
+	pub fn member<'a>(
+		&self,
+		guild: &'a Guild,
+	) -> Option<&'a GuildMember> {
+		let user_id = self.user.as_ref()?.id;
+		guild
+			.members
+			.iter()
+			.find(|m| m.user.as_ref().is_some_and(|u| u.id == user_id))
+	}
+
+	/// the guild list as shown in the rail, guilds outside of a folder are folders without an id
+	///
+	/// guilds missing from the user's folders (e.g. just joined) come first, like the official client
+	pub fn guild_folders(&self) -> Vec<GuildFolder> {
+		let folders = self.settings.guild_folders.as_deref().unwrap_or_default();
+
+		let mut list: Vec<GuildFolder> = self
+			.guilds
+			.iter()
+			.filter(|g| !folders.iter().any(|f| f.guild_ids.contains(&g.id)))
+			.map(|g| GuildFolder {
+				id: None,
+				name: None,
+				color: None,
+				guild_ids: vec![g.id],
+			})
+			.collect();
+
+		for folder in folders {
+			let guild_ids: Vec<_> = folder
+				.guild_ids
+				.iter()
+				.copied()
+				.filter(|id| self.guild(*id).is_some())
+				.collect();
+
+			if !guild_ids.is_empty() {
+				list.push(GuildFolder {
+					guild_ids,
+					..folder.clone()
+				});
+			}
+		}
+
+		list
+	}
+
+	/// what the logged in user can do in the channel, from their roles and the channel's overwrites
+	///
+	/// everything is allowed in private channels, threads use their parent channel's overwrites
+	pub fn permissions(
+		&self,
+		channel: &Channel,
+	) -> Permissions {
+		let Some(guild_id) = channel.guild_id else {
+			return Permissions::ALL;
+		};
+		let (Some(user), Some(guild)) = (&self.user, self.guild(guild_id)) else {
+			return Permissions::NONE;
+		};
+		let Some(member) = self.member(guild) else {
+			return Permissions::NONE;
+		};
+
+		let parent = channel.parent_id.and_then(|id| self.channel(id));
+		let now = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.unwrap_or_default()
+			.as_millis() as u64;
+
+		channel_permissions(guild, channel, parent, member, user.id, now)
+	}
+
+	pub fn can_view(
+		&self,
+		channel: &Channel,
+	) -> bool {
+		self.permissions(channel)
+			.contains(Permissions::VIEW_CHANNEL)
+	}
+
+	/// whether the logged in user can send messages in the channel, threads have a permission of their own
+	pub fn can_send(
+		&self,
+		channel: &Channel,
+	) -> bool {
+		let permission = if channel.r#type.is_thread() {
+			Permissions::SEND_MESSAGES_IN_THREADS
+		} else {
+			Permissions::SEND_MESSAGES
+		};
+		self.permissions(channel).contains(permission)
+	}
+
+	/// the user's bucket in the experiment with the hash, `None` if they aren't in it
+	///
+	/// Discord only sends the hashes of user experiments, [`experiment_hash`] gets it from a name.
+	/// A/A tests give the control bucket, like the official client, and local overrides win over both
+	pub fn user_bucket(
+		&self,
+		hash: i64,
+	) -> Option<i64> {
+		if let Some(bucket) = override_bucket(hash) {
+			return Some(bucket);
+		}
+
+		self.experiments
+			.iter()
+			.find(|e| e.hash == hash)
+			.map(|e| e.effective_bucket())
+	}
+
+	/// the guild's bucket in the experiment, `None` if the guild isn't in it
+	///
+	/// A/A tests give the control bucket, like the official client, and local overrides win over both, for every guild
+	pub fn guild_bucket(
+		&self,
+		name: &str,
+		guild_id: Snowflake,
+	) -> Option<i64> {
+		if let Some(bucket) = override_bucket(experiment_hash(name)) {
+			return Some(bucket);
+		}
+
+		let guild = self.guild(guild_id)?;
+		let now = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.unwrap_or_default()
+			.as_millis() as u64;
+
+		guild_assignment(&self.guild_experiments, name, &guild.into(), now)
+			.map(|a| a.effective_bucket())
+	}
+
+	/// whether the channel has messages newer than the last one the user read
+	pub fn is_unread(
+		&self,
+		channel: &Channel,
+	) -> bool {
+		if channel.r#type.is_voice() || channel.r#type == ChannelType::GuildCategory {
+			return false;
+		}
+
+		// channels without a read state have never been opened, the official client treats them as read
+		match (channel.last_message_id, self.read_states.get(&channel.id)) {
+			| (Some(last_message), Some(read_state)) => read_state
+				.last_message_id
+				.is_none_or(|read| last_message > read),
+			| _ => false,
+		}
+	}
+
+	pub fn mention_count(
+		&self,
+		channel_id: Snowflake,
+	) -> u32 {
+		self.read_states
+			.get(&channel_id)
+			.map_or(0, |r| r.mention_count)
+	}
+
+	/// whether any channel the user can see in the guild is unread, and the total mentions across them
+	pub fn guild_unread(
+		&self,
+		guild: &Guild,
+	) -> (bool, u32) {
+		guild.channels.iter().filter(|c| self.can_view(c)).fold(
+			(false, 0),
+			|(unread, mentions), c| {
+				(
+					unread || self.is_unread(c),
+					mentions + self.mention_count(c.id),
+				)
+			},
+		)
+	}
+}
+
+/// channels in guild payloads don't include their guild id
+fn with_channel_guild_ids(mut guild: Guild) -> Guild {
+	for channel in &mut guild.channels {
+		channel.guild_id = Some(guild.id);
+	}
+	guild
+}
@@ -0,0 +1,458 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use cowcord_config::CONFIG_PATH;
+use dioxus::prelude::*;
+use discord_api::ApiResponse;
+use discord_api::endpoints::messages::{
+	AllowedMentions,
+	CREATE_MESSAGE,
+	CreateMessageRequest,
+	PartialAttachment,
+};
+use discord_api::types::message::{Attachment, Message, MessageReference, MessageType, Nonce};
+use discord_api::types::snowflake::Snowflake;
+use discord_api::types::ws::gateway::GatewayEvent;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+use wreq::Response;
+use wreq::multipart::{Form, Part};
+
+use crate::state::STATE;
+use crate::state::messages::{MESSAGES, insert_message};
+use crate::state::uploads::{UPLOAD_PROGRESS, delete_uploads, request_upload_urls, upload_files};
+use crate::utils::request::{AutoHandle, BaseUrl, RequestClient, with_rate_limit};
+
+/// Messages sent from this client that discord hasn't confirmed yet, shown at the end of their channel
+///
+/// Saved to disk so messages that couldn't be sent before closing the app are sent the next time it's opened
+pub static OUTBOX: GlobalSignal<Vec<PendingMessage>> = Signal::global(load_outbox);
+
+/// Wakes the outbox worker when there's something new to send or the gateway reconnects
+static WAKE: Notify = Notify::const_new();
+
+/// the increment of the last nonce, so nonces made in the same millisecond are different
+static NONCE_INCREMENT: AtomicU64 = AtomicU64::new(0);
+
+/// how many times a message is tried before it's marked as failed
+const MAX_ATTEMPTS: u32 = 10;
+/// the longest wait between attempts, in seconds
+const MAX_BACKOFF: u64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SendState {
+	/// queued or being sent
+	Sending,
+	/// the last attempt hit a network or server error, it's tried again after a while
+	Waiting,
+	/// discord refused the message or it ran out of attempts, the user can retry or delete it
+	Failed,
+}
+
+/// A file picked in the composer to send with the next message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueuedFile {
+	pub path: PathBuf,
+	pub filename: String,
+	pub size: u64,
+	/// hides the file behind a spoiler
+	#[serde(default)]
+	pub spoiler: bool,
+	/// alt text
+	#[serde(default)]
+	pub description: Option<String>,
+}
+
+impl QueuedFile {
+	/// The name the file is uploaded as, discord marks files starting with `SPOILER_` as spoilers
+	pub fn upload_name(&self) -> String {
+		if self.spoiler {
+			format!("SPOILER_{}", self.filename)
+		} else {
+			self.filename.clone()
+		}
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingMessage {
+	/// sent as the message's nonce, also used as the id of the echo
+	pub nonce: Snowflake,
+	pub channel_id: Snowflake,
+	pub request: CreateMessageRequest,
+	pub files: Vec<QueuedFile>,
+	pub state: SendState,
+	/// attempts since it was queued or the user last retried it
+	#[serde(default)]
+	pub attempts: u32,
+	/// how the message will look once sent
+	pub echo: Message,
+}
+
+/// What the message replies to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplyTo {
+	pub message_id: Snowflake,
+	/// whether the author of the replied message gets pinged
+	pub mention: bool,
+}
+
+/// A snowflake for the current time, like the official client uses for nonces
+///
+/// The low 12 bits are an increment, like in snowflakes made by discord
+pub fn generate_nonce() -> Snowflake {
+	let now = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map_or(0, |d| d.as_millis() as u64);
+	let increment = NONCE_INCREMENT.fetch_add(1, Ordering::Relaxed) & 0xfff;
+	Snowflake(Snowflake::from_timestamp(now).0 | increment)
+}
+
+/// Shows the message in the channel straight away and queues it to be sent
+pub fn queue_message(
+	channel_id: Snowflake,
+	content: String,
+	reply_to: Option<ReplyTo>,
+	files: Vec<QueuedFile>,
+) {
+	let Some(author) = STATE.peek().user.clone() else {
+		return;
+	};
+	let nonce = generate_nonce();
+
+	let message_reference = reply_to.map(|reply| MessageReference {
+		r#type: None,
+		message_id: Some(reply.message_id),
+		channel_id: Some(channel_id),
+		guild_id: None,
+		fail_if_not_exists: Some(false),
+	});
+	let referenced_message = reply_to.map(|reply| {
+		MESSAGES
+			.peek()
+			.get(&channel_id)
+			.and_then(|w| w.get(reply.message_id))
+			.cloned()
+			.map(Box::new)
+	});
+
+	let request = CreateMessageRequest {
+		content: Some(content.clone()),
+		nonce: Some(Nonce::String(nonce.to_string())),
+		// retries after a lost response would otherwise send the message twice
+		enforce_nonce: Some(true),
+		message_reference: message_reference.clone(),
+		attachments: (!files.is_empty()).then(|| {
+			files
+				.iter()
+				.enumerate()
+				.map(|(i, file)| PartialAttachment {
+					id: i.to_string(),
+					filename: file.upload_name(),
+					description: file.description.clone(),
+					uploaded_filename: None,
+				})
+				.collect()
+		}),
+		// everything can still be pinged, only the replied user is left out if they shouldn't be
+		allowed_mentions: reply_to
+			.filter(|reply| !reply.mention)
+			.map(|_| AllowedMentions {
+				parse: vec![
+					"users".to_owned(),
+					"roles".to_owned(),
+					"everyone".to_owned(),
+				],
+				replied_user: false,
+			}),
+		..Default::default()
+	};
+
+	let echo = Message {
+		id: nonce,
+		channel_id,
+		author,
+		content,
+		timestamp: String::new(),
+		edited_timestamp: None,
+		tts: false,
+		mention_everyone: false,
+		mentions: Vec::new(),
+		mention_roles: Vec::new(),
+		attachments: files
+			.iter()
+			.enumerate()
+			.map(|(i, file)| Attachment {
+				id: Snowflake(i as u64),
+				filename: file.filename.clone(),
+				title: None,
+				description: file.description.clone(),
+				content_type: None,
+				size: file.size,
+				url: String::new(),
+				proxy_url: String::new(),
+				height: None,
+				width: None,
+				duration_secs: None,
+				waveform: None,
+				flags: None,
+			})
+			.collect(),
+		embeds: Vec::new(),
+		reactions: Vec::new(),
+		nonce: request.nonce.clone(),
+		pinned: false,
+		webhook_id: None,
+		r#type: if reply_to.is_some() {
+			MessageType::Reply
+		} else {
+			MessageType::Default
+		},
+		flags: None,
+		message_reference,
+		referenced_message,
+		guild_id: None,
+	};
+
+	OUTBOX.write().push(PendingMessage {
+		nonce,
+		channel_id,
+		request,
+		files,
+		state: SendState::Sending,
+		attempts: 0,
+		echo,
+	});
+	save_outbox();
+	WAKE.notify_one();
+}
+
+/// Sends a failed message again
+pub fn retry_message(nonce: Snowflake) {
+	if let Some(pending) = OUTBOX.write().iter_mut().find(|p| p.nonce == nonce) {
+		pending.state = SendState::Sending;
+		pending.attempts = 0;
+	}
+	save_outbox();
+	WAKE.notify_one();
+}
+
+/// Removes a message that hasn't been sent from its channel
+pub fn discard_message(nonce: Snowflake) {
+	OUTBOX.write().retain(|p| p.nonce != nonce);
+	save_outbox();
+}
+
+/// Drops every unsent message, they belong to the account that's logging out
+pub fn clear_outbox() {
+	OUTBOX.write().clear();
+	save_outbox();
+}
+
+/// Removes messages from the outbox once they come back through the gateway, and retries waiting messages after reconnecting
+pub fn apply_outbox_event(event: &GatewayEvent) {
+	match event {
+		| GatewayEvent::MessageCreate(message) => {
+			let Some(nonce) = message.nonce.as_ref().map(|n| n.to_string()) else {
+				return;
+			};
+			let sent = OUTBOX
+				.peek()
+				.iter()
+				.any(|p| p.channel_id == message.channel_id && p.nonce.to_string() == nonce);
+			if sent {
+				OUTBOX
+					.write()
+					.retain(|p| p.channel_id != message.channel_id || p.nonce.to_string() != nonce);
+				save_outbox();
+			}
+		},
+		| GatewayEvent::Ready(_) | GatewayEvent::Resumed => WAKE.notify_one(),
+		| _ => {},
+	}
+}
+
+/// Sends queued messages one at a time, so they arrive in the order they were sent
+///
+/// Network and server errors are retried with an increasing delay, cut short when the gateway reconnects
+pub async fn run_outbox() {
+	loop {
+		let next = OUTBOX
+			.peek()
+			.iter()
+			.find(|p| p.state != SendState::Failed)
+			.cloned();
+		let Some(pending) = next else {
+			WAKE.notified().await;
+			continue;
+		};
+
+		let attempts = pending.attempts + 1;
+		if let Some(queued) = OUTBOX.write().iter_mut().find(|p| p.nonce == pending.nonce) {
+			queued.state = SendState::Sending;
+			queued.attempts = attempts;
+		}
+
+		let result = create_message(&pending).await;
+		UPLOAD_PROGRESS.write().remove(&pending.nonce);
+
+		match result {
+			| Ok(message) => {
+				OUTBOX.write().retain(|p| p.nonce != pending.nonce);
+				insert_message(message);
+			},
+			| Err(SendError::Transient(e)) if attempts < MAX_ATTEMPTS => {
+				let backoff = Duration::from_secs(2u64.pow(attempts - 1).min(MAX_BACKOFF));
+				warn!(
+					"failed to send message: {e}, retrying in {}s",
+					backoff.as_secs()
+				);
+				set_state(pending.nonce, SendState::Waiting);
+				save_outbox();
+
+				tokio::select! {
+					_ = tokio::time::sleep(backoff) => {},
+					_ = WAKE.notified() => {},
+				}
+				continue;
+			},
+			| Err(SendError::Transient(e) | SendError::Rejected(e)) => {
+				error!("failed to send message: {e}");
+				set_state(pending.nonce, SendState::Failed);
+			},
+		}
+		save_outbox();
+	}
+}
+
+fn set_state(
+	nonce: Snowflake,
+	state: SendState,
+) {
+	if let Some(pending) = OUTBOX.write().iter_mut().find(|p| p.nonce == nonce) {
+		pending.state = state;
+	}
+}
+
+fn outbox_path() -> Option<PathBuf> {
+	CONFIG_PATH.get().map(|dir| dir.join("outbox.json"))
+}
+
+fn load_outbox() -> Vec<PendingMessage> {
+	outbox_path()
+		.and_then(|path| fs::read_to_string(path).ok())
+		.and_then(|raw| serde_json::from_str(&raw).ok())
+		.unwrap_or_default()
+}
+
+fn save_outbox() {
+	let Some(path) = outbox_path() else {
+		return;
+	};
+
+	let result = serde_json::to_string(&*OUTBOX.peek())
+		.map_err(Box::<dyn std::error::Error>::from)
+		.and_then(|raw| Ok(fs::write(path, raw)?));
+	if let Err(e) = result {
+		error!("failed to save outbox: {e}");
+	}
+}
+
+enum SendError {
+	/// the connection failed or discord had a server error, trying again may work
+	Transient(Box<dyn std::error::Error>),
+	/// discord refused the message, or its files couldn't be read
+	Rejected(Box<dyn std::error::Error>),
+}
+
+async fn create_message(pending: &PendingMessage) -> Result<Message, SendError> {
+	let client = RequestClient::new(BaseUrl::Discord, false);
+	let endpoint = CREATE_MESSAGE(pending.channel_id);
+
+	let response = if pending.files.is_empty() {
+		with_rate_limit(|| client.post(&endpoint, Some(&pending.request)))
+			.await
+			.map_err(SendError::Transient)?
+	} else {
+		match request_upload_urls(pending.channel_id, &pending.files).await {
+			| Ok(slots) => {
+				if let Err(e) = upload_files(pending.nonce, &pending.files, &slots).await {
+					delete_uploads(&slots).await;
+					let cancelled = !OUTBOX.peek().iter().any(|p| p.nonce == pending.nonce);
+					return Err(if cancelled {
+						SendError::Rejected(e)
+					} else {
+						SendError::Transient(e)
+					});
+				}
+
+				let mut request = pending.request.clone();
+				for (attachment, slot) in request.attachments.iter_mut().flatten().zip(&slots) {
+					attachment.uploaded_filename = Some(slot.upload_filename.clone());
+				}
+				with_rate_limit(|| client.post(&endpoint, Some(&request)))
+					.await
+					.map_err(SendError::Transient)?
+			},
+			| Err(e) => {
+				warn!("couldn't get upload urls, sending the files with the message instead: {e}");
+				send_multipart(&client, &endpoint, pending).await?
+			},
+		}
+	};
+
+	if response.status().is_server_error() {
+		return Err(SendError::Transient(
+			format!("Request failed with status: {}", response.status()).into(),
+		));
+	}
+
+	match response
+		.with_auto_handle()
+		.await
+		.map_err(SendError::Rejected)?
+	{
+		| ApiResponse::Success(message) => Ok(message),
+		| ApiResponse::Error(e) => Err(SendError::Rejected(format!("{e:?}").into())),
+	}
+}
+
+/// Sends the files as `files[n]` next to the json body, matched up by the attachment ids
+async fn send_multipart(
+	client: &RequestClient,
+	endpoint: &str,
+	pending: &PendingMessage,
+) -> Result<Response, SendError> {
+	let files = read_files(&pending.files)
+		.await
+		.map_err(SendError::Rejected)?;
+	let payload =
+		serde_json::to_string(&pending.request).map_err(|e| SendError::Rejected(e.into()))?;
+
+	with_rate_limit(|| {
+		let mut form = Form::new().text("payload_json", payload.clone());
+		for (i, (filename, bytes)) in files.iter().enumerate() {
+			form = form.part(
+				format!("files[{i}]"),
+				Part::bytes(bytes.clone()).file_name(filename.clone()),
+			);
+		}
+		client.post_multipart(endpoint, form)
+	})
+	.await
+	.map_err(SendError::Transient)
+}
+
+async fn read_files(
+	files: &[QueuedFile]
+) -> Result<Vec<(String, Vec<u8>)>, Box<dyn std::error::Error>> {
+	let mut read = Vec::with_capacity(files.len());
+	for file in files {
+		let path = file.path.clone();
+		let bytes = tokio::task::spawn_blocking(move || fs::read(path)).await??;
+		read.push((file.upload_name(), bytes));
+	}
+	Ok(read)
+}
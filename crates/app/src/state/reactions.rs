@@ -0,0 +1,186 @@
+use dioxus::prelude::*;
+use discord_api::ApiResponse;
+use discord_api::endpoints::messages::{
+	CREATE_REACTION,
+	CreateReactionQuery,
+	DELETE_OWN_REACTION,
+	GET_REACTIONS,
+	GetReactionsQuery,
+};
+use discord_api::types::emoji::Emoji;
+use discord_api::types::message::{Message, Reaction, ReactionCountDetails, ReactionType};
+use discord_api::types::snowflake::Snowflake;
+use discord_api::types::user::User;
+use discord_api::types::ws::gateway::GatewayEvent;
+
+use crate::state::STATE;
+use crate::state::messages::MESSAGES;
+use crate::utils::request::{AutoHandle, BaseUrl, RequestClient, with_rate_limit};
+
+/// Keeps the reactions of loaded messages up to date
+///
+/// The user's own reactions come back through the gateway too, so adding or removing a reaction doesn't change anything until then
+pub fn apply_reaction_event(event: &GatewayEvent) {
+	let own_id = STATE.peek().user.as_ref().map(|u| u.id);
+
+	match event {
+		| GatewayEvent::MessageReactionAdd(add) => {
+			let me = own_id == Some(add.user_id);
+			update_message(add.channel_id, add.message_id, |message| {
+				let index = match message
+					.reactions
+					.iter()
+					.position(|r| r.emoji.is_same(&add.emoji))
+				{
+					| Some(index) => index,
+					| None => {
+						message.reactions.push(Reaction {
+							count: 0,
+							count_details: ReactionCountDetails::default(),
+							me: false,
+							me_burst: false,
+							emoji: add.emoji.clone(),
+							burst_colors: add.burst_colors.clone(),
+						});
+						message.reactions.len() - 1
+					},
+				};
+				let reaction = &mut message.reactions[index];
+
+				reaction.count += 1;
+				if add.burst {
+					reaction.count_details.burst += 1;
+					reaction.me_burst |= me;
+					if reaction.burst_colors.is_empty() {
+						reaction.burst_colors = add.burst_colors.clone();
+					}
+				} else {
+					reaction.count_details.normal += 1;
+					reaction.me |= me;
+				}
+			});
+		},
+		| GatewayEvent::MessageReactionRemove(remove) => {
+			let me = own_id == Some(remove.user_id);
+			update_message(remove.channel_id, remove.message_id, |message| {
+				let Some(reaction) = message
+					.reactions
+					.iter_mut()
+					.find(|r| r.emoji.is_same(&remove.emoji))
+				else {
+					return;
+				};
+
+				reaction.count = reaction.count.saturating_sub(1);
+				if remove.burst {
+					reaction.count_details.burst = reaction.count_details.burst.saturating_sub(1);
+					reaction.me_burst &= !me;
+				} else {
+					reaction.count_details.normal = reaction.count_details.normal.saturating_sub(1);
+					reaction.me &= !me;
+				}
+				message.reactions.retain(|r| r.count > 0);
+			});
+		},
+		| GatewayEvent::MessageReactionRemoveAll(remove) => {
+			update_message(remove.channel_id, remove.message_id, |message| {
+				message.reactions.clear();
+			});
+		},
+		| GatewayEvent::MessageReactionRemoveEmoji(remove) => {
+			let Some(emoji) = &remove.emoji else {
+				return;
+			};
+			update_message(remove.channel_id, remove.message_id, |message| {
+				message.reactions.retain(|r| !r.emoji.is_same(emoji));
+			});
+		},
+		| _ => {},
+	}
+}
+
+pub async fn add_reaction(
+	channel_id: Snowflake,
+	message_id: Snowflake,
+	emoji: &Emoji,
+	reaction_type: ReactionType,
+) -> Result<(), Box<dyn std::error::Error>> {
+	let client = RequestClient::new(BaseUrl::Discord, false);
+	let endpoint = CREATE_REACTION(
+		channel_id,
+		message_id,
+		emoji.reaction_id(),
+		&CreateReactionQuery {
+			location: Some("Message".to_owned()),
+			r#type: Some(reaction_type),
+		},
+	);
+
+	let response = with_rate_limit(|| client.put::<()>(&endpoint, None)).await?;
+	if response.status().is_success() {
+		Ok(())
+	} else {
+		Err(format!("Request failed with status: {}", response.status()).into())
+	}
+}
+
+pub async fn remove_reaction(
+	channel_id: Snowflake,
+	message_id: Snowflake,
+	emoji: &Emoji,
+	reaction_type: ReactionType,
+) -> Result<(), Box<dyn std::error::Error>> {
+	let client = RequestClient::new(BaseUrl::Discord, false);
+	let endpoint = DELETE_OWN_REACTION(channel_id, message_id, emoji.reaction_id(), reaction_type);
+
+	let response = with_rate_limit(|| client.delete::<()>(&endpoint, None)).await?;
+	if response.status().is_success() {
+		Ok(())
+	} else {
+		Err(format!("Request failed with status: {}", response.status()).into())
+	}
+}
+
+/// One page of the users that reacted with the emoji, continuing after the user `after`
+pub async fn get_reactors(
+	channel_id: Snowflake,
+	message_id: Snowflake,
+	emoji: &Emoji,
+	reaction_type: ReactionType,
+	after: Option<Snowflake>,
+	limit: u8,
+) -> Result<Vec<User>, Box<dyn std::error::Error>> {
+	let client = RequestClient::new(BaseUrl::Discord, false);
+	let endpoint = GET_REACTIONS(
+		channel_id,
+		message_id,
+		emoji.reaction_id(),
+		&GetReactionsQuery {
+			r#type: Some(reaction_type),
+			after,
+			limit: Some(limit),
+		},
+	);
+
+	let response = with_rate_limit(|| client.get::<()>(&endpoint, None)).await?;
+	match response.with_auto_handle().await? {
+		| ApiResponse::Success(users) => Ok(users),
+		| ApiResponse::Error(e) => Err(format!("{e:?}").into()),
+	}
+}
+
+fn update_message(
+	channel_id: Snowflake,
+	message_id: Snowflake,
+	update: impl FnOnce(&mut Message),
+) {
+	let index = MESSAGES
+		.peek()
+		.get(&channel_id)
+		.and_then(|w| w.index_of(message_id));
+	if let Some(index) = index
+		&& let Some(window) = MESSAGES.write().get_mut(&channel_id)
+	{
+		update(&mut window.messages[index]);
+	}
+}
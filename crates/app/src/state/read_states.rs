@@ -0,0 +1,91 @@
+use dioxus::prelude::*;
+use discord_api::ApiResponse;
+use discord_api::endpoints::messages::{ACK_MESSAGE, AckMessageRequest, AckMessageResponse};
+use discord_api::endpoints::read_states::{ACK_BULK, AckBulkRequest, BulkReadState};
+use discord_api::types::snowflake::Snowflake;
+
+use crate::state::STATE;
+use crate::utils::request::{AutoHandle, BaseUrl, RequestClient, with_rate_limit};
+
+/// The token from the last ack, sent with the next one
+static ACK_TOKEN: GlobalSignal<Option<String>> = Signal::global(|| None);
+
+/// The most channels acked by one bulk ack
+const BULK_ACK_LIMIT: usize = 100;
+
+/// Marks everything in the channel as read, if it's unread
+///
+/// The read state is updated straight away so the unread dot doesn't wait for the request, and so the same message isn't acked twice
+pub async fn ack_channel(channel_id: Snowflake) -> Result<(), Box<dyn std::error::Error>> {
+	let message_id = {
+		let state = STATE.peek();
+		let Some(channel) = state.channel(channel_id) else {
+			return Ok(());
+		};
+		let has_mentions = state.mention_count(channel_id) > 0;
+		match channel.last_message_id {
+			| Some(id) if state.is_unread(channel) || has_mentions => id,
+			| _ => return Ok(()),
+		}
+	};
+	STATE.write().mark_read(channel_id, message_id);
+
+	let client = RequestClient::new(BaseUrl::Discord, false);
+	let endpoint = ACK_MESSAGE(channel_id, message_id);
+	let body = AckMessageRequest {
+		token: ACK_TOKEN.peek().clone(),
+		..Default::default()
+	};
+
+	let response = with_rate_limit(|| client.post(&endpoint, Some(&body))).await?;
+	match response.with_auto_handle::<AckMessageResponse>().await? {
+		| ApiResponse::Success(response) => {
+			*ACK_TOKEN.write() = response.token;
+			Ok(())
+		},
+		| ApiResponse::Error(e) => Err(format!("{e:?}").into()),
+	}
+}
+
+/// Marks every channel the user can see in the guild as read
+pub async fn ack_guild(guild_id: Snowflake) -> Result<(), Box<dyn std::error::Error>> {
+	let read_states: Vec<BulkReadState> = {
+		let state = STATE.peek();
+		let Some(guild) = state.guild(guild_id) else {
+			return Ok(());
+		};
+		guild
+			.channels
+			.iter()
+			.filter(|c| state.can_view(c))
+			.filter(|c| state.is_unread(c) || state.mention_count(c.id) > 0)
+			.filter_map(|c| {
+				Some(BulkReadState {
+					channel_id: c.id,
+					message_id: c.last_message_id?,
+					read_state_type: 0,
+				})
+			})
+			.collect()
+	};
+
+	{
+		let mut state = STATE.write();
+		for read_state in &read_states {
+			state.mark_read(read_state.channel_id, read_state.message_id);
+		}
+	}
+
+	let client = RequestClient::new(BaseUrl::Discord, false);
+	for chunk in read_states.chunks(BULK_ACK_LIMIT) {
+		let body = AckBulkRequest {
+			read_states: chunk.to_vec(),
+		};
+		let response = with_rate_limit(|| client.post(ACK_BULK, Some(&body))).await?;
+		if !response.status().is_success() {
+			return Err(format!("Request failed with status: {}", response.status()).into());
+		}
+	}
+
+	Ok(())
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use discord_api::endpoints::channels::TRIGGER_TYPING;
+use discord_api::types::snowflake::Snowflake;
+use discord_api::types::ws::gateway::{GatewayEvent, TypingStart};
+use tokio::time::Instant;
+
+use crate::state::{ClientState, STATE};
+use crate::utils::request::{BaseUrl, RequestClient};
+
+/// Who's typing in each channel, in the order they started
+pub static TYPING: GlobalSignal<HashMap<Snowflake, Vec<Typer>>> = Signal::global(HashMap::new);
+
+/// How long someone is shown as typing after a `TYPING_START`
+const TYPING_DURATION: Duration = Duration::from_secs(10);
+/// How often the composer tells discord the user is still typing, a bit under [`TYPING_DURATION`] so it doesn't flicker for others
+pub const TYPING_INTERVAL: Duration = Duration::from_secs(8);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Typer {
+	pub user_id: Snowflake,
+	/// the name shown in the typing line, their nickname in guilds
+	pub name: String,
+	until: Instant,
+}
+
+/// Keeps [`TYPING`] up to date, removing typers when their time runs out or they send their message
+pub fn apply_typing_event(event: &GatewayEvent) {
+	match event {
+		| GatewayEvent::TypingStart(typing) => {
+			let state = STATE.peek();
+			if state.user.as_ref().is_some_and(|u| u.id == typing.user_id) {
+				return;
+			}
+			let name = typer_name(&state, typing);
+			drop(state);
+
+			let until = Instant::now() + TYPING_DURATION;
+			let mut all = TYPING.write();
+			let typers = all.entry(typing.channel_id).or_default();
+			match typers.iter_mut().find(|t| t.user_id == typing.user_id) {
+				| Some(typer) => typer.until = until,
+				| None => typers.push(Typer {
+					user_id: typing.user_id,
+					name,
+					until,
+				}),
+			}
+
+			let channel_id = typing.channel_id;
+			spawn(async move {
+				tokio::time::sleep(TYPING_DURATION).await;
+				expire(channel_id);
+			});
+		},
+		| GatewayEvent::MessageCreate(message) => {
+			let is_typing = TYPING
+				.peek()
+				.get(&message.channel_id)
+				.is_some_and(|t| t.iter().any(|t| t.user_id == message.author.id));
+			if is_typing && let Some(typers) = TYPING.write().get_mut(&message.channel_id) {
+				typers.retain(|t| t.user_id != message.author.id);
+			}
+		},
+		| _ => {},
+	}
+}
+
+/// Shows the user as typing in the channel for the next 10 seconds
+pub async fn send_typing(channel_id: Snowflake) -> Result<(), Box<dyn std::error::Error>> {
+	let client = RequestClient::new(BaseUrl::Discord, false);
+	let response = client.post::<()>(&TRIGGER_TYPING(channel_id), None).await?;
+
+	if response.status().is_success() {
+		Ok(())
+	} else {
+		Err(format!("Request failed with status: {}", response.status()).into())
+	}
+}
+
+/// The typing line for a channel, eg. `A, B and 2 others are typing...`
+pub fn typing_text(typers: &[Typer]) -> Option<String> {
+	Some(match typers {
+		| [] => return None,
+		| [a] => format!("{} is typing...", a.name),
+		| [a, b] => format!("{} and {} are typing...", a.name, b.name),
+		| [a, b, c] => format!("{}, {} and {} are typing...", a.name, b.name, c.name),
+		| [a, b, rest @ ..] => format!(
+			"{}, {} and {} others are typing...",
+			a.name,
+			b.name,
+			rest.len()
+		),
+	})
+}
+
+fn expire(channel_id: Snowflake) {
+	let now = Instant::now();
+	let expired = TYPING
+		.peek()
+		.get(&channel_id)
+		.is_some_and(|t| t.iter().any(|t| t.until <= now));
+	if !expired {
+		return;
+	}
+
+	let mut all = TYPING.write();
+	if let Some(typers) = all.get_mut(&channel_id) {
+		typers.retain(|t| t.until > now);
+		if typers.is_empty() {
+			all.remove(&channel_id);
+		}
+	}
+}
+
+/// The member's nickname or the user's name, from the event or whatever the state knows about them
+fn typer_name(
+	state: &ClientState,
+	typing: &TypingStart,
+) -> String {
+	if let Some(member) = &typing.member {
+		if let Some(nick) = &member.nick {
+			return nick.clone();
+		}
+		if let Some(user) = &member.user {
+			return user.display_name().to_owned();
+		}
+	}
+
+	state
+		.channel(typing.channel_id)
+		.and_then(|c| c.recipients.as_deref())
+		.unwrap_or_default()
+		.iter()
+		.find(|u| u.id == typing.user_id)
+		.map_or_else(|| "Someone".to_owned(), |u| u.display_name().to_owned())
+}
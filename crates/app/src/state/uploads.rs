@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use std::fs;
+
+use dioxus::prelude::*;
+use discord_api::ApiResponse;
+use discord_api::endpoints::messages::{
+	AttachmentSlotRequest,
+	CREATE_ATTACHMENTS,
+	CloudAttachment,
+	CreateAttachmentsRequest,
+	CreateAttachmentsResponse,
+	DELETE_ATTACHMENT_UPLOAD,
+};
+use discord_api::types::snowflake::Snowflake;
+use futures::channel::mpsc;
+use futures::{SinkExt, StreamExt};
+use wreq::Body;
+
+use crate::state::ClientState;
+use crate::state::outbox::{OUTBOX, QueuedFile};
+use crate::utils::request::{AutoHandle, BaseUrl, RequestClient, with_rate_limit};
+
+/// Bytes uploaded and total bytes of the files of messages being sent, by the message's nonce
+pub static UPLOAD_PROGRESS: GlobalSignal<HashMap<Snowflake, (u64, u64)>> =
+	Signal::global(HashMap::new);
+
+/// The most files a message can have
+pub const MAX_FILES: usize = 10;
+/// files are read and uploaded in chunks of this many bytes, each one updates the progress
+const CHUNK_SIZE: usize = 64 * 1024;
+const MIB: u64 = 1024 * 1024;
+
+/// The largest file the user can upload in the channel, the higher of their nitro's and the guild's boost limit
+pub fn upload_limit(
+	state: &ClientState,
+	channel_id: Snowflake,
+) -> u64 {
+	let premium = match state.user.as_ref().and_then(|u| u.premium_type) {
+		// nitro classic and nitro basic
+		| Some(1 | 3) => 50 * MIB,
+		| Some(2) => 500 * MIB,
+		| _ => 10 * MIB,
+	};
+	let boosts = match state
+		.channel(channel_id)
+		.and_then(|c| c.guild_id)
+		.and_then(|id| state.guild(id))
+		.map(|g| g.premium_tier)
+	{
+		| Some(2) => 50 * MIB,
+		| Some(3) => 100 * MIB,
+		| _ => 0,
+	};
+
+	premium.max(boosts)
+}
+
+/// Requests an upload url for each file, in the same order
+pub async fn request_upload_urls(
+	channel_id: Snowflake,
+	files: &[QueuedFile],
+) -> Result<Vec<CloudAttachment>, Box<dyn std::error::Error>> {
+	let client = RequestClient::new(BaseUrl::Discord, false);
+	let body = CreateAttachmentsRequest {
+		files: files
+			.iter()
+			.enumerate()
+			.map(|(i, file)| AttachmentSlotRequest {
+				id: i.to_string(),
+				filename: file.upload_name(),
+				file_size: file.size,
+			})
+			.collect(),
+	};
+
+	let endpoint = CREATE_ATTACHMENTS(channel_id);
+
+	let response = with_rate_limit(|| client.post(&endpoint, Some(&body))).await?;
+	match response
+		.with_auto_handle::<CreateAttachmentsResponse>()
+		.await?
+	{
+		| ApiResponse::Success(response) if response.attachments.len() == files.len() => {
+			Ok(response.attachments)
+		},
+		| ApiResponse::Success(_) => Err("Discord returned the wrong number of upload urls".into()),
+		| ApiResponse::Error(e) => Err(format!("{e:?}").into()),
+	}
+}
+
+/// Uploads the files of the message with the nonce to their upload urls, updating [`UPLOAD_PROGRESS`] as it goes
+///
+/// Stops with an error if the message is removed from the outbox, which is how uploads are cancelled
+pub async fn upload_files(
+	nonce: Snowflake,
+	files: &[QueuedFile],
+	slots: &[CloudAttachment],
+) -> Result<(), Box<dyn std::error::Error>> {
+	let client = RequestClient::new(BaseUrl::Discord, false);
+	let total = files.iter().map(|f| f.size).sum();
+	let mut sent = 0;
+	UPLOAD_PROGRESS.write().insert(nonce, (0, total));
+
+	for (file, slot) in files.iter().zip(slots) {
+		let path = file.path.clone();
+		let bytes = tokio::task::spawn_blocking(move || fs::read(path)).await??;
+		let length = bytes.len() as u64;
+
+		// the body is fed through a channel holding one chunk, so a chunk is only counted once the request has taken the one before it
+		let (sender, receiver) = mpsc::channel::<Vec<u8>>(1);
+		let body = Body::wrap_stream(receiver.map(Ok::<_, std::io::Error>));
+
+		let feed = async {
+			// moved in so the body ends once every chunk is sent
+			let mut sender = sender;
+			for chunk in bytes.chunks(CHUNK_SIZE) {
+				if !OUTBOX.peek().iter().any(|p| p.nonce == nonce) {
+					return Err::<(), Box<dyn std::error::Error>>("Upload cancelled".into());
+				}
+				sender.send(chunk.to_vec()).await?;
+				sent += chunk.len() as u64;
+				UPLOAD_PROGRESS.write().insert(nonce, (sent, total));
+			}
+			Ok(())
+		};
+		let upload = async {
+			let response = client.put_upload(&slot.upload_url, body, length).await?;
+			if response.status().is_success() {
+				Ok(())
+			} else {
+				Err(format!("Upload failed with status: {}", response.status()).into())
+			}
+		};
+
+		let result = futures::future::try_join(feed, upload).await;
+		if result.is_err() {
+			UPLOAD_PROGRESS.write().remove(&nonce);
+		}
+		result?;
+	}
+
+	Ok(())
+}
+
+/// Deletes uploaded files that won't be sent, like after cancelling
+pub async fn delete_uploads(slots: &[CloudAttachment]) {
+	let client = RequestClient::new(BaseUrl::Discord, false);
+	for slot in slots {
+		let endpoint = DELETE_ATTACHMENT_UPLOAD(&slot.upload_filename);
+		if let Err(e) = client.delete::<()>(&endpoint, None).await {
+			warn!("failed to delete upload {}: {e}", slot.upload_filename);
+		}
+	}
+}
@@ -1,13 +1,19 @@
+use std::time::Duration;
+
 use base64::Engine;
 use base64::prelude::BASE64_STANDARD;
-use dioxus::prelude::use_navigator;
+use dioxus::core::Runtime;
+use dioxus::prelude::navigator;
+use discord_api::types::error::RateLimitError;
 use discord_api::types::locale::Locale;
 use discord_api::types::super_properties::ClientProperties;
 use discord_api::{ApiResponse, ApiVerion, CDN_URL, DISCORD_URL};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use tokio_tungstenite::tungstenite::Bytes;
-use wreq::{Client, RequestBuilder, Response};
+use tracing::warn;
+use wreq::multipart::Form;
+use wreq::{Body, Client, RequestBuilder, Response};
 use wreq_util::Emulation;
 
 use crate::CONFIG;
@@ -63,6 +69,34 @@ impl RequestClient {
 		Ok(request.send().await?)
 	}
 
+	/// Posts a multipart form, used for uploading files
+	pub async fn post_multipart(
+		&self,
+		endpoint: &str,
+		form: Form,
+	) -> Result<Response, Box<dyn std::error::Error>> {
+		let url = format!("{}{}", self.api_base, endpoint);
+		let request = self.client.post(&url).add_headers(self.no_auth)?;
+
+		Ok(request.multipart(form).send().await?)
+	}
+
+	/// Uploads a file to an upload url from `CREATE_ATTACHMENTS`, these are signed so no discord headers are sent
+	pub async fn put_upload(
+		&self,
+		url: &str,
+		body: Body,
+		length: u64,
+	) -> Result<Response, Box<dyn std::error::Error>> {
+		let request = self
+			.client
+			.put(url)
+			.header("Content-Length", length)
+			.body(body);
+
+		Ok(request.send().await?)
+	}
+
 	pub async fn get<T>(
 		&self,
 		endpoint: &str,
@@ -165,7 +199,6 @@ impl RequestBuilderExt for RequestBuilder {
 		self,
 		no_auth: bool,
 	) -> Result<RequestBuilder, Box<dyn std::error::Error>> {
-		let nav = use_navigator();
 		let token = load_token()?;
 		let locale = &CONFIG.get().unwrap().locale;
 		let mut superprops = ClientProperties::new();
@@ -187,7 +220,10 @@ impl RequestBuilderExt for RequestBuilder {
 			{
 				builder = builder.header("Authorization", token.0);
 			} else {
-				nav.replace("/login");
+				// the cli has no router to send the user back to the login page
+				if Runtime::try_current().is_some() {
+					navigator().replace("/login");
+				}
 				return Err("Authorization token is missing or is invalid".into());
 			}
 		}
@@ -222,3 +258,42 @@ impl AutoHandle for Response {
 		}
 	}
 }
+
+/// Sends the request made by `request`, waiting out rate limits
+///
+/// Retries when a `429` is returned, and waits for the bucket to reset before returning if the response used up the last request in it
+pub async fn with_rate_limit<F, Fut>(mut request: F) -> Result<Response, Box<dyn std::error::Error>>
+where
+	F: FnMut() -> Fut,
+	Fut: Future<Output = Result<Response, Box<dyn std::error::Error>>>,
+{
+	loop {
+		let response = request().await?;
+
+		if response.status().as_u16() == 429 {
+			let limit: RateLimitError = response.json().await?;
+			warn!(
+				"Rate limited (global: {}), retrying in {}s",
+				limit.global, limit.retry_after
+			);
+			tokio::time::sleep(Duration::from_secs_f64(limit.retry_after)).await;
+			continue;
+		}
+
+		let header = |name: &str| {
+			response
+				.headers()
+				.get(name)
+				.and_then(|v| v.to_str().ok())
+				.and_then(|v| v.parse::<f64>().ok())
+		};
+
+		if header("x-ratelimit-remaining") == Some(0.0)
+			&& let Some(reset_after) = header("x-ratelimit-reset-after")
+		{
+			tokio::time::sleep(Duration::from_secs_f64(reset_after)).await;
+		}
+
+		return Ok(response);
+	}
+}
@@ -1,7 +1,7 @@
-use std::time::Duration;
+use std::sync::Arc;
 
 use base64::Engine;
-use base64::prelude::{BASE64_STANDARD, BASE64_URL_SAFE_NO_PAD};
+use base64::prelude::BASE64_STANDARD;
 use dioxus::prelude::*;
 use discord_api::endpoints::auth::login::{
 	AuthenticatorType,
@@ -10,35 +10,24 @@ use discord_api::endpoints::auth::login::{
 	LoginAccountResponse,
 	LoginAccountSuspendedResponse,
 	LoginRequiredActionType,
-	REMOTE_AUTH_TICKET_EXCHANGE,
-	RemoteAuthTicketExchangeRequest,
-	RemoteAuthTicketExchangeResponse,
 	VERIFY_MFA_LOGIN,
 	VerifyMfaLoginRequest,
 	VerifyMfaLoginResponse,
 };
 use discord_api::endpoints::cdn::USER_AVATAR;
 use discord_api::types::token::Token;
-use discord_api::types::ws::remote_auth::{
-	REMOTE_AUTH_QR_CODE_URL,
-	RemoteAuthGatewayClientOpCode,
-	RemoteAuthGatewayServerOpCode,
-};
 use discord_api::{ApiResponse, CDN_URL};
 use fast_qr::convert::Builder;
 use fast_qr::convert::svg::SvgBuilder;
 use fast_qr::{ECL, QRBuilder};
 use lucide_dioxus::LoaderCircle;
-use rsa::pkcs8::EncodePublicKey;
-use rsa::rand_core::OsRng;
-use rsa::{Oaep, RsaPrivateKey};
-use sha2::{Digest, Sha256};
-use tokio::time::{Instant, Interval, interval_at};
 
 use crate::components::ui::Button;
+use crate::import::DataPackage;
 use crate::utils::request::{AutoHandle, BaseUrl, RequestClient};
 use crate::utils::token::save_token;
-use crate::ws::remote_auth::RemoteAuthWsClient;
+use crate::views::DATA_PACKAGE;
+use crate::ws::remote_auth::{RemoteAuthEvent, remote_auth_login};
 
 #[component]
 pub fn Login() -> Element {
@@ -56,7 +45,7 @@ pub fn Login() -> Element {
 
 	use_effect(move || {
 		spawn(async move {
-			match get_remote_auth_qr_url(remote_auth_state).await {
+			match get_remote_auth_token(remote_auth_state).await {
 				| Ok(_) => {
 					nav.replace("/channels/@me");
 				},
@@ -131,6 +120,30 @@ pub fn Login() -> Element {
 		});
 	};
 
+	let open_package = move |e: FormEvent| {
+		let Some(file) = e.files().into_iter().next() else {
+			return;
+		};
+
+		spawn(async move {
+			let path = file.path();
+			// parsing a large package takes a while, keep it off the ui thread
+			let package = tokio::task::spawn_blocking(move || {
+				DataPackage::open(&path).map_err(|e| e.to_string())
+			})
+			.await;
+
+			match package {
+				| Ok(Ok(package)) => {
+					*DATA_PACKAGE.write() = Some(Arc::new(package));
+					nav.push("/package");
+				},
+				| Ok(Err(e)) => error!("failed to open data package: {e}"),
+				| Err(e) => error!("failed to open data package: {e}"),
+			}
+		});
+	};
+
 	let qrcode_component = match &*remote_auth_state.read() {
 		| RemoteAuthState::Loading => rsx! {
 			PreAccepted {
@@ -224,6 +237,18 @@ pub fn Login() -> Element {
 							p { "Need an account?" }
 							a { class: "text-link", href: "/register", "Register" }
 						}
+						div { class: "flex flex-row text-xs gap-x-1",
+							p { "Have a data package?" }
+							label { class: "text-link cursor-pointer",
+								"Browse it offline"
+								input {
+									r#type: "file",
+									accept: ".zip",
+									class: "hidden",
+									onchange: open_package,
+								}
+							}
+						}
 					}
 					div { class: "justify-center items-center flex m-auto", {qrcode_component} }
 				}
@@ -382,7 +407,7 @@ async fn mfa_verify(
 	println!("{}", serde_json::to_string(&payload)?);
 
 	let resp: ApiResponse<VerifyMfaLoginResponse> = client
-		.post::<VerifyMfaLoginRequest>(&VERIFY_MFA_LOGIN(authn_type), Some(&payload))
+		.post::<VerifyMfaLoginRequest>(&VERIFY_MFA_LOGIN(authn_type.name()), Some(&payload))
 		.await?
 		.with_auto_handle()
 		.await?;
@@ -414,195 +439,38 @@ enum RemoteAuthState {
 	Cancelled,
 }
 
-async fn get_remote_auth_qr_url(
+async fn get_remote_auth_token(
 	mut remote_auth_state: Signal<RemoteAuthState>
 ) -> Result<(), Box<dyn std::error::Error>> {
-	'reconnect: loop {
-		let mut client = RemoteAuthWsClient::connect().await?;
-
-		// generate rsa and public key
-		let (private_key, public_key) = tokio::task::spawn_blocking(|| {
-			let private_key = RsaPrivateKey::new(&mut OsRng, 2048).map_err(|e| e.to_string())?;
-			let public_key = private_key
-				.to_public_key()
-				.to_public_key_der()
-				.map_err(|e| e.to_string())?
-				.to_vec();
-			Ok::<_, String>((private_key, public_key))
-		})
-		.await
-		.map_err(|e| e.to_string())??;
-
-		// let pkey = PKey::from_rsa(rsa.clone())?;
-		// let mut decrypter = Decrypter::new(&pkey)?;
-		// decrypter.set_rsa_padding(Padding::PKCS1_OAEP)?;
-		// decrypter.set_rsa_oaep_md(MessageDigest::sha256())?;
-
-		let mut heartbeat_interval: Option<Interval> = None;
-		let mut awaiting_ack = false;
-
-		loop {
-			let hb_tick = async {
-				match heartbeat_interval.as_mut() {
-					| Some(iv) => {
-						iv.tick().await;
-					},
-					| None => std::future::pending().await,
+	let token = remote_auth_login(move |event| {
+		remote_auth_state.set(match event {
+			| RemoteAuthEvent::QrCode {
+				url,
+			} => {
+				// generate qr code from the fingerprint discord gave
+				let qr = QRBuilder::new(url).ecl(ECL::L).build().unwrap();
+				let svg = SvgBuilder::default().margin(2).to_str(&qr);
+
+				RemoteAuthState::QrCode {
+					svg,
 				}
-			};
-
-			tokio::select! {
-				_ = hb_tick => {
-					if awaiting_ack {
-						warn!("heartbeat ack expected but not recieved, reconnecting...");
-						let _ = client.close(None).await;
-						continue 'reconnect;
-					}
-					client.send_json(&RemoteAuthGatewayClientOpCode::Heartbeat).await?;
-					awaiting_ack = true;
-				}
-
-				result = client.recv_json() => {
-					let opcode = result?
-						.ok_or("connection closed while waiting for mobile client")?;
-					trace!("remote auth ws recieved {:?}", &opcode);
-
-					// if awaiting_ack {
-					// 	if !matches!(opcode, RemoteAuthGatewayServerOpCode::HeartbeatAck) {
-					// 		let _ = client.close(None).await;
-					// 		continue 'reconnect;
-					// 	}
-					// 	awaiting_ack = false;
-					// 	continue;
-					// }
-
-					match opcode {
-						// server sends tihs after we connect
-						| RemoteAuthGatewayServerOpCode::Hello {
-							heartbeat_interval: interval_ms,
-							timeout_ms: _,
-						} => {
-							let start = Instant::now() + Duration::from_millis(interval_ms);
-							heartbeat_interval = Some(interval_at(start, Duration::from_millis(interval_ms)));
-
-							// we need to send Init opcode after recieving the Hello opcode
-							client
-								.send_json(&RemoteAuthGatewayClientOpCode::Init {
-									encoded_public_key: BASE64_STANDARD.encode(&public_key),
-								})
-								.await?;
-						},
-						// if gateway accepts the public key we sent in the Init
-						// then it will respond withan encrypted nonce
-						// that we have to verify with our private key
-						| RemoteAuthGatewayServerOpCode::NonceProof {
-							encrypted_nonce,
-						} => {
-							// decrypt the nonce
-							let encrypted_nonce_bytes = BASE64_STANDARD.decode(&encrypted_nonce)?;
-							let decrypted_nonce = private_key.decrypt(
-								Oaep::new::<Sha256>(),
-								&encrypted_nonce_bytes
-							)?;
-							let nonce_proof = BASE64_URL_SAFE_NO_PAD.encode(&decrypted_nonce);
-
-							// send the decrypted nonce
-							client
-								.send_json(&RemoteAuthGatewayClientOpCode::NonceProof {
-									nonce: nonce_proof,
-								})
-								.await?;
-						},
-						// server verifies we're waiting on the mobile device to init
-						| RemoteAuthGatewayServerOpCode::PendingRemoteInit {
-							fingerprint,
-						} => {
-							// validate the fingerprint we recieved
-							let expected_fingerprint = BASE64_URL_SAFE_NO_PAD.encode(Sha256::digest(&public_key));
-
-							// if fingerprint isnt correct, close connection and reconnect
-							if !(fingerprint == expected_fingerprint) {
-								error!(
-									"fingerprint mismatch! discord: {fingerprint} expected: {expected_fingerprint}, reconnecting..."
-								);
-								let _ = client.close(None).await;
-								continue 'reconnect;
-							}
-
-							// generate qr code from the fingerprint discord gave
-							let url = REMOTE_AUTH_QR_CODE_URL(&fingerprint);
-							let qr = QRBuilder::new(url).ecl(ECL::L).build().unwrap();
-							let svg = SvgBuilder::default().margin(2).to_str(&qr);
+			},
+			| RemoteAuthEvent::Accepted {
+				user_id,
+				discriminator,
+				avatar_hash,
+				username,
+			} => RemoteAuthState::Accepted {
+				user_id,
+				_discriminator: discriminator,
+				avatar_hash,
+				username,
+			},
+			| RemoteAuthEvent::Cancelled => RemoteAuthState::Cancelled,
+		});
+	})
+	.await?;
 
-							remote_auth_state.set(RemoteAuthState::QrCode {
-								svg,
-							});
-						},
-						// mobile client has scanned the qr code
-						| RemoteAuthGatewayServerOpCode::PendingTicket {
-							encrypted_user_payload,
-						} => {
-							let encrypted_bytes = BASE64_STANDARD.decode(&encrypted_user_payload)?;
-							let decrypted_payload = private_key.decrypt(
-								Oaep::new::<Sha256>(),
-								&encrypted_bytes
-							)?;
-
-							let decrypted_str = str::from_utf8(&decrypted_payload)?;
-							let mut parts = decrypted_str.split(':');
-
-							remote_auth_state.set(RemoteAuthState::Accepted {
-								user_id: parts.next().unwrap().to_owned(),
-								_discriminator: parts.next().unwrap().to_owned(),
-								avatar_hash: parts.next().unwrap().to_owned(),
-								username: parts.next().unwrap().to_owned(),
-							});
-						},
-						// mobile client has accepted the login attempt
-						// and now we need to take the ticket and get our token
-						| RemoteAuthGatewayServerOpCode::PendingLogin {
-							ticket,
-						} => {
-							// create request client without sending an authorization header
-							let http_client = RequestClient::new(BaseUrl::Discord, true);
-
-							// send ticket to ticket exchange endpoint
-							let resp: ApiResponse<RemoteAuthTicketExchangeResponse> = http_client
-								.post(
-									REMOTE_AUTH_TICKET_EXCHANGE,
-									Some(&RemoteAuthTicketExchangeRequest {
-										ticket,
-									}),
-								)
-								.await?
-								.with_auto_handle()
-								.await?;
-
-							return match resp {
-								ApiResponse::Success(v) => {
-									let encrypted_bytes = BASE64_STANDARD.decode(&v.encrypted_token)?;
-									let decrypted_payload = private_key.decrypt(
-										Oaep::new::<Sha256>(),
-										&encrypted_bytes
-									)?;
-									let token = str::from_utf8(&decrypted_payload)?;
-
-									save_token(token)?;
-									let _ = client.close(None).await;
-									Ok(())
-								},
-								ApiResponse::Error(e) => Err(format!("{:?}", e).into()),
-							}
-						},
-						| RemoteAuthGatewayServerOpCode::HeartbeatAck => {
-							awaiting_ack = false;
-						},
-						| RemoteAuthGatewayServerOpCode::Cancel => {
-							remote_auth_state.set(RemoteAuthState::Cancelled);
-						},
-					}
-				}
-			}
-		}
-	}
+	save_token(&token)?;
+	Ok(())
 }
@@ -0,0 +1,258 @@
+use std::collections::HashSet;
+use std::ops::Range;
+
+use discord_api::CDN_URL;
+use discord_api::endpoints::cdn::CUSTOM_EMOJI;
+use discord_api::types::channel::ChannelType;
+use discord_api::types::permissions::Permissions;
+use discord_api::types::snowflake::Snowflake;
+use discord_api::types::user::User;
+
+use crate::state::ClientState;
+
+pub const MAX_SUGGESTIONS: usize = 10;
+
+/// Discord's built in commands that only change the content of the message, and what they do
+pub const COMMANDS: [(&str, &str); 5] = [
+	("shrug", r"Appends ¯\_(ツ)_/¯ to your message"),
+	("tableflip", "Appends (╯°□°)╯︵ ┻━┻ to your message"),
+	("unflip", "Appends ┬─┬ノ( º _ ºノ) to your message"),
+	("me", "Displays text with emphasis"),
+	("spoiler", "Marks your message as a spoiler"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Trigger {
+	/// `@`
+	User,
+	/// `#`
+	Channel,
+	/// `:`
+	Emoji,
+	/// `/`, only at the start of the message
+	Command,
+}
+
+/// The word being typed, if it's something that can be autocompleted
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+	pub trigger: Trigger,
+	/// what was typed after the trigger
+	pub text: String,
+	/// the bytes of the content that get replaced when a suggestion is picked
+	pub range: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+	pub label: String,
+	pub detail: Option<String>,
+	/// an avatar or emoji image
+	pub image: Option<String>,
+	/// shown when there's no image, eg. a unicode emoji
+	pub symbol: Option<String>,
+	/// what replaces the query
+	pub insert: String,
+}
+
+/// Finds the query ending at `cursor`, a byte offset into `content`
+pub fn find_query(
+	content: &str,
+	cursor: usize,
+) -> Option<Query> {
+	let before = content.get(..cursor)?;
+	let start = before
+		.char_indices()
+		.rev()
+		.find(|(_, c)| c.is_whitespace())
+		.map_or(0, |(i, c)| i + c.len_utf8());
+
+	let mut word = before[start..].chars();
+	let trigger = match word.next()? {
+		| '@' => Trigger::User,
+		| '#' => Trigger::Channel,
+		// a lone colon is too common in normal text to start suggesting emojis
+		| ':' if before[start..].len() > 2 => Trigger::Emoji,
+		| '/' if start == 0 => Trigger::Command,
+		| _ => return None,
+	};
+
+	Some(Query {
+		trigger,
+		text: word.as_str().to_lowercase(),
+		range: start..cursor,
+	})
+}
+
+/// Suggestions for the query in the channel, best matches first
+///
+/// `recent_authors` are the authors of the loaded messages, for channels where the member list isn't known
+pub fn suggestions(
+	state: &ClientState,
+	channel_id: Snowflake,
+	recent_authors: &[User],
+	query: &Query,
+) -> Vec<Suggestion> {
+	let channel = state.channel(channel_id);
+	let guild = channel
+		.and_then(|c| c.guild_id)
+		.and_then(|id| state.guild(id));
+	let text = query.text.as_str();
+
+	let mut matches: Vec<(bool, Suggestion)> = Vec::new();
+	let mut push = |names: &[&str], suggestion: Suggestion| {
+		let names: Vec<String> = names.iter().map(|n| n.to_lowercase()).collect();
+		if names.iter().any(|n| n.starts_with(text)) {
+			matches.push((true, suggestion));
+		} else if names.iter().any(|n| n.contains(text)) {
+			matches.push((false, suggestion));
+		}
+	};
+
+	match query.trigger {
+		| Trigger::User => {
+			let mut seen = HashSet::new();
+			let members = guild
+				.into_iter()
+				.flat_map(|g| &g.members)
+				.filter_map(|m| m.user.as_ref().map(|u| (u, m.nick.as_deref())));
+			let recipients = channel
+				.and_then(|c| c.recipients.as_deref())
+				.unwrap_or_default()
+				.iter()
+				.chain(recent_authors)
+				.map(|u| (u, None));
+
+			for (user, nick) in members.chain(recipients) {
+				if !seen.insert(user.id) {
+					continue;
+				}
+				let name = nick.unwrap_or(user.display_name());
+				push(&[name, user.display_name(), &user.username], Suggestion {
+					label: name.to_owned(),
+					detail: Some(user.username.clone()),
+					image: Some(format!("{CDN_URL}{}", user.avatar_endpoint())),
+					symbol: None,
+					insert: format!("<@{}>", user.id),
+				});
+			}
+
+			let can_mention_everyone = channel
+				.is_some_and(|c| state.permissions(c).contains(Permissions::MENTION_EVERYONE));
+			if guild.is_some() && can_mention_everyone {
+				for (name, detail) in [
+					(
+						"everyone",
+						"Notify everyone who has permission to view this channel",
+					),
+					(
+						"here",
+						"Notify everyone online who has permission to view this channel",
+					),
+				] {
+					push(&[name], Suggestion {
+						label: format!("@{name}"),
+						detail: Some(detail.to_owned()),
+						image: None,
+						symbol: None,
+						insert: format!("@{name}"),
+					});
+				}
+			}
+		},
+		| Trigger::Channel => {
+			for channel in guild.into_iter().flat_map(|g| &g.channels) {
+				if !state.can_view(channel) || channel.r#type == ChannelType::GuildCategory {
+					continue;
+				}
+				let name = channel.display_name();
+				push(&[name.as_str()], Suggestion {
+					label: name.clone(),
+					detail: None,
+					image: None,
+					symbol: Some("#".to_owned()),
+					insert: format!("<#{}>", channel.id),
+				});
+			}
+		},
+		| Trigger::Emoji => {
+			// without nitro only the guild's own emojis can be used
+			let has_nitro = state
+				.user
+				.as_ref()
+				.is_some_and(|u| u.premium_type.is_some_and(|p| p > 0));
+			let guilds = state
+				.guilds
+				.iter()
+				.filter(|g| has_nitro || guild.is_some_and(|current| current.id == g.id));
+
+			for emoji in guilds.flat_map(|g| &g.emojis).filter(|e| e.available) {
+				let (Some(id), Some(name)) = (emoji.id, &emoji.name) else {
+					continue;
+				};
+				let prefix = if emoji.animated { "a" } else { "" };
+				push(&[name.as_str()], Suggestion {
+					label: format!(":{name}:"),
+					detail: None,
+					image: Some(format!("{CDN_URL}{}", CUSTOM_EMOJI(&id, emoji.animated))),
+					symbol: None,
+					insert: format!("<{prefix}:{name}:{id}>"),
+				});
+			}
+
+			for emoji in emojis::iter() {
+				let shortcodes: Vec<&str> = emoji.shortcodes().collect();
+				let Some(shortcode) = shortcodes.first() else {
+					continue;
+				};
+				push(&shortcodes, Suggestion {
+					label: format!(":{shortcode}:"),
+					detail: None,
+					image: None,
+					symbol: Some(emoji.as_str().to_owned()),
+					insert: emoji.as_str().to_owned(),
+				});
+			}
+		},
+		| Trigger::Command => {
+			for (name, description) in COMMANDS {
+				push(&[name], Suggestion {
+					label: format!("/{name}"),
+					detail: Some(description.to_owned()),
+					image: None,
+					symbol: None,
+					insert: format!("/{name}"),
+				});
+			}
+		},
+	}
+
+	// stable, so matches keep the order they were found in
+	matches.sort_by_key(|(prefix, _)| !prefix);
+	matches
+		.into_iter()
+		.take(MAX_SUGGESTIONS)
+		.map(|(_, s)| s)
+		.collect()
+}
+
+/// Runs a built in command at the start of the content, leaving other content as is
+pub fn apply_command(content: &str) -> String {
+	let Some((command, rest)) = content
+		.strip_prefix('/')
+		.map(|c| c.split_once(char::is_whitespace).unwrap_or((c, "")))
+	else {
+		return content.to_owned();
+	};
+	let rest = rest.trim();
+
+	match command {
+		// the backslash is escaped so markdown doesn't read it as escaping the underscore
+		| "shrug" => format!(r"{rest} ¯\\_(ツ)_/¯").trim_start().to_owned(),
+		| "tableflip" => format!("{rest} (╯°□°)╯︵ ┻━┻").trim_start().to_owned(),
+		| "unflip" => format!("{rest} ┬─┬ノ( º _ ºノ)").trim_start().to_owned(),
+		| "me" if !rest.is_empty() => format!("_{rest}_"),
+		| "spoiler" if !rest.is_empty() => format!("||{rest}||"),
+		| _ => content.to_owned(),
+	}
+}
@@ -0,0 +1,79 @@
+use dioxus::prelude::*;
+use discord_api::types::snowflake::Snowflake;
+use lucide_dioxus::{AtSign, Upload};
+
+use crate::state::STATE;
+use crate::views::channels::channel_list::ChannelIcon;
+use crate::views::channels::composer::{Composer, ComposerMode, UploadQueue};
+use crate::views::channels::message_list::MessageList;
+
+/// A text channel or private channel, with its header
+#[component]
+pub fn ChannelView(
+	channel_id: Snowflake,
+	/// the message to scroll to, from a message link
+	#[props(default)]
+	jump_to: Option<Snowflake>,
+) -> Element {
+	// replying and editing, set from the message list and read by the composer
+	use_context_provider(|| Signal::new(ComposerMode::default()));
+	let mut uploads = use_context_provider(UploadQueue::default);
+	let mut dragging = use_signal(|| false);
+
+	let state = STATE.read();
+
+	let Some(channel) = state.channel(channel_id) else {
+		return rsx! {
+			p { class: "text-muted-foreground m-auto", "This channel doesn't exist" }
+		};
+	};
+	if !state.can_view(channel) {
+		return rsx! {
+			p { class: "text-muted-foreground m-auto", "You don't have access to this channel" }
+		};
+	}
+
+	rsx! {
+		header { class: "flex flex-row items-center gap-x-2 h-12 shrink-0 px-4 border-b border-border",
+			if channel.r#type.is_private() {
+				AtSign { class: "size-5 shrink-0 text-muted-foreground" }
+			} else {
+				ChannelIcon { channel_type: channel.r#type, class: "size-5 shrink-0 text-muted-foreground" }
+			}
+			span { class: "font-bold truncate shrink-0", "{channel.display_name()}" }
+			if let Some(topic) = &channel.topic {
+				span { class: "w-px h-6 bg-border shrink-0" }
+				span { class: "text-sm text-muted-foreground truncate", title: "{topic}", "{topic}" }
+			}
+		}
+		div {
+			class: "relative flex flex-col grow min-h-0",
+			ondragover: move |e| {
+				e.prevent_default();
+				dragging.set(true);
+			},
+			ondragleave: move |_| dragging.set(false),
+			ondrop: move |e| {
+				e.prevent_default();
+				dragging.set(false);
+				uploads.add(channel_id, e.data_transfer().files());
+			},
+			MessageList { channel_id, jump_to }
+			if state.can_send(channel) {
+				Composer { channel_id }
+			} else {
+				div { class: "shrink-0 px-4 pb-6",
+					p { class: "rounded-lg bg-muted px-4 py-2.5 text-muted-foreground",
+						"You do not have permission to send messages in this channel."
+					}
+				}
+			}
+			if dragging() {
+				div { class: "absolute inset-2 flex flex-col items-center justify-center gap-y-2 rounded-lg border-2 border-dashed border-primary bg-background/80 pointer-events-none",
+					Upload { class: "size-10 text-primary" }
+					p { class: "font-bold", "Upload to {channel.display_name()}" }
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,470 @@
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::path::PathBuf;
+
+use dioxus::prelude::*;
+use discord_api::CDN_URL;
+use discord_api::types::channel::{Channel, ChannelType};
+use discord_api::types::guild::Guild;
+use discord_api::types::snowflake::Snowflake;
+use lucide_dioxus::{
+	CheckCheck,
+	ChevronDown,
+	ChevronRight,
+	Hash,
+	HeadphoneOff,
+	Images,
+	Megaphone,
+	MessagesSquare,
+	MicOff,
+	Radio,
+	Volume2,
+};
+use tailwind_fuse::tw_merge;
+
+use crate::components::ui::Badge;
+use crate::export::{ExportFormat, ExportOptions, export_channel};
+use crate::state::read_states::{ack_channel, ack_guild};
+use crate::state::{ClientState, STATE};
+
+/// categories the user collapsed, kept while the app is open
+pub static COLLAPSED_CATEGORIES: GlobalSignal<HashSet<Snowflake>> = Signal::global(HashSet::new);
+/// the last opened channel of each guild, clicking a guild goes back to it
+pub static LAST_CHANNELS: GlobalSignal<HashMap<Snowflake, Snowflake>> =
+	Signal::global(HashMap::new);
+
+/// The channels the user can see grouped by category, in the order the official client shows them
+///
+/// Channels without a category come first under `None`, categories without visible channels are left out
+pub fn channel_tree<'a>(
+	state: &ClientState,
+	guild: &'a Guild,
+) -> Vec<(Option<&'a Channel>, Vec<&'a Channel>)> {
+	// text channels are always above voice channels in the same category
+	let sort_key = |c: &&Channel| (c.r#type.is_voice(), c.position.unwrap_or_default(), c.id);
+
+	let mut categories: Vec<&Channel> = guild
+		.channels
+		.iter()
+		.filter(|c| c.r#type == ChannelType::GuildCategory)
+		.collect();
+	categories.sort_by_key(sort_key);
+
+	let visible: Vec<&Channel> = guild
+		.channels
+		.iter()
+		.filter(|c| {
+			c.r#type != ChannelType::GuildCategory && !c.r#type.is_thread() && state.can_view(c)
+		})
+		.collect();
+
+	let children = |parent: Option<Snowflake>| {
+		let mut channels: Vec<&Channel> = visible
+			.iter()
+			.copied()
+			.filter(|c| c.parent_id == parent)
+			.collect();
+		channels.sort_by_key(sort_key);
+		channels
+	};
+
+	std::iter::once((None, children(None)))
+		.chain(
+			categories
+				.into_iter()
+				.map(|c| (Some(c), children(Some(c.id)))),
+		)
+		.filter(|(_, channels)| !channels.is_empty())
+		.collect()
+}
+
+/// The first text channel in the guild the user can see, opened when the guild is clicked for the first time
+pub fn default_channel(
+	state: &ClientState,
+	guild: &Guild,
+) -> Option<Snowflake> {
+	channel_tree(state, guild)
+		.into_iter()
+		.flat_map(|(_, channels)| channels)
+		.find(|c| !c.r#type.is_voice())
+		.map(|c| c.id)
+}
+
+#[derive(Clone, PartialEq)]
+struct ContextMenu {
+	channel: Channel,
+	x: f64,
+	y: f64,
+}
+
+/// What was picked in a channel's context menu
+#[derive(Clone, Copy, PartialEq)]
+enum ChannelAction {
+	CopyId,
+	MarkAsRead,
+	Export(ExportFormat),
+}
+
+/// The channel sidebar of a guild
+#[component]
+pub fn ChannelList(
+	guild_id: Snowflake,
+	selected: Option<Snowflake>,
+) -> Element {
+	let mut menu = use_signal(|| None::<ContextMenu>);
+	let mut export_status = use_signal(|| None::<String>);
+
+	let state = STATE.read();
+	let Some(guild) = state.guild(guild_id) else {
+		return rsx! {
+			div { class: "grow" }
+		};
+	};
+
+	let collapsed = COLLAPSED_CATEGORIES.read();
+	let on_context_menu = move |(channel, e): (Channel, MouseEvent)| {
+		e.prevent_default();
+		let point = e.client_coordinates();
+		menu.set(Some(ContextMenu {
+			channel,
+			x: point.x,
+			y: point.y,
+		}));
+	};
+
+	// the menu is gone once something is picked, so its requests are spawned here to outlive it
+	let on_action = move |(channel, action): (Channel, ChannelAction)| {
+		let channel_id = channel.id;
+		match action {
+			| ChannelAction::CopyId => {
+				spawn(async move {
+					let _ =
+						document::eval(&format!("navigator.clipboard.writeText('{channel_id}')"))
+							.await;
+				});
+			},
+			| ChannelAction::MarkAsRead => {
+				spawn(async move {
+					if let Err(e) = ack_channel(channel_id).await {
+						error!("failed to mark channel as read: {e}");
+					}
+				});
+			},
+			| ChannelAction::Export(format) => {
+				let name = channel.display_name();
+				let output = export_dir().join(format!(
+					"{}-{channel_id}.{}",
+					file_name(&name),
+					format.extension()
+				));
+
+				spawn(async move {
+					let options = ExportOptions {
+						format,
+						after: None,
+						before: None,
+						download_attachments: false,
+						output: output.clone(),
+					};
+
+					let result = export_channel(channel_id, &options, |fetched| {
+						export_status.set(Some(format!("Exporting #{name}: {fetched} messages")));
+					})
+					.await;
+
+					export_status.set(Some(match result {
+						| Ok(count) => format!("Exported {count} messages to {}", output.display()),
+						| Err(e) => format!("Export of #{name} failed: {e}"),
+					}));
+				});
+			},
+		}
+	};
+
+	let (unread, _) = state.guild_unread(guild);
+
+	rsx! {
+		header { class: "flex flex-row items-center gap-x-2 h-12 shrink-0 px-4 font-bold border-b border-border",
+			span { class: "grow truncate", "{guild.name}" }
+			if unread {
+				button {
+					class: "shrink-0 text-muted-foreground hover:text-foreground",
+					title: "Mark Server as Read",
+					onclick: move |_| {
+						spawn(async move {
+							if let Err(e) = ack_guild(guild_id).await {
+								error!("failed to mark server as read: {e}");
+							}
+						});
+					},
+					CheckCheck { class: "size-4" }
+				}
+			}
+		}
+		div { class: "flex flex-col grow overflow-y-auto px-2 py-3 gap-y-0.5",
+			for (category, channels) in channel_tree(&state, guild) {
+				if let Some(category) = category {
+					{
+						let id = category.id;
+						let is_collapsed = collapsed.contains(&id);
+						rsx! {
+							button {
+								key: "{id}",
+								class: "flex flex-row items-center gap-x-0.5 pt-4 pb-1 text-xs font-bold uppercase text-muted-foreground hover:text-foreground truncate",
+								onclick: move |_| {
+									let mut collapsed = COLLAPSED_CATEGORIES.write();
+									if !collapsed.remove(&id) {
+										collapsed.insert(id);
+									}
+								},
+								if is_collapsed {
+									ChevronRight { class: "size-3 shrink-0" }
+								} else {
+									ChevronDown { class: "size-3 shrink-0" }
+								}
+								"{category.display_name()}"
+							}
+						}
+					}
+				}
+				for channel in channels {
+					// collapsed categories still show the open channel and unread ones, like the official client
+					if !category.is_some_and(|c| collapsed.contains(&c.id))
+						|| selected == Some(channel.id)
+						|| state.is_unread(channel)
+					{
+						ChannelItem {
+							key: "{channel.id}",
+							channel_id: channel.id,
+							active: selected == Some(channel.id),
+							on_context_menu,
+						}
+					}
+				}
+			}
+		}
+		if let Some(status) = export_status() {
+			p {
+				class: "px-3 py-1 text-xs text-muted-foreground border-t border-border truncate",
+				title: "{status}",
+				"{status}"
+			}
+		}
+		if let Some(ContextMenu { channel, x, y }) = menu() {
+			ChannelContextMenu {
+				x,
+				y,
+				on_pick: move |action| on_action((channel.clone(), action)),
+				on_close: move |_| menu.set(None),
+			}
+		}
+	}
+}
+
+#[component]
+fn ChannelItem(
+	channel_id: Snowflake,
+	active: bool,
+	on_context_menu: EventHandler<(Channel, MouseEvent)>,
+) -> Element {
+	let nav = use_navigator();
+	let state = STATE.read();
+	let Some(channel) = state.channel(channel_id) else {
+		return rsx! {};
+	};
+	let Some(guild_id) = channel.guild_id else {
+		return rsx! {};
+	};
+
+	let unread = state.is_unread(channel);
+	let mentions = state.mention_count(channel_id);
+	let is_voice = channel.r#type.is_voice();
+
+	let class = tw_merge!(
+		"group relative flex flex-row items-center gap-x-1.5 rounded-md px-2 py-1.5 text-left text-muted-foreground hover:bg-muted hover:text-foreground",
+		if active {
+			"bg-muted-lighter text-foreground"
+		} else if unread {
+			"text-foreground font-semibold"
+		} else {
+			""
+		}
+	);
+
+	let menu_channel = channel.clone();
+
+	rsx! {
+		button {
+			class,
+			title: channel.topic.clone().unwrap_or_default(),
+			onclick: move |_| {
+				// joining voice isn't supported yet
+				if !is_voice {
+					nav.push(format!("/channels/{guild_id}/{channel_id}").as_str());
+				}
+			},
+			oncontextmenu: move |e| on_context_menu.call((menu_channel.clone(), e)),
+			if unread && !active {
+				span { class: "absolute -left-2 top-1/2 -translate-y-1/2 h-2 w-1 rounded-r-full bg-foreground" }
+			}
+			ChannelIcon { channel_type: channel.r#type, class: "size-5 shrink-0" }
+			span { class: "grow truncate", "{channel.display_name()}" }
+			if mentions > 0 {
+				Badge { count: mentions }
+			}
+		}
+		if is_voice {
+			VoiceParticipants { guild_id, channel_id }
+		}
+	}
+}
+
+/// The icon shown before a channel's name
+#[component]
+pub fn ChannelIcon(
+	channel_type: ChannelType,
+	#[props(into, default)] class: String,
+) -> Element {
+	match channel_type {
+		| ChannelType::GuildVoice => rsx! {
+			Volume2 { class }
+		},
+		| ChannelType::GuildStageVoice => rsx! {
+			Radio { class }
+		},
+		| ChannelType::GuildNews => rsx! {
+			Megaphone { class }
+		},
+		| ChannelType::GuildForum => rsx! {
+			MessagesSquare { class }
+		},
+		| ChannelType::GuildMedia => rsx! {
+			Images { class }
+		},
+		| _ => rsx! {
+			Hash { class }
+		},
+	}
+}
+
+/// The members connected to a voice channel, listed under it
+#[component]
+fn VoiceParticipants(
+	guild_id: Snowflake,
+	channel_id: Snowflake,
+) -> Element {
+	let state = STATE.read();
+	let Some(guild) = state.guild(guild_id) else {
+		return rsx! {};
+	};
+
+	let participants = guild
+		.voice_states
+		.iter()
+		.filter(|s| s.channel_id == Some(channel_id))
+		.filter_map(|voice_state| {
+			// the voice state only includes the member sometimes, otherwise it should be in the guild's members
+			let member = voice_state.member.as_ref().or_else(|| {
+				guild
+					.members
+					.iter()
+					.find(|m| m.user.as_ref().is_some_and(|u| u.id == voice_state.user_id))
+			})?;
+			let user = member.user.as_ref()?;
+			let name = member
+				.nick
+				.clone()
+				.unwrap_or_else(|| user.display_name().to_owned());
+			Some((voice_state, user, name))
+		});
+
+	rsx! {
+		for (voice_state, user, name) in participants {
+			div {
+				key: "{user.id}",
+				class: "flex flex-row items-center gap-x-2 py-0.5 pl-8 pr-2 text-sm text-muted-foreground",
+				img {
+					class: "size-6 rounded-full",
+					src: "{CDN_URL}{user.avatar_endpoint()}",
+				}
+				span { class: "grow truncate", "{name}" }
+				if voice_state.self_mute || voice_state.mute {
+					MicOff { class: "size-3.5 shrink-0" }
+				}
+				if voice_state.self_deaf || voice_state.deaf {
+					HeadphoneOff { class: "size-3.5 shrink-0" }
+				}
+			}
+		}
+	}
+}
+
+#[component]
+fn ChannelContextMenu(
+	x: f64,
+	y: f64,
+	on_pick: EventHandler<ChannelAction>,
+	on_close: EventHandler<()>,
+) -> Element {
+	let pick = move |action: ChannelAction| {
+		on_close.call(());
+		on_pick.call(action);
+	};
+
+	rsx! {
+		// clicking anywhere outside the menu closes it
+		div {
+			class: "fixed inset-0 z-40",
+			onclick: move |_| on_close.call(()),
+			oncontextmenu: move |e| {
+				e.prevent_default();
+				on_close.call(());
+			},
+		}
+		div {
+			class: "fixed z-50 flex flex-col min-w-48 p-1 rounded-md bg-card border border-border shadow-lg text-sm",
+			style: "left: {x}px; top: {y}px",
+			MenuItem { onclick: move |_| pick(ChannelAction::CopyId), "Copy Channel ID" }
+			MenuItem { onclick: move |_| pick(ChannelAction::MarkAsRead), "Mark As Read" }
+			div { class: "my-1 h-px bg-border" }
+			MenuItem { onclick: move |_| pick(ChannelAction::Export(ExportFormat::Html)), "Export as HTML" }
+			MenuItem { onclick: move |_| pick(ChannelAction::Export(ExportFormat::Json)), "Export as JSON" }
+			MenuItem { onclick: move |_| pick(ChannelAction::Export(ExportFormat::Text)), "Export as Text" }
+		}
+	}
+}
+
+#[component]
+fn MenuItem(
+	children: Element,
+	onclick: EventHandler<MouseEvent>,
+) -> Element {
+	rsx! {
+		button {
+			class: "rounded-sm px-2 py-1.5 text-left hover:bg-primary hover:text-primary-foreground",
+			onclick: move |e| onclick.call(e),
+			{children}
+		}
+	}
+}
+
+/// exports from the app go to the downloads folder, or the working directory if there is none
+fn export_dir() -> PathBuf {
+	env::home_dir()
+		.map(|home| home.join("Downloads"))
+		.filter(|dir| dir.is_dir())
+		.unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// replaces characters that aren't allowed in file names on some platforms
+fn file_name(name: &str) -> String {
+	name.chars()
+		.map(|c| {
+			if c.is_alphanumeric() || c == '-' || c == '_' {
+				c
+			} else {
+				'_'
+			}
+		})
+		.collect()
+}
@@ -0,0 +1,552 @@
+use std::time::{Duration, Instant};
+
+use dioxus::html::FileData;
+use dioxus::prelude::*;
+use discord_api::types::permissions::Permissions;
+use discord_api::types::snowflake::Snowflake;
+use discord_api::types::user::User;
+use lucide_dioxus::{Eye, EyeOff, File, Plus, SendHorizontal, X};
+use tailwind_fuse::tw_merge;
+
+use crate::state::STATE;
+use crate::state::drafts::{DRAFTS, save_drafts};
+use crate::state::messages::{MESSAGES, edit_message};
+use crate::state::outbox::{QueuedFile, ReplyTo, queue_message};
+use crate::state::typing::{TYPING, TYPING_INTERVAL, send_typing, typing_text};
+use crate::state::uploads::{MAX_FILES, upload_limit};
+use crate::views::channels::autocomplete::{Suggestion, apply_command, find_query, suggestions};
+use crate::views::channels::message::format_size;
+
+/// The most characters a message can have without nitro
+const MAX_LENGTH: usize = 2000;
+
+/// What the composer does with the next message, shared with the message list through context
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ComposerMode {
+	#[default]
+	Send,
+	Reply(ReplyTo),
+	/// edits one of the user's messages instead of sending a new one
+	Edit(Snowflake),
+}
+
+/// Files waiting to be sent with the next message, shared through context so files can be dropped anywhere on the channel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UploadQueue {
+	pub files: Signal<Vec<QueuedFile>>,
+	/// why some of the last picked files weren't added
+	pub error: Signal<Option<String>>,
+}
+
+impl Default for UploadQueue {
+	fn default() -> Self {
+		UploadQueue {
+			files: Signal::new(Vec::new()),
+			error: Signal::new(None),
+		}
+	}
+}
+
+impl UploadQueue {
+	/// Adds picked or dropped files, leaving out ones over the user's upload limit
+	pub fn add(
+		&mut self,
+		channel_id: Snowflake,
+		picked: Vec<FileData>,
+	) {
+		let state = STATE.peek();
+		if !state
+			.channel(channel_id)
+			.is_some_and(|c| state.permissions(c).contains(Permissions::ATTACH_FILES))
+		{
+			self.error.set(Some(
+				"You don't have permission to upload files here".to_owned(),
+			));
+			return;
+		}
+		let limit = upload_limit(&state, channel_id);
+		drop(state);
+		let mut files = self.files.write();
+		let mut too_large = Vec::new();
+		let mut too_many = false;
+
+		for file in picked {
+			if file.size() > limit {
+				too_large.push(file.name());
+			} else if files.len() >= MAX_FILES {
+				too_many = true;
+			} else {
+				files.push(QueuedFile {
+					path: file.path(),
+					filename: file.name(),
+					size: file.size(),
+					spoiler: false,
+					description: None,
+				});
+			}
+		}
+
+		self.error.set(if !too_large.is_empty() {
+			Some(format!(
+				"{} over your upload limit of {}",
+				too_large.join(", "),
+				format_size(limit)
+			))
+		} else if too_many {
+			Some(format!("You can only upload {MAX_FILES} files at a time"))
+		} else {
+			None
+		});
+	}
+}
+
+/// The message input at the bottom of a channel
+///
+/// Enter sends, shift+enter adds a new line, up edits the last message and escape cancels replying or editing
+#[component]
+pub fn Composer(channel_id: Snowflake) -> Element {
+	let mut mode = use_context::<Signal<ComposerMode>>();
+	let mut content = use_signal(|| DRAFTS.peek().get(&channel_id).cloned().unwrap_or_default());
+	// the draft is put aside while editing a message, and brought back after
+	let mut stashed_draft = use_signal(|| None::<String>);
+	let mut uploads = use_context::<UploadQueue>();
+	let mut files = uploads.files;
+	// byte offset of the caret in `content`
+	let mut cursor = use_signal(|| 0);
+	let mut selected = use_signal(|| 0);
+	let mut dismissed = use_signal(|| false);
+	// utf-16 offset the caret is moved to after the next render
+	let mut move_caret = use_signal(|| None::<usize>);
+	// when discord was last told the user is typing
+	let mut typing_sent = use_signal(|| None::<Instant>);
+
+	let input_id = format!("composer-{channel_id}");
+
+	// saved a moment after typing stops, restarting the resource cancels the previous save
+	use_resource(move || {
+		let _ = content();
+		async move {
+			tokio::time::sleep(Duration::from_millis(500)).await;
+			if let Err(e) = save_drafts() {
+				error!("failed to save drafts: {e}");
+			}
+		}
+	});
+
+	use_effect(move || {
+		if let Some(offset) = move_caret() {
+			move_caret.set(None);
+			document::eval(&format!(
+				r#"
+				const input = document.getElementById("composer-{channel_id}");
+				if (input) {{
+					input.focus();
+					input.setSelectionRange({offset}, {offset});
+				}}
+				"#
+			));
+		}
+	});
+
+	let read_cursor = use_callback(move |_: ()| {
+		spawn(async move {
+			let position = document::eval(&format!(
+				r#"return document.getElementById("composer-{channel_id}")?.selectionStart ?? 0"#
+			))
+			.await
+			.ok()
+			.and_then(|v| v.as_u64())
+			.unwrap_or_default();
+			cursor.set(utf16_to_byte(&content.peek(), position as usize));
+		});
+	});
+
+	let mut set_content = move |value: String| {
+		if stashed_draft.peek().is_none() {
+			DRAFTS.write().insert(channel_id, value.clone());
+		}
+		content.set(value);
+	};
+
+	let query = use_memo(move || find_query(&content.read(), cursor()));
+	let suggestions = use_memo(move || {
+		let Some(query) = query() else {
+			return Vec::new();
+		};
+		let recent_authors: Vec<User> = MESSAGES
+			.read()
+			.get(&channel_id)
+			.map(|w| {
+				w.messages
+					.iter()
+					.rev()
+					.take(50)
+					.map(|m| m.author.clone())
+					.collect()
+			})
+			.unwrap_or_default();
+		suggestions(&STATE.read(), channel_id, &recent_authors, &query)
+	});
+	let show_suggestions = !dismissed() && !suggestions.read().is_empty();
+
+	let mut pick = move |suggestion: Suggestion| {
+		let Some(query) = query() else {
+			return;
+		};
+		let mut value = content.peek().clone();
+		let insert = format!("{} ", suggestion.insert);
+		value.replace_range(query.range.clone(), &insert);
+
+		let caret = query.range.start + insert.len();
+		move_caret.set(Some(value[..caret].encode_utf16().count()));
+		cursor.set(caret);
+		set_content(value);
+		selected.set(0);
+	};
+
+	let mut leave_edit = move || {
+		mode.set(ComposerMode::Send);
+		if let Some(draft) = stashed_draft.take() {
+			content.set(draft);
+		}
+	};
+
+	let mut send = move || {
+		let text = content.peek().trim().to_owned();
+		if text.chars().count() > MAX_LENGTH {
+			return;
+		}
+
+		match *mode.peek() {
+			| ComposerMode::Edit(message_id) => {
+				if text.is_empty() {
+					return;
+				}
+				spawn(async move {
+					if let Err(e) = edit_message(channel_id, message_id, text).await {
+						error!("failed to edit message: {e}");
+					}
+				});
+				leave_edit();
+				return;
+			},
+			| ComposerMode::Reply(reply) => {
+				if text.is_empty() && files.peek().is_empty() {
+					return;
+				}
+				queue_message(channel_id, apply_command(&text), Some(reply), files.take());
+			},
+			| ComposerMode::Send => {
+				if text.is_empty() && files.peek().is_empty() {
+					return;
+				}
+				queue_message(channel_id, apply_command(&text), None, files.take());
+			},
+		}
+
+		mode.set(ComposerMode::Send);
+		uploads.error.set(None);
+		// sending the message stops the typing indicator
+		typing_sent.set(None);
+		set_content(String::new());
+		cursor.set(0);
+	};
+
+	let onkeydown = move |e: KeyboardEvent| {
+		if e.is_composing() {
+			return;
+		}
+		let count = suggestions.peek().len();
+
+		if show_suggestions {
+			match e.key() {
+				| Key::ArrowUp => {
+					e.prevent_default();
+					selected.set((selected() + count - 1) % count);
+					return;
+				},
+				| Key::ArrowDown => {
+					e.prevent_default();
+					selected.set((selected() + 1) % count);
+					return;
+				},
+				| Key::Enter | Key::Tab => {
+					e.prevent_default();
+					if let Some(suggestion) = suggestions.peek().get(selected()).cloned() {
+						pick(suggestion);
+					}
+					return;
+				},
+				| Key::Escape => {
+					dismissed.set(true);
+					return;
+				},
+				| _ => {},
+			}
+		}
+
+		match e.key() {
+			| Key::Enter if !e.modifiers().shift() => {
+				e.prevent_default();
+				send();
+			},
+			| Key::Escape => leave_edit(),
+			// like the official client, up in an empty composer edits the last message
+			| Key::ArrowUp if content.peek().is_empty() => {
+				let user_id = STATE.peek().user.as_ref().map(|u| u.id);
+				let last = MESSAGES.peek().get(&channel_id).and_then(|w| {
+					w.messages
+						.iter()
+						.rev()
+						.find(|m| Some(m.author.id) == user_id)
+						.map(|m| (m.id, m.content.clone()))
+				});
+				if let Some((message_id, text)) = last {
+					e.prevent_default();
+					stashed_draft.set(Some(String::new()));
+					mode.set(ComposerMode::Edit(message_id));
+					content.set(text);
+				}
+			},
+			| _ => {},
+		}
+	};
+
+	// picking edit from a message's actions fills in its content
+	use_effect(move || {
+		if let ComposerMode::Edit(message_id) = mode()
+			&& stashed_draft.peek().is_none()
+		{
+			let text = MESSAGES
+				.peek()
+				.get(&channel_id)
+				.and_then(|w| w.get(message_id))
+				.map(|m| m.content.clone())
+				.unwrap_or_default();
+			stashed_draft.set(Some(content.peek().clone()));
+			content.set(text);
+		}
+	});
+
+	let banner = match mode() {
+		| ComposerMode::Send => None,
+		| ComposerMode::Reply(reply) => {
+			let author = MESSAGES
+				.read()
+				.get(&channel_id)
+				.and_then(|w| w.get(reply.message_id))
+				.map(|m| m.author.display_name().to_owned())
+				.unwrap_or_default();
+			Some(format!("Replying to {author}"))
+		},
+		| ComposerMode::Edit(_) => Some("Editing message".to_owned()),
+	};
+	let length = content.read().chars().count();
+	let can_attach = {
+		let state = STATE.read();
+		state
+			.channel(channel_id)
+			.is_some_and(|c| state.permissions(c).contains(Permissions::ATTACH_FILES))
+	};
+	let typing = TYPING.read().get(&channel_id).and_then(|t| typing_text(t));
+
+	rsx! {
+		div { class: "relative shrink-0 px-4 pb-6",
+			if show_suggestions {
+				div { class: "absolute bottom-full inset-x-4 mb-1 flex flex-col py-1 rounded-md border border-border bg-muted-darker shadow-lg",
+					for (i, suggestion) in suggestions.read().iter().cloned().enumerate() {
+						button {
+							key: "{suggestion.insert}",
+							class: tw_merge!(
+								"flex flex-row items-center gap-x-2 px-3 py-1.5 text-left",
+								if i == selected() { "bg-muted-lighter" } else { "hover:bg-muted" }
+							),
+							onmousedown: move |e| e.prevent_default(),
+							onclick: move |_| pick(suggestion.clone()),
+							if let Some(image) = &suggestion.image {
+								img { class: "size-6 shrink-0 rounded-full object-contain", src: "{image}" }
+							} else if let Some(symbol) = &suggestion.symbol {
+								span { class: "flex items-center justify-center size-6 shrink-0 text-lg text-muted-foreground",
+									"{symbol}"
+								}
+							}
+							span { class: "truncate", "{suggestion.label}" }
+							if let Some(detail) = &suggestion.detail {
+								span { class: "ml-auto pl-2 text-xs text-muted-foreground truncate", "{detail}" }
+							}
+						}
+					}
+				}
+			}
+			div { class: "flex flex-col rounded-lg bg-muted",
+				if let Some(banner) = banner {
+					div { class: "flex flex-row items-center gap-x-2 px-4 py-1.5 text-sm text-muted-foreground border-b border-border",
+						span { class: "grow truncate", "{banner}" }
+						if let ComposerMode::Reply(reply) = mode() {
+							button {
+								class: tw_merge!("text-xs font-bold", if reply.mention { "text-link" } else { "" }),
+								title: "Whether the author gets pinged",
+								onclick: move |_| {
+									mode.set(ComposerMode::Reply(ReplyTo {
+										mention: !reply.mention,
+										..reply
+									}));
+								},
+								if reply.mention {
+									"@ON"
+								} else {
+									"@OFF"
+								}
+							}
+						}
+						button { class: "hover:text-foreground", onclick: move |_| leave_edit(),
+							X { class: "size-4" }
+						}
+					}
+				}
+				if !files.read().is_empty() {
+					div { class: "flex flex-row gap-x-2 overflow-x-auto p-3 border-b border-border",
+						for (i, file) in files.read().iter().enumerate() {
+							div {
+								key: "{file.path.display()}",
+								class: "relative flex flex-col items-center gap-y-1 w-40 shrink-0 rounded-md bg-muted-darker p-2",
+								div { class: "flex flex-row gap-x-1 self-end",
+									button {
+										class: tw_merge!(
+											"rounded-md p-1 bg-muted hover:text-foreground",
+											if file.spoiler { "text-foreground" } else { "text-muted-foreground" }
+										),
+										title: if file.spoiler { "Remove spoiler" } else { "Mark as spoiler" },
+										onclick: move |_| {
+											if let Some(file) = files.write().get_mut(i) {
+												file.spoiler = !file.spoiler;
+											}
+										},
+										if file.spoiler {
+											EyeOff { class: "size-3" }
+										} else {
+											Eye { class: "size-3" }
+										}
+									}
+									button {
+										class: "rounded-md p-1 bg-muted text-muted-foreground hover:text-destructive",
+										title: "Remove",
+										onclick: move |_| {
+											files.write().remove(i);
+										},
+										X { class: "size-3" }
+									}
+								}
+								File {
+									class: tw_merge!("size-10 text-muted-foreground", if file.spoiler { "blur-sm" } else { "" }),
+								}
+								span { class: "w-full text-xs text-center truncate", title: "{file.filename}",
+									"{file.filename}"
+								}
+								span { class: "text-[0.625rem] text-muted-foreground", "{format_size(file.size)}" }
+								input {
+									class: "w-full rounded-sm bg-muted px-1.5 py-0.5 text-xs outline-none",
+									placeholder: "Alt text",
+									maxlength: 1024,
+									value: file.description.clone().unwrap_or_default(),
+									oninput: move |e| {
+										if let Some(file) = files.write().get_mut(i) {
+											let value = e.value();
+											file.description = (!value.trim().is_empty()).then_some(value);
+										}
+									},
+								}
+							}
+						}
+					}
+				}
+				if let Some(error) = uploads.error.read().clone() {
+					div { class: "flex flex-row items-center gap-x-2 px-4 py-1.5 text-sm text-destructive border-b border-border",
+						span { class: "grow", "{error}" }
+						button { onclick: move |_| uploads.error.set(None), X { class: "size-4" } }
+					}
+				}
+				div { class: "flex flex-row items-end gap-x-2 px-2",
+					if can_attach && !matches!(mode(), ComposerMode::Edit(_)) {
+						label {
+							class: "p-2 my-1 rounded-full cursor-pointer text-muted-foreground hover:text-foreground",
+							title: "Upload a file",
+							Plus { class: "size-5" }
+							input {
+								r#type: "file",
+								multiple: true,
+								class: "hidden",
+								onchange: move |e| uploads.add(channel_id, e.files()),
+							}
+						}
+					}
+					textarea {
+						id: input_id,
+						class: "grow resize-none bg-transparent py-2.5 max-h-[50vh] field-sizing-content outline-none",
+						rows: 1,
+						placeholder: "Message",
+						value: "{content}",
+						oninput: move |e| {
+							let value = e.value();
+							let is_editing = matches!(*mode.peek(), ComposerMode::Edit(_));
+							if !value.is_empty()
+								&& !is_editing
+								&& typing_sent.peek().is_none_or(|t| t.elapsed() >= TYPING_INTERVAL)
+							{
+								typing_sent.set(Some(Instant::now()));
+								spawn(async move {
+									if let Err(e) = send_typing(channel_id).await {
+										warn!("failed to send typing: {e}");
+									}
+								});
+							}
+							set_content(value);
+							dismissed.set(false);
+							selected.set(0);
+							read_cursor.call(());
+						},
+						onkeydown,
+						onkeyup: move |e| {
+							if matches!(e.key(), Key::ArrowLeft | Key::ArrowRight | Key::Home | Key::End) {
+								read_cursor.call(());
+							}
+						},
+						onclick: move |_| read_cursor.call(()),
+					}
+					if length > MAX_LENGTH - 200 {
+						span {
+							class: tw_merge!("py-2.5 text-xs", if length > MAX_LENGTH { "text-destructive" } else { "text-muted-foreground" }),
+							"{MAX_LENGTH as i64 - length as i64}"
+						}
+					}
+					button {
+						class: "p-2 my-1 rounded-full text-muted-foreground hover:text-foreground",
+						title: "Send",
+						onclick: move |_| send(),
+						SendHorizontal { class: "size-5" }
+					}
+				}
+			}
+			if let Some(typing) = typing {
+				span { class: "absolute bottom-1 inset-x-4 h-4 text-xs text-muted-foreground truncate",
+					"{typing}"
+				}
+			}
+		}
+	}
+}
+
+/// Converts a utf-16 offset from the webview to a byte offset into `text`
+fn utf16_to_byte(
+	text: &str,
+	offset: usize,
+) -> usize {
+	let mut units = 0;
+	for (i, c) in text.char_indices() {
+		if units >= offset {
+			return i;
+		}
+		units += c.len_utf16();
+	}
+	text.len()
+}
@@ -0,0 +1,35 @@
+use dioxus::prelude::*;
+use discord_api::types::snowflake::Snowflake;
+
+use crate::views::channels::channel::ChannelView;
+use crate::views::channels::channel_list::LAST_CHANNELS;
+
+#[component]
+pub fn GuildChannel(
+	guild_id: Snowflake,
+	channel_id: Snowflake,
+) -> Element {
+	use_effect(use_reactive!(|guild_id, channel_id| {
+		LAST_CHANNELS.write().insert(guild_id, channel_id);
+	}));
+
+	rsx! {
+		ChannelView { key: "{channel_id}", channel_id }
+	}
+}
+
+/// A message link, opens the channel scrolled to the message
+#[component]
+pub fn GuildMessage(
+	guild_id: Snowflake,
+	channel_id: Snowflake,
+	message_id: Snowflake,
+) -> Element {
+	use_effect(use_reactive!(|guild_id, channel_id| {
+		LAST_CHANNELS.write().insert(guild_id, channel_id);
+	}));
+
+	rsx! {
+		ChannelView { key: "{channel_id}", channel_id, jump_to: message_id }
+	}
+}
@@ -0,0 +1,222 @@
+use dioxus::prelude::*;
+use discord_api::CDN_URL;
+use discord_api::endpoints::cdn::GUILD_ICON;
+use discord_api::types::settings::GuildFolder;
+use discord_api::types::snowflake::Snowflake;
+use lucide_dioxus::{Folder, MessageCircle};
+use tailwind_fuse::tw_merge;
+
+use crate::components::ui::Badge;
+use crate::state::STATE;
+use crate::views::channels::channel_list::{LAST_CHANNELS, default_channel};
+
+const ITEM_CLASS: &str = "relative flex items-center justify-center size-12 shrink-0 transition-all bg-muted hover:bg-primary hover:text-primary-foreground";
+
+/// The vertical list of guilds and folders on the left
+#[component]
+pub fn GuildRail(selected: Option<Snowflake>) -> Element {
+	let nav = use_navigator();
+	let state = STATE.read();
+
+	let dm_mentions: u32 = state
+		.private_channels
+		.iter()
+		.map(|c| state.mention_count(c.id))
+		.sum();
+
+	rsx! {
+		nav { class: "flex flex-col items-center gap-y-2 w-[72px] shrink-0 py-3 overflow-y-auto bg-background",
+			RailItem { active: selected.is_none(), unread: false,
+				button {
+					class: tw_merge!(ITEM_CLASS, if selected.is_none() { "rounded-2xl bg-primary text-primary-foreground" } else { "rounded-3xl hover:rounded-2xl" }),
+					title: "Direct Messages",
+					onclick: move |_| {
+						nav.push("/channels/@me");
+					},
+					MessageCircle { class: "size-6" }
+					if dm_mentions > 0 {
+						Badge {
+							count: dm_mentions,
+							class: "absolute -bottom-0.5 -right-0.5 ring-4 ring-background",
+						}
+					}
+				}
+			}
+			div { class: "w-8 h-0.5 shrink-0 rounded-full bg-border" }
+			for folder in state.guild_folders() {
+				if folder.is_folder() {
+					GuildFolderItem {
+						key: "folder-{folder.id.unwrap_or_default()}",
+						folder: folder.clone(),
+						selected,
+					}
+				} else {
+					for guild_id in folder.guild_ids {
+						GuildItem { key: "{guild_id}", guild_id, active: selected == Some(guild_id) }
+					}
+				}
+			}
+		}
+	}
+}
+
+/// Adds the pill on the left of rail items showing whether it's selected or unread
+#[component]
+fn RailItem(
+	active: bool,
+	unread: bool,
+	children: Element,
+) -> Element {
+	let pill_class = tw_merge!(
+		"absolute left-0 top-1/2 -translate-y-1/2 w-1 rounded-r-full bg-foreground transition-all",
+		if active {
+			"h-10"
+		} else if unread {
+			"h-2 group-hover:h-5"
+		} else {
+			"h-0 group-hover:h-5"
+		}
+	);
+
+	rsx! {
+		div { class: "group relative flex w-full justify-center",
+			span { class: pill_class }
+			{children}
+		}
+	}
+}
+
+#[component]
+fn GuildItem(
+	guild_id: Snowflake,
+	active: bool,
+) -> Element {
+	let nav = use_navigator();
+	let state = STATE.read();
+	let Some(guild) = state.guild(guild_id) else {
+		return rsx! {};
+	};
+
+	let (unread, mentions) = state.guild_unread(guild);
+	let target = LAST_CHANNELS
+		.read()
+		.get(&guild_id)
+		.copied()
+		.or_else(|| default_channel(&state, guild));
+
+	rsx! {
+		RailItem { active, unread,
+			button {
+				class: tw_merge!(ITEM_CLASS, if active { "rounded-2xl bg-primary text-primary-foreground" } else { "rounded-3xl hover:rounded-2xl" }),
+				title: "{guild.name}",
+				onclick: move |_| {
+					if let Some(channel_id) = target {
+						nav.push(format!("/channels/{guild_id}/{channel_id}").as_str());
+					}
+				},
+				GuildIcon { guild_id, class: "rounded-[inherit]" }
+				if mentions > 0 {
+					Badge {
+						count: mentions,
+						class: "absolute -bottom-0.5 -right-0.5 ring-4 ring-background",
+					}
+				}
+			}
+		}
+	}
+}
+
+/// The guild's icon, or the initials of its name if it has none
+#[component]
+pub fn GuildIcon(
+	guild_id: Snowflake,
+	#[props(into, default)] class: String,
+) -> Element {
+	let state = STATE.read();
+	let Some(guild) = state.guild(guild_id) else {
+		return rsx! {};
+	};
+
+	match &guild.icon {
+		| Some(hash) => rsx! {
+			img {
+				class: tw_merge!("size-full object-cover", class),
+				src: "{CDN_URL}{GUILD_ICON(&guild_id, hash)}",
+				alt: "{guild.name}",
+			}
+		},
+		| None => rsx! {
+			span { class: tw_merge!("flex size-full items-center justify-center overflow-hidden text-sm font-medium", class),
+				"{initials(&guild.name)}"
+			}
+		},
+	}
+}
+
+/// A folder of guilds, shows a preview of the first few guilds while closed
+#[component]
+fn GuildFolderItem(
+	folder: GuildFolder,
+	selected: Option<Snowflake>,
+) -> Element {
+	let mut open = use_signal(|| false);
+	let state = STATE.read();
+
+	let active = selected.is_some_and(|id| folder.guild_ids.contains(&id));
+	let (unread, mentions) = folder
+		.guild_ids
+		.iter()
+		.filter_map(|id| state.guild(*id))
+		.map(|g| state.guild_unread(g))
+		.fold((false, 0), |(unread, mentions), (u, m)| {
+			(unread || u, mentions + m)
+		});
+
+	let color = folder
+		.color
+		.map_or("var(--primary)".to_owned(), |c| format!("#{c:06x}"));
+	let name = folder.name.clone().unwrap_or("Folder".to_owned());
+
+	rsx! {
+		div { class: "flex flex-col items-center gap-y-2 w-full",
+			RailItem { active: active && !open(), unread: unread && !open(),
+				button {
+					class: tw_merge!(ITEM_CLASS, "rounded-2xl"),
+					style: "background-color: color-mix(in srgb, {color} 40%, transparent)",
+					title: "{name}",
+					onclick: move |_| open.toggle(),
+					if open() {
+						span { style: "color: {color}",
+							Folder { class: "size-6" }
+						}
+					} else {
+						div { class: "grid grid-cols-2 gap-0.5 p-1.5 size-full",
+							for guild_id in folder.guild_ids.iter().take(4).copied() {
+								GuildIcon { key: "{guild_id}", guild_id, class: "rounded-full text-[0.5rem]" }
+							}
+						}
+					}
+					if mentions > 0 && !open() {
+						Badge {
+							count: mentions,
+							class: "absolute -bottom-0.5 -right-0.5 ring-4 ring-background",
+						}
+					}
+				}
+			}
+			if open() {
+				for guild_id in folder.guild_ids.iter().copied() {
+					GuildItem { key: "{guild_id}", guild_id, active: selected == Some(guild_id) }
+				}
+			}
+		}
+	}
+}
+
+/// "cool guild name" -> "cgn", like the official client does for guilds without an icon
+fn initials(name: &str) -> String {
+	name.split_whitespace()
+		.filter_map(|word| word.chars().next())
+		.take(5)
+		.collect()
+}
@@ -0,0 +1,122 @@
+use dioxus::prelude::*;
+use discord_api::CDN_URL;
+use lucide_dioxus::{FlaskConical, LoaderCircle, LogOut};
+
+use crate::Route;
+use crate::state::messages::{MESSAGES, apply_message_event};
+use crate::state::outbox::{apply_outbox_event, clear_outbox, run_outbox};
+use crate::state::reactions::apply_reaction_event;
+use crate::state::typing::apply_typing_event;
+use crate::state::{ClientState, STATE};
+use crate::utils::token::delete_token;
+use crate::views::channels::channel_list::ChannelList;
+use crate::views::channels::guild_rail::GuildRail;
+use crate::views::channels::me::PrivateChannelList;
+use crate::ws::gateway::run_gateway;
+
+/// Wraps every `/channels` route, keeps the gateway connected, sends queued messages and shows the guild rail and channel sidebar
+#[component]
+pub fn Channels() -> Element {
+	let nav = use_navigator();
+	let route = use_route::<Route>();
+
+	use_future(run_outbox);
+	use_future(move || async move {
+		let on_event = |event| {
+			apply_outbox_event(&event);
+			apply_message_event(&event);
+			apply_reaction_event(&event);
+			apply_typing_event(&event);
+			STATE.write().apply(event);
+		};
+		if let Err(e) = run_gateway(on_event).await {
+			error!("gateway error: {e}");
+			nav.replace("/login");
+		}
+	});
+
+	if !STATE.read().is_ready() {
+		return rsx! {
+			div { class: "flex h-screen items-center justify-center",
+				LoaderCircle { class: "animate-spin size-8" }
+			}
+		};
+	}
+
+	let (guild_id, channel_id) = match route {
+		| Route::GuildChannel {
+			guild_id,
+			channel_id,
+		}
+		| Route::GuildMessage {
+			guild_id,
+			channel_id,
+			..
+		} => (Some(guild_id), Some(channel_id)),
+		| Route::MeChannel {
+			channel_id,
+		}
+		| Route::MeMessage {
+			channel_id, ..
+		} => (None, Some(channel_id)),
+		| _ => (None, None),
+	};
+
+	rsx! {
+		div { class: "flex flex-row h-screen",
+			GuildRail { selected: guild_id }
+			div { class: "flex flex-col w-60 shrink-0 bg-muted-darker border-r border-border",
+				if let Some(guild_id) = guild_id {
+					ChannelList { guild_id, selected: channel_id }
+				} else {
+					PrivateChannelList { selected: channel_id }
+				}
+				UserPanel {}
+			}
+			div { class: "flex flex-col grow min-w-0", Outlet::<Route> {} }
+		}
+	}
+}
+
+/// The logged in user at the bottom of the sidebar
+#[component]
+fn UserPanel() -> Element {
+	let nav = use_navigator();
+	let state = STATE.read();
+	let Some(user) = &state.user else {
+		return rsx! {};
+	};
+
+	rsx! {
+		div { class: "flex flex-row items-center gap-x-2 p-2 border-t border-border",
+			img {
+				class: "size-8 rounded-full",
+				src: "{CDN_URL}{user.avatar_endpoint()}",
+			}
+			div { class: "flex flex-col grow min-w-0 leading-tight",
+				span { class: "text-sm font-bold truncate", "{user.display_name()}" }
+				span { class: "text-xs text-muted-foreground truncate", "{user.username}" }
+			}
+			button {
+				class: "p-1.5 rounded-md text-muted-foreground hover:text-foreground hover:bg-muted",
+				title: "Experiments",
+				onclick: move |_| {
+					nav.push("/settings/experiments");
+				},
+				FlaskConical { class: "size-4" }
+			}
+			button {
+				class: "p-1.5 rounded-md text-muted-foreground hover:text-foreground hover:bg-muted",
+				title: "Log out",
+				onclick: move |_| {
+					let _ = delete_token();
+					*STATE.write() = ClientState::default();
+					MESSAGES.write().clear();
+					clear_outbox();
+					nav.replace("/login");
+				},
+				LogOut { class: "size-4" }
+			}
+		}
+	}
+}
@@ -1,22 +1,131 @@
 use dioxus::prelude::*;
+use discord_api::CDN_URL;
+use discord_api::endpoints::cdn::CHANNEL_ICON;
+use discord_api::types::channel::Channel;
+use discord_api::types::snowflake::Snowflake;
+use lucide_dioxus::{MessageCircle, Users};
+use tailwind_fuse::tw_merge;
 
-use crate::utils::token::delete_token;
+use crate::components::ui::Badge;
+use crate::state::STATE;
+use crate::views::channels::channel::ChannelView;
 
 #[component]
 pub fn Me() -> Element {
-	let nav = use_navigator();
 	rsx! {
-		button {
-			onclick: |_| {
-				let _ = delete_token();
-			},
-			"delete token"
+		div { class: "flex flex-col items-center gap-y-2 m-auto text-muted-foreground",
+			MessageCircle { class: "size-10" }
+			p { "Pick a conversation to start chatting" }
+		}
+	}
+}
+
+#[component]
+pub fn MeChannel(channel_id: Snowflake) -> Element {
+	rsx! {
+		ChannelView { key: "{channel_id}", channel_id }
+	}
+}
+
+/// A message link in a private channel, opens the channel scrolled to the message
+#[component]
+pub fn MeMessage(
+	channel_id: Snowflake,
+	message_id: Snowflake,
+) -> Element {
+	rsx! {
+		ChannelView { key: "{channel_id}", channel_id, jump_to: message_id }
+	}
+}
+
+/// The DMs and group DMs sidebar, most recently active first
+#[component]
+pub fn PrivateChannelList(selected: Option<Snowflake>) -> Element {
+	let state = STATE.read();
+
+	let mut channels: Vec<&Channel> = state.private_channels.iter().collect();
+	// channels without messages sort by when they were created instead
+	channels.sort_by_key(|c| std::cmp::Reverse(c.last_message_id.unwrap_or(c.id)));
+
+	rsx! {
+		header { class: "flex items-center h-12 shrink-0 px-4 font-bold border-b border-border",
+			"Direct Messages"
+		}
+		div { class: "flex flex-col grow overflow-y-auto px-2 py-3 gap-y-0.5",
+			for channel in channels {
+				PrivateChannelItem {
+					key: "{channel.id}",
+					channel_id: channel.id,
+					active: selected == Some(channel.id),
+				}
+			}
+		}
+	}
+}
+
+#[component]
+fn PrivateChannelItem(
+	channel_id: Snowflake,
+	active: bool,
+) -> Element {
+	let nav = use_navigator();
+	let state = STATE.read();
+	let Some(channel) = state.channel(channel_id) else {
+		return rsx! {};
+	};
+
+	let unread = state.is_unread(channel);
+	let mentions = state.mention_count(channel_id);
+	let recipients = channel.recipients.as_deref().unwrap_or_default();
+
+	let class = tw_merge!(
+		"flex flex-row items-center gap-x-3 rounded-md px-2 py-1.5 text-left text-muted-foreground hover:bg-muted hover:text-foreground",
+		if active {
+			"bg-muted-lighter text-foreground"
+		} else if unread {
+			"text-foreground font-semibold"
+		} else {
+			""
 		}
+	);
+
+	rsx! {
 		button {
+			class,
 			onclick: move |_| {
-				nav.replace("/");
+				nav.push(format!("/channels/@me/{channel_id}").as_str());
 			},
-			"goto home"
+			// group dms without an icon get a generic one, dms show the other user
+			{
+				match (&channel.icon, recipients) {
+					| (Some(icon), _) => rsx! {
+						img {
+							class: "size-8 shrink-0 rounded-full",
+							src: "{CDN_URL}{CHANNEL_ICON(&channel_id, icon)}",
+						}
+					},
+					| (None, [recipient]) => rsx! {
+						img {
+							class: "size-8 shrink-0 rounded-full",
+							src: "{CDN_URL}{recipient.avatar_endpoint()}",
+						}
+					},
+					| (None, _) => rsx! {
+						span { class: "flex items-center justify-center size-8 shrink-0 rounded-full bg-primary text-primary-foreground",
+							Users { class: "size-4" }
+						}
+					},
+				}
+			}
+			div { class: "flex flex-col grow min-w-0 leading-tight",
+				span { class: "truncate", "{channel.display_name()}" }
+				if recipients.len() > 1 {
+					span { class: "text-xs text-muted-foreground truncate", "{recipients.len() + 1} Members" }
+				}
+			}
+			if mentions > 0 {
+				Badge { count: mentions }
+			}
 		}
 	}
 }
@@ -0,0 +1,376 @@
+use cowcord_markdown::TimestampStyle;
+use dioxus::prelude::*;
+use discord_api::CDN_URL;
+use discord_api::types::emoji::Emoji;
+use discord_api::types::message::{Attachment, Message, MessageType, ReactionType};
+use discord_api::types::permissions::Permissions;
+use discord_api::types::snowflake::Snowflake;
+use lucide_dioxus::{File, Pencil, Reply, RotateCw, SmilePlus, Trash2, X};
+use tailwind_fuse::tw_merge;
+
+use crate::components::markdown::{Markdown, Timestamp};
+use crate::components::ui::RoleName;
+use crate::state::STATE;
+use crate::state::messages::MESSAGES;
+use crate::state::outbox::{OUTBOX, ReplyTo, SendState, discard_message, retry_message};
+use crate::state::reactions::{add_reaction, remove_reaction};
+use crate::state::uploads::UPLOAD_PROGRESS;
+use crate::views::channels::composer::ComposerMode;
+use crate::views::channels::reactions::{QuickReactions, Reactions};
+
+/// A message in the message list, with a date divider above it if it's the first of the day
+#[component]
+pub fn MessageRow(
+	channel_id: Snowflake,
+	message_id: Snowflake,
+	group_start: bool,
+	new_day: bool,
+	/// shows the "new messages" divider above the message
+	new_messages: bool,
+	highlighted: bool,
+	/// called with the id of the replied message when the reply preview is clicked
+	on_jump: EventHandler<Snowflake>,
+) -> Element {
+	let mut mode = use_context::<Signal<ComposerMode>>();
+	// whether the quick reaction picker is open, which keeps the actions shown
+	let mut picking = use_signal(|| false);
+	let messages = MESSAGES.read();
+	let outbox = OUTBOX.read();
+	// messages being sent are looked up by their nonce, which is the id of their echo
+	let pending = outbox.iter().find(|p| p.nonce == message_id);
+	let Some(message) = messages
+		.get(&channel_id)
+		.and_then(|w| w.get(message_id))
+		.or(pending.map(|p| &p.echo))
+	else {
+		return rsx! {};
+	};
+	let timestamp = (message.id.timestamp() / 1000) as i64;
+	let send_state = pending.map(|p| p.state);
+	let (is_own, can_react, author_colors) = {
+		let state = STATE.read();
+		let guild = state
+			.channel(channel_id)
+			.and_then(|c| c.guild_id)
+			.and_then(|id| state.guild(id));
+		(
+			state
+				.user
+				.as_ref()
+				.is_some_and(|u| u.id == message.author.id),
+			state
+				.channel(channel_id)
+				.is_some_and(|c| state.permissions(c).contains(Permissions::ADD_REACTIONS)),
+			guild.and_then(|g| {
+				let member = g
+					.members
+					.iter()
+					.find(|m| m.user.as_ref().is_some_and(|u| u.id == message.author.id))?;
+				g.member_colors(member)
+			}),
+		)
+	};
+
+	let class = tw_merge!(
+		"group relative flex flex-row gap-x-4 px-4 py-0.5 hover:bg-muted-darker transition-colors",
+		if highlighted {
+			"bg-primary-muted hover:bg-primary-muted"
+		} else {
+			""
+		},
+		if send_state.is_some_and(|s| s != SendState::Failed) {
+			"opacity-60"
+		} else {
+			""
+		}
+	);
+
+	rsx! {
+		if new_day {
+			div { class: "flex flex-row items-center gap-x-2 px-4 pt-4 pb-1 text-xs font-semibold text-muted-foreground",
+				span { class: "grow h-px bg-border" }
+				Timestamp { timestamp, style: TimestampStyle::LongDate, class: "bg-transparent" }
+				span { class: "grow h-px bg-border" }
+			}
+		} else if group_start {
+			div { class: "h-4" }
+		}
+		if new_messages {
+			div { class: "flex flex-row items-center px-4 py-1",
+				span { class: "grow h-px bg-destructive" }
+				span { class: "rounded-sm px-1 text-[0.625rem] font-bold uppercase bg-destructive text-white",
+					"New"
+				}
+			}
+		}
+		if let Some(referenced) = &message.referenced_message {
+			ReplyPreview {
+				referenced: referenced
+					.as_deref()
+					.map(|m| (m.id, m.author.display_name().to_owned(), m.content.clone())),
+				on_jump,
+			}
+		}
+		div { class,
+			if send_state.is_none() && system_text(message).is_none() {
+				div {
+					class: tw_merge!(
+						"absolute -top-3 right-4 flex-row rounded-md border border-border bg-muted shadow",
+						if picking() { "flex" } else { "hidden group-hover:flex" }
+					),
+					if can_react {
+						button {
+							class: "p-1.5 text-muted-foreground hover:text-foreground",
+							title: "Add Reaction",
+							onclick: move |_| picking.toggle(),
+							SmilePlus { class: "size-4" }
+						}
+					}
+					button {
+						class: "p-1.5 text-muted-foreground hover:text-foreground",
+						title: "Reply",
+						onclick: move |_| {
+							mode.set(
+								ComposerMode::Reply(ReplyTo {
+									message_id,
+									mention: true,
+								}),
+							)
+						},
+						Reply { class: "size-4" }
+					}
+					if is_own {
+						button {
+							class: "p-1.5 text-muted-foreground hover:text-foreground",
+							title: "Edit",
+							onclick: move |_| mode.set(ComposerMode::Edit(message_id)),
+							Pencil { class: "size-4" }
+						}
+					}
+					if picking() {
+						QuickReactions {
+							reactions: message.reactions.clone(),
+							on_pick: move |(emoji, reacted): (Emoji, bool)| {
+								spawn(async move {
+									let result = if reacted {
+										remove_reaction(channel_id, message_id, &emoji, ReactionType::Normal).await
+									} else {
+										add_reaction(channel_id, message_id, &emoji, ReactionType::Normal).await
+									};
+									if let Err(e) = result {
+										error!("failed to toggle reaction: {e}");
+									}
+								});
+							},
+							on_close: move |_| picking.set(false),
+						}
+					}
+				}
+			}
+			if group_start {
+				img {
+					class: "size-10 shrink-0 rounded-full mt-0.5",
+					src: "{CDN_URL}{message.author.avatar_endpoint()}",
+				}
+			} else {
+				div { class: "flex justify-end w-10 shrink-0 pt-1 invisible group-hover:visible text-[0.625rem] text-muted-foreground",
+					Timestamp { timestamp, style: TimestampStyle::ShortTime, class: "bg-transparent px-0" }
+				}
+			}
+			div { class: "flex flex-col min-w-0 grow",
+				if group_start {
+					div { class: "flex flex-row items-baseline gap-x-2",
+						span { class: "font-medium truncate", title: "{message.author.username}",
+							RoleName {
+								name: message.author.display_name().to_owned(),
+								colors: author_colors,
+							}
+						}
+						Timestamp {
+							timestamp,
+							style: TimestampStyle::ShortDateTime,
+							class: "bg-transparent px-0 text-xs text-muted-foreground shrink-0",
+						}
+					}
+				}
+				if let Some(text) = system_text(message) {
+					p { class: "italic text-muted-foreground", "{text}" }
+				} else if !message.content.is_empty() {
+					div { class: "flex flex-row flex-wrap items-baseline gap-x-1",
+						Markdown {
+							content: message.content.clone(),
+							mentions: message.mentions.clone(),
+							class: "min-w-0",
+						}
+						if message.edited_timestamp.is_some() {
+							span { class: "text-[0.625rem] text-muted-foreground", "(edited)" }
+						}
+					}
+				}
+				for attachment in &message.attachments {
+					AttachmentView { key: "{attachment.id}", attachment: attachment.clone() }
+				}
+				if !message.reactions.is_empty() {
+					Reactions { channel_id, message_id, reactions: message.reactions.clone() }
+				}
+				if let Some((sent, total)) = UPLOAD_PROGRESS.read().get(&message_id).copied() {
+					div { class: "flex flex-row items-center gap-x-2 max-w-md mt-1",
+						div { class: "grow h-1.5 rounded-full bg-muted overflow-hidden",
+							div {
+								class: "h-full bg-primary transition-[width]",
+								style: "width: {sent as f64 / total.max(1) as f64 * 100.0}%",
+							}
+						}
+						span { class: "text-xs text-muted-foreground shrink-0",
+							"{format_size(sent)} / {format_size(total)}"
+						}
+						button {
+							class: "text-muted-foreground hover:text-destructive",
+							title: "Cancel upload",
+							onclick: move |_| discard_message(message_id),
+							X { class: "size-4" }
+						}
+					}
+				}
+				if send_state == Some(SendState::Failed) {
+					div { class: "flex flex-row items-center gap-x-2 text-sm text-destructive",
+						"Message failed to send."
+						button {
+							class: "flex flex-row items-center gap-x-1 text-link hover:underline",
+							onclick: move |_| retry_message(message_id),
+							RotateCw { class: "size-3" }
+							"Retry"
+						}
+						button {
+							class: "flex flex-row items-center gap-x-1 text-link hover:underline",
+							onclick: move |_| discard_message(message_id),
+							Trash2 { class: "size-3" }
+							"Delete"
+						}
+					}
+				}
+			}
+		}
+	}
+}
+
+/// The message being replied to, shown above the reply
+#[component]
+fn ReplyPreview(
+	/// the id, author name and content of the message, `None` if it was deleted
+	referenced: Option<(Snowflake, String, String)>,
+	on_jump: EventHandler<Snowflake>,
+) -> Element {
+	rsx! {
+		div { class: "flex flex-row items-center gap-x-1 pl-9 pr-4 pt-0.5 text-sm text-muted-foreground",
+			Reply { class: "size-4 shrink-0 -scale-x-100" }
+			if let Some((id, author, content)) = referenced {
+				button {
+					class: "flex flex-row gap-x-1 min-w-0 hover:text-foreground",
+					onclick: move |_| on_jump.call(id),
+					span { class: "font-medium shrink-0", "{author}" }
+					span { class: "truncate", "{content}" }
+				}
+			} else {
+				span { class: "italic", "Original message was deleted" }
+			}
+		}
+	}
+}
+
+#[component]
+fn AttachmentView(attachment: Attachment) -> Element {
+	let is_image = attachment
+		.content_type
+		.as_deref()
+		.is_some_and(|t| t.starts_with("image/"));
+
+	if is_image {
+		// the size is set up front so the row doesn't change height when the image loads
+		let (width, height) = match (attachment.width, attachment.height) {
+			| (Some(w), Some(h)) => fit(w, h, 400, 300),
+			| _ => (400, 300),
+		};
+
+		return rsx! {
+			a { href: "{attachment.url}", class: "w-fit mt-1",
+				img {
+					class: "rounded-md object-contain bg-muted-darker",
+					width: "{width}",
+					height: "{height}",
+					src: "{attachment.proxy_url}",
+					alt: attachment.description.clone().unwrap_or(attachment.filename.clone()),
+				}
+			}
+		};
+	}
+
+	rsx! {
+		a {
+			class: "flex flex-row items-center gap-x-2 w-fit max-w-md mt-1 rounded-md border border-border bg-muted-darker p-3",
+			href: "{attachment.url}",
+			File { class: "size-8 shrink-0 text-muted-foreground" }
+			div { class: "flex flex-col min-w-0",
+				span { class: "text-link truncate", "{attachment.filename}" }
+				span { class: "text-xs text-muted-foreground", "{format_size(attachment.size)}" }
+			}
+		}
+	}
+}
+
+/// Scales the size down to fit inside the bounds, keeping its aspect ratio
+fn fit(
+	width: u32,
+	height: u32,
+	max_width: u32,
+	max_height: u32,
+) -> (u32, u32) {
+	let scale = (max_width as f64 / width as f64)
+		.min(max_height as f64 / height as f64)
+		.min(1.0);
+	(
+		(width as f64 * scale).round() as u32,
+		(height as f64 * scale).round() as u32,
+	)
+}
+
+/// A file size in bytes, kilobytes or megabytes
+pub fn format_size(bytes: u64) -> String {
+	match bytes {
+		| ..1024 => format!("{bytes} B"),
+		| ..1_048_576 => format!("{:.1} KB", bytes as f64 / 1024.0),
+		| _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+	}
+}
+
+/// The text shown instead of the content for system messages
+fn system_text(message: &Message) -> Option<String> {
+	let author = message.author.display_name();
+	let target = message
+		.mentions
+		.first()
+		.map_or("someone", |u| u.display_name());
+
+	Some(match message.r#type {
+		| MessageType::RecipientAdd => format!("{author} added {target} to the group."),
+		| MessageType::RecipientRemove => format!("{author} removed {target} from the group."),
+		| MessageType::Call => format!("{author} started a call."),
+		| MessageType::ChannelNameChange => {
+			format!("{author} changed the channel name: {}", message.content)
+		},
+		| MessageType::ChannelIconChange => format!("{author} changed the channel icon."),
+		| MessageType::ChannelPinnedMessage => {
+			format!("{author} pinned a message to this channel.")
+		},
+		| MessageType::UserJoin => format!("{author} joined the server."),
+		| MessageType::PremiumGuildSubscription
+		| MessageType::PremiumGuildSubscriptionTier1
+		| MessageType::PremiumGuildSubscriptionTier2
+		| MessageType::PremiumGuildSubscriptionTier3 => format!("{author} boosted the server!"),
+		| MessageType::ChannelFollowAdd => {
+			format!("{author} added {} to this channel.", message.content)
+		},
+		| MessageType::ThreadCreated => format!("{author} started a thread: {}", message.content),
+		| _ => return None,
+	})
+}
@@ -0,0 +1,438 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use discord_api::types::guild::Guild;
+use discord_api::types::message::{Message, MessageType};
+use discord_api::types::snowflake::Snowflake;
+use lucide_dioxus::{ArrowDown, LoaderCircle};
+
+use crate::components::markdown::{MentionNames, MentionRole};
+use crate::state::STATE;
+use crate::state::messages::{HistoryFetch, MESSAGES, load_messages};
+use crate::state::outbox::OUTBOX;
+use crate::state::read_states::ack_channel;
+use crate::views::channels::message::MessageRow;
+
+/// used for rows that haven't been measured yet, in pixels
+const ESTIMATED_GROUP_HEIGHT: f64 = 64.0;
+const ESTIMATED_ROW_HEIGHT: f64 = 24.0;
+/// how far outside the viewport rows are still rendered, in pixels
+const OVERSCAN: f64 = 800.0;
+/// how close to either end of the loaded messages the next page starts loading, in pixels
+const LOAD_THRESHOLD: f64 = 1200.0;
+/// messages from the same author are grouped while they're sent within this long of each other, in milliseconds
+const GROUP_WINDOW: u64 = 7 * 60 * 1000;
+const DAY: u64 = 24 * 60 * 60 * 1000;
+
+/// What the scroll position follows when rows are added, removed or measured
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ScrollAnchor {
+	/// stays at the newest message
+	Bottom,
+	/// keeps the message `offset` pixels below the top of the viewport
+	Message { id: Snowflake, offset: f64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct RowInfo {
+	id: Snowflake,
+	/// shows the author and avatar, instead of continuing the previous message
+	group_start: bool,
+	/// shows a date divider above the message
+	new_day: bool,
+	/// shows the "new messages" divider above the message, it's the first one the user hasn't read
+	new_messages: bool,
+}
+
+/// The channel's messages, only rendering the rows near the viewport
+///
+/// Row heights are measured as they render, rows that haven't been rendered yet use an estimate
+#[component]
+pub fn MessageList(
+	channel_id: Snowflake,
+	/// scrolls to and highlights this message, loading the history around it if needed
+	jump_to: Option<Snowflake>,
+) -> Element {
+	let mut heights = use_signal(HashMap::<Snowflake, f64>::new);
+	let mut intro_height = use_signal(|| 0.0);
+	// scroll top and height of the viewport
+	let mut viewport = use_signal(|| (0.0, 0.0));
+	let mut anchor = use_signal(|| ScrollAnchor::Bottom);
+	let mut loading = use_signal(|| false);
+	let mut failed = use_signal(|| false);
+	let mut highlighted = use_signal(|| None::<Snowflake>);
+	// the last message the user had read when the channel was opened, `None` if it was read
+	let last_read = use_hook(|| {
+		let state = STATE.peek();
+		let channel = state.channel(channel_id)?;
+		if !state.is_unread(channel) {
+			return None;
+		}
+		state.read_states.get(&channel_id)?.last_message_id
+	});
+
+	use_context_provider(|| {
+		let state = STATE.peek();
+		let guild = state
+			.channel(channel_id)
+			.and_then(|c| c.guild_id)
+			.and_then(|id| state.guild(id));
+		mention_names(guild)
+	});
+
+	// minutes behind utc, so date dividers follow the user's days
+	let timezone_offset = use_resource(|| async {
+		document::eval("return new Date().getTimezoneOffset()")
+			.await
+			.ok()
+			.and_then(|v| v.as_i64())
+			.unwrap_or_default()
+	});
+
+	let load = use_callback(move |fetch: HistoryFetch| {
+		if *loading.peek() {
+			return;
+		}
+		loading.set(true);
+		spawn(async move {
+			if let Err(e) = load_messages(channel_id, fetch).await {
+				error!("failed to load messages: {e}");
+				failed.set(true);
+			}
+			loading.set(false);
+		});
+	});
+
+	let jump = use_callback(move |target: Option<Snowflake>| {
+		let (at_present, loaded) = MESSAGES
+			.peek()
+			.get(&channel_id)
+			.map_or((false, false), |w| {
+				(!w.has_after, target.is_some_and(|id| w.contains(id)))
+			});
+
+		match target {
+			| Some(id) => {
+				anchor.set(ScrollAnchor::Message {
+					id,
+					offset: viewport.peek().1 / 3.0,
+				});
+				highlighted.set(Some(id));
+				spawn(async move {
+					tokio::time::sleep(Duration::from_secs(2)).await;
+					if *highlighted.peek() == Some(id) {
+						highlighted.set(None);
+					}
+				});
+				if !loaded {
+					load.call(HistoryFetch::Around(id));
+				}
+			},
+			| None => {
+				anchor.set(ScrollAnchor::Bottom);
+				if !at_present {
+					load.call(HistoryFetch::Latest);
+				}
+			},
+		}
+	});
+
+	use_effect(use_reactive!(|jump_to| jump.call(jump_to)));
+
+	// `None` until the first page is loaded, then whether there are older and newer messages to load
+	let window = use_memo(move || {
+		MESSAGES
+			.read()
+			.get(&channel_id)
+			.map(|w| (w.has_before, w.has_after))
+	});
+
+	let rows = use_memo(move || {
+		let timezone_offset = timezone_offset().unwrap_or_default() * 60 * 1000;
+		let local_day = |m: &Message| (m.id.timestamp() as i64 - timezone_offset) as u64 / DAY;
+
+		let messages = MESSAGES.read();
+		let Some(window) = messages.get(&channel_id) else {
+			return Vec::new();
+		};
+
+		// messages still being sent go after the newest message, so they're only shown at the present
+		let outbox = OUTBOX.read();
+		let pending = outbox
+			.iter()
+			.filter(|p| p.channel_id == channel_id && !window.has_after)
+			.map(|p| &p.echo);
+
+		let mut previous: Option<&Message> = None;
+		let has_before = window.has_before;
+		window
+			.messages
+			.iter()
+			.chain(pending)
+			.map(|message| {
+				let row = RowInfo {
+					id: message.id,
+					group_start: starts_group(previous, message),
+					new_day: previous.is_none_or(|p| local_day(p) != local_day(message)),
+					// the divider isn't shown if the last read message isn't loaded
+					new_messages: last_read.is_some_and(|read| {
+						message.id > read && previous.map_or(!has_before, |p| p.id <= read)
+					}),
+				};
+				previous = Some(message);
+				row
+			})
+			.collect::<Vec<_>>()
+	});
+
+	// the top of each row and the height of everything, from the top of the list
+	let layout = use_memo(move || {
+		let heights = heights.read();
+		let mut top = match window() {
+			| Some((false, _)) => intro_height(),
+			| _ => 0.0,
+		};
+		let offsets: Vec<f64> = rows
+			.read()
+			.iter()
+			.map(|row| {
+				let offset = top;
+				top += heights.get(&row.id).copied().unwrap_or(if row.group_start {
+					ESTIMATED_GROUP_HEIGHT
+				} else {
+					ESTIMATED_ROW_HEIGHT
+				});
+				offset
+			})
+			.collect();
+		(offsets, top)
+	});
+
+	// keeps the anchor in place, this is what stops the view jumping when older messages are added above it
+	use_effect(move || {
+		let (offsets, total) = &*layout.read();
+		let (top, height) = *viewport.peek();
+
+		let target = match anchor() {
+			| ScrollAnchor::Bottom if top + height < total - 1.0 => None,
+			| ScrollAnchor::Bottom => return,
+			| ScrollAnchor::Message {
+				id,
+				offset,
+			} => match rows.peek().iter().position(|r| r.id == id) {
+				| Some(index) => Some((offsets[index] - offset).max(0.0)),
+				| None => return,
+			},
+		};
+
+		if let Some(target) = target {
+			if (target - top).abs() < 1.0 {
+				return;
+			}
+			viewport.write().0 = target;
+		}
+		scroll_to(channel_id, target);
+	});
+
+	// reading the newest messages marks the channel as read, including ones that arrive while at the bottom
+	use_effect(move || {
+		let at_present =
+			anchor() == ScrollAnchor::Bottom && window().is_some_and(|(_, has_after)| !has_after);
+		let unread = {
+			let state = STATE.read();
+			state
+				.channel(channel_id)
+				.is_some_and(|c| state.is_unread(c))
+				|| state.mention_count(channel_id) > 0
+		};
+
+		if at_present && unread {
+			spawn(async move {
+				if let Err(e) = ack_channel(channel_id).await {
+					error!("failed to mark channel as read: {e}");
+				}
+			});
+		}
+	});
+
+	let onscroll = move |e: ScrollEvent| {
+		let top = e.scroll_top();
+		let height = e.client_height() as f64;
+		let bottom_gap = e.scroll_height() as f64 - top - height;
+		viewport.set((top, height));
+
+		let Some((has_before, has_after)) = window() else {
+			return;
+		};
+
+		if bottom_gap <= 2.0 && !has_after {
+			anchor.set(ScrollAnchor::Bottom);
+		} else {
+			let (offsets, _) = &*layout.peek();
+			let rows = rows.peek();
+			// the first row that's at least partly visible
+			let index = offsets.partition_point(|o| *o <= top).saturating_sub(1);
+			if let Some(row) = rows.get(index) {
+				anchor.set(ScrollAnchor::Message {
+					id: row.id,
+					offset: offsets[index] - top,
+				});
+			}
+		}
+
+		if top < LOAD_THRESHOLD && has_before {
+			load.call(HistoryFetch::Before);
+		} else if bottom_gap < LOAD_THRESHOLD && has_after {
+			load.call(HistoryFetch::After);
+		}
+	};
+
+	let (offsets, total) = &*layout.read();
+	let (top, height) = viewport();
+	let rows = rows.read();
+
+	let start = offsets
+		.partition_point(|o| *o < top - OVERSCAN)
+		.saturating_sub(1);
+	let end = offsets.partition_point(|o| *o < top + height + OVERSCAN);
+	let space_above = offsets.get(start).map_or(0.0, |o| o - offsets[0]);
+	let space_below = offsets.get(end).map_or(0.0, |o| total - o);
+
+	let far_from_present = match window() {
+		| Some((_, true)) => true,
+		| _ => total - top - height > height * 2.0,
+	};
+
+	rsx! {
+		div { class: "relative flex flex-col grow min-h-0",
+			div {
+				id: "messages-{channel_id}",
+				class: "grow overflow-y-auto overflow-x-hidden",
+				onscroll,
+				onresize: move |e| {
+					if let Ok(size) = e.get_border_box_size() {
+						viewport.write().1 = size.height;
+					}
+				},
+				if let Some((false, _)) = window() {
+					div {
+						class: "flex flex-col gap-y-1 px-4 pt-8 pb-4",
+						onresize: move |e| {
+							if let Ok(size) = e.get_border_box_size() {
+								intro_height.set(size.height);
+							}
+						},
+						p { class: "text-2xl font-bold", "This is the start of the channel" }
+						p { class: "text-muted-foreground", "There are no older messages." }
+					}
+				}
+				div { style: "height: {space_above}px" }
+				for row in rows[start.min(rows.len())..end.min(rows.len())].iter().copied() {
+					div {
+						key: "{row.id}",
+						onresize: move |e| {
+							if let Ok(size) = e.get_border_box_size()
+								&& heights.peek().get(&row.id) != Some(&size.height)
+							{
+								heights.write().insert(row.id, size.height);
+							}
+						},
+						MessageRow {
+							channel_id,
+							message_id: row.id,
+							group_start: row.group_start,
+							new_day: row.new_day,
+							new_messages: row.new_messages,
+							highlighted: highlighted() == Some(row.id),
+							on_jump: move |id| jump.call(Some(id)),
+						}
+					}
+				}
+				div { style: "height: {space_below}px" }
+				div { class: "h-4" }
+			}
+			if loading() {
+				div { class: "absolute top-2 left-1/2 -translate-x-1/2 rounded-full p-1.5 bg-muted shadow",
+					LoaderCircle { class: "animate-spin size-4" }
+				}
+			}
+			if failed() {
+				button {
+					class: "absolute top-2 left-1/2 -translate-x-1/2 rounded-full px-3 py-1 text-sm bg-destructive text-white shadow",
+					onclick: move |_| {
+						failed.set(false);
+						jump.call(jump_to);
+					},
+					"Couldn't load messages, retry"
+				}
+			}
+			if far_from_present {
+				button {
+					class: "absolute bottom-4 right-4 flex items-center gap-x-1 rounded-full px-3 py-1.5 text-sm bg-primary text-primary-foreground shadow",
+					onclick: move |_| jump.call(None),
+					"Jump to present"
+					ArrowDown { class: "size-4" }
+				}
+			}
+		}
+	}
+}
+
+/// whether the message starts a new group, showing its author again
+fn starts_group(
+	previous: Option<&Message>,
+	message: &Message,
+) -> bool {
+	let Some(previous) = previous else {
+		return true;
+	};
+	let is_plain = |m: &Message| m.r#type == MessageType::Default;
+
+	// replies always show their author, under the message they reply to
+	!is_plain(message)
+		|| !is_plain(previous)
+		|| previous.author.id != message.author.id
+		|| message.id.timestamp() - previous.id.timestamp() > GROUP_WINDOW
+}
+
+fn scroll_to(
+	channel_id: Snowflake,
+	top: Option<f64>,
+) {
+	let top = top.map_or("list.scrollHeight".to_owned(), |t| t.to_string());
+	document::eval(&format!(
+		r#"
+		const list = document.getElementById("messages-{channel_id}");
+		if (list) list.scrollTop = {top};
+		"#
+	));
+}
+
+/// What mentions resolve to in the guild, private channels only have the users sent with each message
+fn mention_names(guild: Option<&Guild>) -> MentionNames {
+	let Some(guild) = guild else {
+		return MentionNames::default();
+	};
+
+	MentionNames {
+		users: guild
+			.members
+			.iter()
+			.filter_map(|m| m.user.clone())
+			.map(|u| (u.id, u))
+			.collect(),
+		roles: guild
+			.roles
+			.iter()
+			.map(|r| {
+				(r.id, MentionRole {
+					name: r.name.clone(),
+					color: r.color.into(),
+				})
+			})
+			.collect(),
+		channels: guild.channels.iter().map(|c| (c.id, c.clone())).collect(),
+		on_user_click: None,
+	}
+}
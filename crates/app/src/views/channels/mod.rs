@@ -1 +1,11 @@
+pub mod autocomplete;
+pub mod channel;
+pub mod channel_list;
+pub mod composer;
+pub mod guild;
+pub mod guild_rail;
+pub mod layout;
 pub mod me;
+pub mod message;
+pub mod message_list;
+pub mod reactions;
@@ -0,0 +1,235 @@
+use dioxus::prelude::*;
+use discord_api::CDN_URL;
+use discord_api::endpoints::cdn::CUSTOM_EMOJI;
+use discord_api::types::emoji::Emoji;
+use discord_api::types::message::{Reaction, ReactionType};
+use discord_api::types::snowflake::Snowflake;
+use discord_api::types::user::User;
+use tailwind_fuse::tw_merge;
+
+use crate::state::reactions::{add_reaction, get_reactors, remove_reaction};
+
+/// Offered in the quick reaction picker of a message's actions
+const QUICK_REACTIONS: [&str; 6] = ["👍", "❤️", "😂", "😮", "😢", "🎉"];
+/// How many reactors are named in a pill's tooltip
+const REACTORS_PREVIEW: u8 = 10;
+
+/// The reaction pills under a message, super reactions get a pill of their own
+#[component]
+pub fn Reactions(
+	channel_id: Snowflake,
+	message_id: Snowflake,
+	reactions: Vec<Reaction>,
+) -> Element {
+	let pills = reactions.iter().flat_map(|reaction| {
+		let details = reaction.count_details;
+		// older messages can be missing the count details
+		let normal = if details.normal + details.burst == 0 {
+			reaction.count
+		} else {
+			details.normal
+		};
+		[
+			(ReactionType::Normal, normal, reaction.me, None),
+			(
+				ReactionType::Burst,
+				details.burst,
+				reaction.me_burst,
+				reaction.burst_colors.first().cloned(),
+			),
+		]
+		.into_iter()
+		.filter(|(_, count, ..)| *count > 0)
+		.map(|(reaction_type, count, me, color)| {
+			(reaction.emoji.clone(), reaction_type, count, me, color)
+		})
+	});
+
+	rsx! {
+		div { class: "flex flex-row flex-wrap gap-1 mt-1",
+			for (emoji , reaction_type , count , me , color) in pills {
+				ReactionPill {
+					key: "{emoji.reaction_id()}-{reaction_type as u8}",
+					channel_id,
+					message_id,
+					emoji,
+					reaction_type,
+					count,
+					me,
+					color,
+				}
+			}
+		}
+	}
+}
+
+#[component]
+fn ReactionPill(
+	channel_id: Snowflake,
+	message_id: Snowflake,
+	emoji: Emoji,
+	reaction_type: ReactionType,
+	count: u32,
+	/// whether the user reacted with this
+	me: bool,
+	/// the color of a super reaction
+	color: Option<String>,
+) -> Element {
+	let mut hovered = use_signal(|| false);
+	// the first few reactors, and the count they were fetched at so they're fetched again when it changes
+	let mut reactors = use_signal(|| None::<(u32, Vec<User>)>);
+
+	let onmouseenter = {
+		let emoji = emoji.clone();
+		move |_| {
+			hovered.set(true);
+			if reactors.peek().as_ref().is_some_and(|(c, _)| *c == count) {
+				return;
+			}
+			let emoji = emoji.clone();
+			spawn(async move {
+				match get_reactors(
+					channel_id,
+					message_id,
+					&emoji,
+					reaction_type,
+					None,
+					REACTORS_PREVIEW,
+				)
+				.await
+				{
+					| Ok(users) => reactors.set(Some((count, users))),
+					| Err(e) => error!("failed to get reactors: {e}"),
+				}
+			});
+		}
+	};
+
+	let onclick = {
+		let emoji = emoji.clone();
+		move |_| {
+			let emoji = emoji.clone();
+			spawn(async move {
+				let result = if me {
+					remove_reaction(channel_id, message_id, &emoji, reaction_type).await
+				} else {
+					add_reaction(channel_id, message_id, &emoji, reaction_type).await
+				};
+				if let Err(e) = result {
+					error!("failed to toggle reaction: {e}");
+				}
+			});
+		}
+	};
+
+	let tooltip = reactors.read().as_ref().map(|(_, users)| {
+		let names: Vec<&str> = users.iter().map(|u| u.display_name()).collect();
+		let others = (count as usize).saturating_sub(names.len());
+		let names = match names.as_slice() {
+			| [] => format!("{count} people"),
+			| [.., last] if others == 0 && names.len() > 1 => {
+				format!("{} and {last}", names[..names.len() - 1].join(", "))
+			},
+			| _ if others > 0 => format!("{} and {others} others", names.join(", ")),
+			| _ => names.join(", "),
+		};
+		format!("{names} reacted with {}", emoji_label(&emoji))
+	});
+
+	rsx! {
+		div { class: "relative",
+			button {
+				class: tw_merge!(
+					"flex flex-row items-center gap-x-1.5 px-1.5 py-0.5 rounded-md border text-sm",
+					if me {
+						"border-primary bg-primary-muted"
+					} else {
+						"border-transparent bg-muted hover:border-border"
+					}
+				),
+				style: color.as_ref().map(|c| format!("border-color: {c}")),
+				onmouseenter,
+				onmouseleave: move |_| hovered.set(false),
+				onclick,
+				EmojiView { emoji: emoji.clone() }
+				span { class: tw_merge!("font-medium", if me { "text-primary" } else { "text-muted-foreground" }),
+					"{count}"
+				}
+			}
+			if hovered() && let Some(tooltip) = tooltip {
+				div { class: "absolute bottom-full left-0 mb-1 z-10 w-max max-w-64 rounded-md bg-muted-darker px-2 py-1 text-xs shadow-lg",
+					"{tooltip}"
+				}
+			}
+		}
+	}
+}
+
+/// A small popover of common reactions, opened from a message's actions
+#[component]
+pub fn QuickReactions(
+	/// the message's current reactions, so picking one the user already added removes it
+	reactions: Vec<Reaction>,
+	/// called with the picked emoji and whether the user already reacted with it, the
+	/// request is sent by the caller since the popover is gone once it's closed
+	on_pick: EventHandler<(Emoji, bool)>,
+	on_close: EventHandler,
+) -> Element {
+	rsx! {
+		div { class: "absolute top-full right-0 mt-1 z-10 flex flex-row gap-x-1 rounded-md border border-border bg-muted p-1 shadow-lg",
+			for name in QUICK_REACTIONS {
+				button {
+					key: "{name}",
+					class: "rounded-md p-1 text-lg leading-none hover:bg-muted-lighter",
+					onclick: {
+						let reacted = reactions.iter().any(|r| r.me && r.emoji.name.as_deref() == Some(name));
+						move |_| {
+							on_pick.call((unicode_emoji(name), reacted));
+							on_close.call(());
+						}
+					},
+					"{name}"
+				}
+			}
+		}
+	}
+}
+
+#[component]
+fn EmojiView(emoji: Emoji) -> Element {
+	let name = emoji.name.clone().unwrap_or_default();
+
+	match emoji.id {
+		| Some(id) => rsx! {
+			img {
+				class: "size-4 object-contain",
+				alt: ":{name}:",
+				src: "{CDN_URL}{CUSTOM_EMOJI(&id, emoji.animated)}",
+			}
+		},
+		| None => rsx! {
+			span { class: "leading-none", "{name}" }
+		},
+	}
+}
+
+/// How the emoji is named in text, `:name:` for custom emojis
+fn emoji_label(emoji: &Emoji) -> String {
+	let name = emoji.name.as_deref().unwrap_or_default();
+	match emoji.id {
+		| Some(_) => format!(":{name}:"),
+		| None => name.to_owned(),
+	}
+}
+
+fn unicode_emoji(name: &str) -> Emoji {
+	Emoji {
+		id: None,
+		name: Some(name.to_owned()),
+		roles: Vec::new(),
+		require_colons: false,
+		managed: false,
+		animated: false,
+		available: true,
+	}
+}
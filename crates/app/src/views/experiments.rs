@@ -0,0 +1,269 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use dioxus::prelude::*;
+use discord_api::types::rollout::experiment_hash;
+use discord_api::types::snowflake::Snowflake;
+
+use crate::components::ui::{Button, ButtonSize, ButtonVariant};
+use crate::state::STATE;
+use crate::state::experiments::{EXPERIMENT_OVERRIDES, fetch_experiments, set_override};
+
+/// Every user and guild experiment with the bucket it resolves to, and local overrides for them
+///
+/// Discord only sends hashes, so names are only known for guild experiments with a hash key, or when searched for
+#[component]
+pub fn Experiments() -> Element {
+	let mut query = use_signal(String::new);
+	let mut selected_guild = use_signal(|| None::<Snowflake>);
+	let mut fetched =
+		use_resource(|| async { fetch_experiments().await.map_err(|e| e.to_string()) });
+
+	let state = STATE.read();
+	let overrides = EXPERIMENT_OVERRIDES.read();
+	let search = Search::new(&query.read());
+	let guild = selected_guild()
+		.or(state.guilds.first().map(|g| g.id))
+		.and_then(|id| state.guild(id));
+	let now = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.unwrap_or_default()
+		.as_millis() as u64;
+
+	let user_rows: Vec<Row> = state
+		.experiments
+		.iter()
+		.filter(|e| search.matches(e.hash, &[e.holdout_name.as_deref()]))
+		.map(|e| Row {
+			hash: e.hash,
+			name: search.name(e.hash),
+			details: format!(
+				"revision {}, population {}, position {}",
+				e.revision, e.population, e.hash_result
+			),
+			bucket: Some(e.bucket),
+			effective: state.user_bucket(e.hash),
+			aa_mode: e.aa_mode != 0,
+			r#override: overrides.get(&e.hash).copied(),
+		})
+		.collect();
+
+	let guild_rows: Vec<Row> = state
+		.guild_experiments
+		.iter()
+		.filter(|e| search.matches(e.hash, &[e.hash_key.as_deref(), e.holdout_name.as_deref()]))
+		.map(|e| {
+			let name = search.name(e.hash).or(e.hash_key.clone());
+			// the rollout position needs the name when there's no hash key
+			let assignment = guild.zip(name.as_deref()).and_then(|(guild, name)| {
+				e.assign(name, &state.guild_experiments, &guild.into(), now)
+			});
+			let details = match (&name, assignment) {
+				| (None, _) => format!("revision {}, search its name for the bucket", e.revision),
+				| (Some(_), Some(a)) => format!("revision {}, position {}", e.revision, a.position),
+				| (Some(_), None) => format!("revision {}, not in the rollout", e.revision),
+			};
+			// without a name or a guild only an override can give the bucket
+			let effective = match (&name, guild) {
+				| (Some(name), Some(guild)) => state.guild_bucket(name, guild.id),
+				| _ => overrides.get(&e.hash).copied(),
+			};
+
+			Row {
+				hash: e.hash,
+				name,
+				details,
+				bucket: assignment.map(|a| a.bucket),
+				effective,
+				aa_mode: e.aa_mode != 0,
+				r#override: overrides.get(&e.hash).copied(),
+			}
+		})
+		.collect();
+
+	rsx! {
+		div { class: "flex flex-col h-full",
+			div { class: "flex flex-row items-center gap-x-2 p-3 border-b border-border",
+				input {
+					class: "border-border border bg-muted-darker rounded-md h-8 px-2 grow",
+					placeholder: "Search by name or hash",
+					value: "{query}",
+					oninput: move |e| query.set(e.value()),
+				}
+				Button {
+					variant: ButtonVariant::Secondary,
+					size: ButtonSize::Sm,
+					onclick: move |_| fetched.restart(),
+					"Refresh"
+				}
+			}
+			match &*fetched.read() {
+				| None => rsx! {
+					p { class: "text-xs text-muted-foreground px-3 pt-2", "Fetching assignments..." }
+				},
+				| Some(Err(e)) => rsx! {
+					p { class: "text-xs text-destructive px-3 pt-2",
+						"Couldn't fetch assignments, showing the ones from READY: {e}"
+					}
+				},
+				| Some(Ok(())) => rsx! {},
+			}
+			div { class: "flex flex-col overflow-y-auto grow p-3 gap-y-4",
+				div { class: "flex flex-col gap-y-1",
+					p { class: "text-xs font-bold uppercase text-muted-foreground",
+						"User experiments ({user_rows.len()})"
+					}
+					for row in user_rows {
+						ExperimentRow { key: "{row.hash}", row }
+					}
+				}
+				div { class: "flex flex-col gap-y-1",
+					div { class: "flex flex-row items-center gap-x-2",
+						p { class: "text-xs font-bold uppercase text-muted-foreground grow",
+							"Guild experiments ({guild_rows.len()})"
+						}
+						select {
+							class: "border-border border bg-muted-darker rounded-md h-8 px-2",
+							onchange: move |e| selected_guild.set(e.value().parse().ok()),
+							for g in &state.guilds {
+								option {
+									key: "{g.id}",
+									value: "{g.id}",
+									selected: guild.is_some_and(|guild| guild.id == g.id),
+									"{g.name}"
+								}
+							}
+						}
+					}
+					for row in guild_rows {
+						ExperimentRow { key: "{row.hash}", row }
+					}
+				}
+			}
+		}
+	}
+}
+
+/// An experiment as shown in the list
+#[derive(Debug, Clone, PartialEq)]
+struct Row {
+	hash: i64,
+	name: Option<String>,
+	details: String,
+	/// the assigned bucket, `None` if the user or guild isn't in the experiment
+	bucket: Option<i64>,
+	/// the bucket the client acts on, after A/A tests and overrides
+	effective: Option<i64>,
+	aa_mode: bool,
+	r#override: Option<i64>,
+}
+
+#[component]
+fn ExperimentRow(row: Row) -> Element {
+	let hash = row.hash;
+	let label = |bucket: Option<i64>| match bucket {
+		| Some(bucket) => format!("bucket {bucket}"),
+		| None => "no bucket".to_owned(),
+	};
+	let on_override = move |e: Event<FormData>| {
+		let value = e.value();
+		let bucket = if value.trim().is_empty() {
+			None
+		} else {
+			match value.trim().parse() {
+				| Ok(bucket) => Some(bucket),
+				| Err(_) => return,
+			}
+		};
+		if let Err(e) = set_override(hash, bucket) {
+			error!("failed to save the experiment override: {e}");
+		}
+	};
+
+	rsx! {
+		div { class: "flex flex-row items-center gap-x-3 rounded-md px-2 py-1 hover:bg-muted",
+			div { class: "flex flex-col grow min-w-0 leading-tight",
+				span { class: "font-mono text-sm truncate",
+					if let Some(name) = &row.name {
+						"{name} "
+					}
+					span { class: "text-muted-foreground", "{hash}" }
+				}
+				span { class: "text-xs text-muted-foreground truncate", "{row.details}" }
+			}
+			div { class: "flex flex-row gap-x-1.5 text-sm",
+				// the assigned bucket is crossed out when an override or an A/A test changes it
+				if row.effective != row.bucket {
+					span { class: "text-muted-foreground line-through", {label(row.bucket)} }
+				}
+				span {
+					{label(row.effective)}
+					if row.aa_mode {
+						" (A/A)"
+					}
+				}
+			}
+			input {
+				r#type: "number",
+				class: "border-border border bg-muted-darker rounded-md h-8 px-2 w-20",
+				placeholder: "Override",
+				value: row.r#override.map(|b| b.to_string()).unwrap_or_default(),
+				onchange: on_override,
+			}
+			if row.r#override.is_some() {
+				Button {
+					variant: ButtonVariant::Ghost,
+					size: ButtonSize::Sm,
+					onclick: move |_| {
+						if let Err(e) = set_override(hash, None) {
+							error!("failed to save the experiment override: {e}");
+						}
+					},
+					"Clear"
+				}
+			}
+		}
+	}
+}
+
+/// What the search box matches experiments on
+struct Search {
+	/// lowercased, for matching names
+	text: String,
+	name: String,
+	/// the hash of the text, for when it's an experiment name
+	hash: i64,
+}
+
+impl Search {
+	fn new(text: &str) -> Self {
+		let text = text.trim();
+		Search {
+			text: text.to_lowercase(),
+			name: text.to_owned(),
+			hash: experiment_hash(text),
+		}
+	}
+
+	/// whether the experiment's hash starts with the text, the text is its name, or one of the names it sends has the text
+	fn matches(
+		&self,
+		hash: i64,
+		names: &[Option<&str>],
+	) -> bool {
+		self.text.is_empty()
+			|| hash == self.hash
+			|| hash.to_string().starts_with(&self.text)
+			|| names
+				.iter()
+				.flatten()
+				.any(|name| name.to_lowercase().contains(&self.text))
+	}
+
+	/// the text, if it's the experiment's name
+	fn name(
+		&self,
+		hash: i64,
+	) -> Option<String> {
+		(!self.name.is_empty() && hash == self.hash).then(|| self.name.clone())
+	}
+}
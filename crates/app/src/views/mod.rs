@@ -1,11 +1,17 @@
 // TODO: jsut make some build script that autogenerates this file
 
+mod experiments;
+pub use experiments::*;
+
 mod home;
 pub use home::*;
 
 mod not_found;
 pub use not_found::*;
 
+mod package;
+pub use package::*;
+
 pub mod auth;
 pub use auth::*;
 
@@ -0,0 +1,205 @@
+use std::sync::Arc;
+
+use dioxus::prelude::*;
+use discord_api::types::message::Message;
+use discord_api::types::snowflake::Snowflake;
+
+use crate::components::markdown::Markdown;
+use crate::components::ui::{Button, ButtonSize, ButtonVariant};
+use crate::export::text::short_timestamp;
+use crate::import::{DataPackage, PackageChannel};
+
+/// the data package opened from the login page, `None` until one is picked
+pub static DATA_PACKAGE: GlobalSignal<Option<Arc<DataPackage>>> = Signal::global(|| None);
+
+/// how many messages are shown at first, and how many more each "show more" adds
+const PAGE_SIZE: usize = 100;
+
+/// Read-only view of a data package, works without being logged in
+#[component]
+pub fn Package() -> Element {
+	let nav = use_navigator();
+	let mut selected = use_signal(|| None::<Snowflake>);
+	let mut query = use_signal(String::new);
+	let mut shown = use_signal(|| PAGE_SIZE);
+	// searching goes through every message, so it only runs again when the query changes
+	let results = use_memo(move || {
+		let query = query.read();
+		match DATA_PACKAGE.read().as_ref() {
+			| Some(package) if !query.is_empty() => package.search(&query),
+			| _ => Vec::new(),
+		}
+	});
+
+	let Some(package) = DATA_PACKAGE() else {
+		nav.replace("/login");
+		return rsx! {};
+	};
+
+	let selected_id = selected().or(package.channels.first().map(|c| c.channel.id));
+
+	// private channels first, then guild channels grouped under their guild
+	let mut sections: Vec<(String, Vec<&PackageChannel>)> = vec![(
+		"Direct Messages".to_owned(),
+		package
+			.channels
+			.iter()
+			.filter(|c| c.channel.guild_id.is_none())
+			.collect(),
+	)];
+	for guild in &package.guilds {
+		let channels: Vec<_> = package
+			.channels
+			.iter()
+			.filter(|c| c.channel.guild_id == Some(guild.id))
+			.collect();
+		if !channels.is_empty() {
+			sections.push((guild.name.clone(), channels));
+		}
+	}
+	// channels from guilds missing in the servers index
+	let unknown: Vec<_> = package
+		.channels
+		.iter()
+		.filter(|c| {
+			c.channel
+				.guild_id
+				.is_some_and(|id| !package.guilds.iter().any(|g| g.id == id))
+		})
+		.collect();
+	if !unknown.is_empty() {
+		sections.push(("Unknown Servers".to_owned(), unknown));
+	}
+
+	let searching = !query.read().is_empty();
+	let (messages, total): (Vec<(&PackageChannel, &Message)>, usize) = if searching {
+		let results = results.read();
+		let messages = results
+			.iter()
+			.take(shown())
+			.map(|&(c, m)| {
+				let channel = &package.channels[c];
+				(channel, &channel.messages[m])
+			})
+			.collect();
+		(messages, results.len())
+	} else {
+		selected_id
+			.and_then(|id| package.channel(id))
+			.map(|c| {
+				let messages = c.messages.iter().rev().take(shown()).map(|m| (c, m));
+				(messages.collect(), c.messages.len())
+			})
+			.unwrap_or_default()
+	};
+	let messages_len = messages.len();
+
+	rsx! {
+		div { class: "flex flex-row h-screen",
+			div { class: "flex flex-col w-64 shrink-0 bg-muted-darker border-r border-border",
+				div { class: "p-3 border-b border-border",
+					p { class: "font-bold", "{package.user.display_name()}" }
+					p { class: "text-xs text-muted-foreground", "Data package, read only" }
+				}
+				div { class: "flex flex-col overflow-y-auto grow p-2 gap-y-3",
+					for (name, channels) in sections {
+						div { class: "flex flex-col gap-y-0.5",
+							p { class: "text-xs font-bold uppercase text-muted-foreground px-2",
+								"{name}"
+							}
+							for c in channels {
+								{
+									let id = c.channel.id;
+									let label = channel_label(c);
+									let active = !searching && selected_id == Some(id);
+									rsx! {
+										button {
+											key: "{id}",
+											class: if active { "text-left truncate rounded-md px-2 py-1 bg-muted-lighter" } else { "text-left truncate rounded-md px-2 py-1 text-muted-foreground hover:bg-muted" },
+											onclick: move |_| {
+												selected.set(Some(id));
+												query.set(String::new());
+												shown.set(PAGE_SIZE);
+											},
+											"{label}"
+										}
+									}
+								}
+							}
+						}
+					}
+				}
+				div { class: "p-2 border-t border-border",
+					Button {
+						variant: ButtonVariant::Secondary,
+						size: ButtonSize::Sm,
+						class: "w-full",
+						onclick: move |_| {
+							*DATA_PACKAGE.write() = None;
+							nav.replace("/login");
+						},
+						"Close package"
+					}
+				}
+			}
+			div { class: "flex flex-col grow min-w-0",
+				div { class: "p-3 border-b border-border",
+					input {
+						class: "border-border border bg-muted-darker rounded-md h-8 px-2 w-full",
+						placeholder: "Search your messages",
+						value: "{query}",
+						oninput: move |e| {
+							query.set(e.value());
+							shown.set(PAGE_SIZE);
+						},
+					}
+				}
+				div { class: "flex flex-col-reverse overflow-y-auto grow p-3 gap-y-2",
+					if messages.is_empty() {
+						p { class: "text-muted-foreground m-auto", "No messages" }
+					}
+					for (c, message) in messages {
+						div { key: "{message.id}", class: "flex flex-col",
+							div { class: "flex flex-row gap-x-2 items-baseline",
+								span { class: "font-bold", "{message.author.display_name()}" }
+								span { class: "text-xs text-muted-foreground",
+									"{short_timestamp(&message.timestamp)}"
+								}
+								if searching {
+									span { class: "text-xs text-muted-foreground", "in {channel_label(c)}" }
+								}
+							}
+							Markdown { content: message.content.clone() }
+							for attachment in &message.attachments {
+								a {
+									key: "{attachment.id}",
+									class: "text-link text-sm",
+									href: "{attachment.url}",
+									"{attachment.filename}"
+								}
+							}
+						}
+					}
+					// the list is reversed, so this ends up above the oldest shown message
+					if total > messages_len {
+						Button {
+							variant: ButtonVariant::Ghost,
+							size: ButtonSize::Sm,
+							class: "self-center",
+							onclick: move |_| shown += PAGE_SIZE,
+							"Show more ({total - messages_len} left)"
+						}
+					}
+				}
+			}
+		}
+	}
+}
+
+fn channel_label(channel: &PackageChannel) -> String {
+	match (&channel.channel.name, &channel.label) {
+		| (Some(name), _) => name.to_owned(),
+		| (None, Some(label)) => label.to_owned(),
+		| (None, None) => channel.channel.display_name(),
+	}
+}
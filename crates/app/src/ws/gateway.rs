@@ -0,0 +1,286 @@
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+use discord_api::types::super_properties::ClientProperties;
+use discord_api::types::ws::gateway::{
+	GatewayCapabilities,
+	GatewayEvent,
+	GatewayOpCode,
+	GatewayReceivePayload,
+	GatewaySendPayload,
+	Hello,
+	Identify,
+	Resume,
+};
+use discord_api::{DISCORD_URL, GATEWAY_URL};
+use futures::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio::time::{Instant, Interval, interval_at, sleep};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async};
+use tracing::{debug, trace, warn};
+
+use crate::CONFIG;
+use crate::utils::token::load_token;
+
+/// close codes after which reconnecting won't help, like an invalid token
+const FATAL_CLOSE_CODES: [u16; 6] = [4004, 4010, 4011, 4012, 4013, 4014];
+
+pub struct GatewayWsClient(WebSocketStream<MaybeTlsStream<TcpStream>>);
+
+/// The gateway closed the connection
+#[derive(Debug)]
+pub struct GatewayClosed(pub Option<CloseFrame>);
+
+impl GatewayClosed {
+	pub fn is_fatal(&self) -> bool {
+		self.0
+			.as_ref()
+			.is_some_and(|frame| FATAL_CLOSE_CODES.contains(&u16::from(frame.code)))
+	}
+}
+
+impl Display for GatewayClosed {
+	fn fmt(
+		&self,
+		f: &mut fmt::Formatter<'_>,
+	) -> fmt::Result {
+		write!(f, "Gateway closed. Frame: {:?}", self.0)
+	}
+}
+
+impl std::error::Error for GatewayClosed {}
+
+impl GatewayWsClient {
+	pub async fn connect(url: &str) -> Result<Self, Box<dyn std::error::Error>> {
+		let mut request = url.into_client_request()?;
+		request.headers_mut().insert("Origin", DISCORD_URL.parse()?);
+
+		let (stream, _) = connect_async(request).await?;
+
+		Ok(Self(stream))
+	}
+
+	pub async fn recv_json(
+		&mut self
+	) -> Result<Option<GatewayReceivePayload>, Box<dyn std::error::Error>> {
+		let message = match self.0.next().await {
+			| Some(Ok(msg)) => msg,
+			| Some(Err(e)) => return Err(e.into()),
+			| None => return Err(GatewayClosed(None).into()),
+		};
+
+		let value = match message {
+			| Message::Text(payload) => serde_json::from_str(&payload)?,
+			| Message::Close(frame) => return Err(GatewayClosed(frame).into()),
+			| _ => return Ok(None),
+		};
+
+		Ok(Some(value))
+	}
+
+	pub async fn send_json(
+		&mut self,
+		op: GatewayOpCode,
+		d: impl serde::Serialize,
+	) -> Result<(), Box<dyn std::error::Error>> {
+		let message = serde_json::to_string(&GatewaySendPayload {
+			op,
+			d,
+		})
+		.map(|s| Message::Text(s.into()))?;
+		self.0.send(message).await?;
+		Ok(())
+	}
+
+	pub async fn close(
+		&mut self,
+		msg: Option<CloseFrame>,
+	) -> Result<(), Box<dyn std::error::Error>> {
+		self.0.close(msg).await?;
+		Ok(())
+	}
+
+	pub async fn send_heartbeat(
+		&mut self,
+		sequence: Option<u64>,
+	) -> Result<(), Box<dyn std::error::Error>> {
+		self.send_json(GatewayOpCode::Heartbeat, sequence).await
+	}
+}
+
+/// Keeps a gateway session open, calling `on_event` for every dispatched event
+///
+/// Dropped connections are resumed, only returns if the session can't continue (e.g. the token is invalid)
+pub async fn run_gateway(
+	mut on_event: impl FnMut(GatewayEvent)
+) -> Result<(), Box<dyn std::error::Error>> {
+	let token = load_token()?
+		.filter(|t| t.is_valid())
+		.ok_or("Authorization token is missing or is invalid")?
+		.0;
+
+	// session id and resume url, set once READY is received
+	let mut session: Option<(String, String)> = None;
+	let mut sequence: Option<u64> = None;
+
+	'reconnect: loop {
+		let url = match &session {
+			| Some((_, resume_url)) => format!("{resume_url}/?v=9&encoding=json"),
+			| None => GATEWAY_URL.to_owned(),
+		};
+
+		let mut client = match GatewayWsClient::connect(&url).await {
+			| Ok(client) => client,
+			| Err(e) => {
+				warn!("failed to connect to the gateway: {e}, retrying in 5s...");
+				sleep(Duration::from_secs(5)).await;
+				continue 'reconnect;
+			},
+		};
+
+		let mut heartbeat_interval: Option<Interval> = None;
+		let mut awaiting_ack = false;
+
+		loop {
+			let hb_tick = async {
+				match heartbeat_interval.as_mut() {
+					| Some(iv) => {
+						iv.tick().await;
+					},
+					| None => std::future::pending().await,
+				}
+			};
+
+			tokio::select! {
+				_ = hb_tick => {
+					if awaiting_ack {
+						warn!("heartbeat ack expected but not recieved, reconnecting...");
+						let _ = client.close(None).await;
+						continue 'reconnect;
+					}
+					if let Err(e) = client.send_heartbeat(sequence).await {
+						warn!("failed to send a heartbeat: {e}, reconnecting...");
+						sleep(Duration::from_secs(1)).await;
+						continue 'reconnect;
+					}
+					awaiting_ack = true;
+				}
+
+				result = client.recv_json() => {
+					let payload = match result {
+						| Ok(Some(payload)) => payload,
+						| Ok(None) => continue,
+						| Err(e) => {
+							if e.downcast_ref::<GatewayClosed>().is_some_and(|c| c.is_fatal()) {
+								return Err(e);
+							}
+							warn!("gateway connection lost: {e}, reconnecting...");
+							sleep(Duration::from_secs(1)).await;
+							continue 'reconnect;
+						},
+					};
+
+					if payload.s.is_some() {
+						sequence = payload.s;
+					}
+
+					match payload.op {
+						// server sends this after we connect
+						| GatewayOpCode::Hello => {
+							let hello: Hello = match serde_json::from_value(payload.d) {
+								| Ok(hello) => hello,
+								| Err(e) => {
+									warn!("failed to decode hello: {e}, reconnecting...");
+									let _ = client.close(None).await;
+									sleep(Duration::from_secs(1)).await;
+									continue 'reconnect;
+								},
+							};
+							let period = Duration::from_millis(hello.heartbeat_interval);
+							heartbeat_interval = Some(interval_at(Instant::now() + period, period));
+
+							let sent = match &session {
+								| Some((session_id, _)) => {
+									client
+										.send_json(GatewayOpCode::Resume, Resume {
+											token: token.clone(),
+											session_id: session_id.clone(),
+											seq: sequence,
+										})
+										.await
+								},
+								| None => {
+									let mut properties = ClientProperties::new();
+									properties.system_locale = CONFIG.get().unwrap().locale;
+
+									client
+										.send_json(GatewayOpCode::Identify, Identify {
+											token: token.clone(),
+											capabilities: GatewayCapabilities::empty(),
+											properties,
+											compress: false,
+										})
+										.await
+								},
+							};
+							if let Err(e) = sent {
+								warn!("failed to identify with the gateway: {e}, reconnecting...");
+								sleep(Duration::from_secs(1)).await;
+								continue 'reconnect;
+							}
+						},
+						// the server can ask for a heartbeat outside of the interval
+						| GatewayOpCode::Heartbeat => {
+							if let Err(e) = client.send_heartbeat(sequence).await {
+								warn!("failed to send a heartbeat: {e}, reconnecting...");
+								sleep(Duration::from_secs(1)).await;
+								continue 'reconnect;
+							}
+						},
+						| GatewayOpCode::HeartbeatAck => {
+							awaiting_ack = false;
+						},
+						| GatewayOpCode::Reconnect => {
+							debug!("gateway asked us to reconnect");
+							let _ = client.close(None).await;
+							continue 'reconnect;
+						},
+						// `d` says whether the session can still be resumed
+						| GatewayOpCode::InvalidSession => {
+							if !payload.d.as_bool().unwrap_or_default() {
+								session = None;
+								sequence = None;
+							}
+							let _ = client.close(None).await;
+							sleep(Duration::from_secs(2)).await;
+							continue 'reconnect;
+						},
+						| GatewayOpCode::Dispatch => {
+							let Some(name) = payload.t else {
+								continue;
+							};
+							trace!("gateway recieved {name}");
+
+							match GatewayEvent::from_dispatch(&name, payload.d) {
+								| Ok(event) => {
+									if let GatewayEvent::Ready(ready) = &event {
+										session = Some((
+											ready.session_id.clone(),
+											ready.resume_gateway_url.clone(),
+										));
+									}
+									on_event(event);
+								},
+								| Err(e) => warn!("failed to decode {name} event: {e}"),
+							}
+						},
+						| _ => {},
+					}
+				}
+			}
+		}
+	}
+}
@@ -1,14 +1,32 @@
+use std::time::Duration;
+
+use base64::Engine;
+use base64::prelude::{BASE64_STANDARD, BASE64_URL_SAFE_NO_PAD};
+use discord_api::endpoints::auth::login::{
+	REMOTE_AUTH_TICKET_EXCHANGE,
+	RemoteAuthTicketExchangeRequest,
+	RemoteAuthTicketExchangeResponse,
+};
 use discord_api::types::ws::remote_auth::{
+	REMOTE_AUTH_QR_CODE_URL,
 	RemoteAuthGatewayClientOpCode,
 	RemoteAuthGatewayServerOpCode,
 };
-use discord_api::{DISCORD_URL, REMOTE_AUTH_WS_URL};
+use discord_api::{ApiResponse, DISCORD_URL, REMOTE_AUTH_WS_URL};
 use futures::{SinkExt, StreamExt};
+use rsa::pkcs8::EncodePublicKey;
+use rsa::rand_core::OsRng;
+use rsa::{Oaep, RsaPrivateKey};
+use sha2::{Digest, Sha256};
 use tokio::net::TcpStream;
+use tokio::time::{Instant, Interval, interval_at};
 use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 use tokio_tungstenite::tungstenite::protocol::CloseFrame;
 use tokio_tungstenite::tungstenite::{Error as WsError, Message};
 use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async};
+use tracing::{error, trace, warn};
+
+use crate::utils::request::{AutoHandle, BaseUrl, RequestClient};
 
 pub struct RemoteAuthWsClient(WebSocketStream<MaybeTlsStream<TcpStream>>);
 
@@ -68,3 +86,196 @@ impl RemoteAuthWsClient {
 			.await
 	}
 }
+
+/// Progress of a remote auth session, reported to whoever is showing the qr code
+#[derive(Debug, Clone)]
+pub enum RemoteAuthEvent {
+	/// the handshake completed and the qr code for `url` can be shown
+	QrCode { url: String },
+	/// mobile client has scanned the qr code
+	Accepted {
+		user_id: String,
+		discriminator: String,
+		avatar_hash: String,
+		username: String,
+	},
+	/// mobile client cancelled the login attempt
+	Cancelled,
+}
+
+/// Runs the remote auth handshake until the mobile client accepts the login
+///
+/// returns the decrypted token, it is not saved to the keyring
+pub async fn remote_auth_login(
+	mut on_event: impl FnMut(RemoteAuthEvent)
+) -> Result<String, Box<dyn std::error::Error>> {
+	'reconnect: loop {
+		let mut client = RemoteAuthWsClient::connect().await?;
+
+		// generate rsa and public key
+		let (private_key, public_key) = tokio::task::spawn_blocking(|| {
+			let private_key = RsaPrivateKey::new(&mut OsRng, 2048).map_err(|e| e.to_string())?;
+			let public_key = private_key
+				.to_public_key()
+				.to_public_key_der()
+				.map_err(|e| e.to_string())?
+				.to_vec();
+			Ok::<_, String>((private_key, public_key))
+		})
+		.await
+		.map_err(|e| e.to_string())??;
+
+		let mut heartbeat_interval: Option<Interval> = None;
+		let mut awaiting_ack = false;
+
+		loop {
+			let hb_tick = async {
+				match heartbeat_interval.as_mut() {
+					| Some(iv) => {
+						iv.tick().await;
+					},
+					| None => std::future::pending().await,
+				}
+			};
+
+			tokio::select! {
+				_ = hb_tick => {
+					if awaiting_ack {
+						warn!("heartbeat ack expected but not recieved, reconnecting...");
+						let _ = client.close(None).await;
+						continue 'reconnect;
+					}
+					client.send_heartbeat().await?;
+					awaiting_ack = true;
+				}
+
+				result = client.recv_json() => {
+					let opcode = result?
+						.ok_or("connection closed while waiting for mobile client")?;
+					trace!("remote auth ws recieved {:?}", &opcode);
+
+					match opcode {
+						// server sends tihs after we connect
+						| RemoteAuthGatewayServerOpCode::Hello {
+							heartbeat_interval: interval_ms,
+							timeout_ms: _,
+						} => {
+							let start = Instant::now() + Duration::from_millis(interval_ms);
+							heartbeat_interval = Some(interval_at(start, Duration::from_millis(interval_ms)));
+
+							// we need to send Init opcode after recieving the Hello opcode
+							client
+								.send_json(&RemoteAuthGatewayClientOpCode::Init {
+									encoded_public_key: BASE64_STANDARD.encode(&public_key),
+								})
+								.await?;
+						},
+						// if gateway accepts the public key we sent in the Init
+						// then it will respond withan encrypted nonce
+						// that we have to verify with our private key
+						| RemoteAuthGatewayServerOpCode::NonceProof {
+							encrypted_nonce,
+						} => {
+							// decrypt the nonce
+							let encrypted_nonce_bytes = BASE64_STANDARD.decode(&encrypted_nonce)?;
+							let decrypted_nonce = private_key.decrypt(
+								Oaep::new::<Sha256>(),
+								&encrypted_nonce_bytes
+							)?;
+							let nonce_proof = BASE64_URL_SAFE_NO_PAD.encode(&decrypted_nonce);
+
+							// send the decrypted nonce
+							client
+								.send_json(&RemoteAuthGatewayClientOpCode::NonceProof {
+									nonce: nonce_proof,
+								})
+								.await?;
+						},
+						// server verifies we're waiting on the mobile device to init
+						| RemoteAuthGatewayServerOpCode::PendingRemoteInit {
+							fingerprint,
+						} => {
+							// validate the fingerprint we recieved
+							let expected_fingerprint = BASE64_URL_SAFE_NO_PAD.encode(Sha256::digest(&public_key));
+
+							// if fingerprint isnt correct, close connection and reconnect
+							if fingerprint != expected_fingerprint {
+								error!(
+									"fingerprint mismatch! discord: {fingerprint} expected: {expected_fingerprint}, reconnecting..."
+								);
+								let _ = client.close(None).await;
+								continue 'reconnect;
+							}
+
+							on_event(RemoteAuthEvent::QrCode {
+								url: REMOTE_AUTH_QR_CODE_URL(&fingerprint),
+							});
+						},
+						// mobile client has scanned the qr code
+						| RemoteAuthGatewayServerOpCode::PendingTicket {
+							encrypted_user_payload,
+						} => {
+							let encrypted_bytes = BASE64_STANDARD.decode(&encrypted_user_payload)?;
+							let decrypted_payload = private_key.decrypt(
+								Oaep::new::<Sha256>(),
+								&encrypted_bytes
+							)?;
+
+							let decrypted_str = str::from_utf8(&decrypted_payload)?;
+							let mut parts = decrypted_str.split(':');
+							let mut next_part = || parts.next().unwrap_or_default().to_owned();
+
+							on_event(RemoteAuthEvent::Accepted {
+								user_id: next_part(),
+								discriminator: next_part(),
+								avatar_hash: next_part(),
+								username: next_part(),
+							});
+						},
+						// mobile client has accepted the login attempt
+						// and now we need to take the ticket and get our token
+						| RemoteAuthGatewayServerOpCode::PendingLogin {
+							ticket,
+						} => {
+							// create request client without sending an authorization header
+							let http_client = RequestClient::new(BaseUrl::Discord, true);
+
+							// send ticket to ticket exchange endpoint
+							let resp: ApiResponse<RemoteAuthTicketExchangeResponse> = http_client
+								.post(
+									REMOTE_AUTH_TICKET_EXCHANGE,
+									Some(&RemoteAuthTicketExchangeRequest {
+										ticket,
+									}),
+								)
+								.await?
+								.with_auto_handle()
+								.await?;
+
+							return match resp {
+								ApiResponse::Success(v) => {
+									let encrypted_bytes = BASE64_STANDARD.decode(&v.encrypted_token)?;
+									let decrypted_payload = private_key.decrypt(
+										Oaep::new::<Sha256>(),
+										&encrypted_bytes
+									)?;
+									let token = str::from_utf8(&decrypted_payload)?.to_owned();
+
+									let _ = client.close(None).await;
+									Ok(token)
+								},
+								ApiResponse::Error(e) => Err(format!("{:?}", e).into()),
+							}
+						},
+						| RemoteAuthGatewayServerOpCode::HeartbeatAck => {
+							awaiting_ack = false;
+						},
+						| RemoteAuthGatewayServerOpCode::Cancel => {
+							on_event(RemoteAuthEvent::Cancelled);
+						},
+					}
+				}
+			}
+		}
+	}
+}
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::io::Write;
 use std::path::PathBuf;
 use std::sync::OnceLock;
@@ -12,6 +13,9 @@ pub static CONFIG_PATH: OnceLock<PathBuf> = OnceLock::new();
 #[derive(Serialize, Deserialize, Debug, Default)]
 pub struct Config {
 	pub locale: Locale,
+	/// buckets to use instead of the assigned ones, keyed by the experiment's hash
+	#[serde(default)]
+	pub experiment_overrides: HashMap<i64, i64>,
 }
 
 impl Config {
@@ -1,10 +1,13 @@
 use std::fmt::Display;
 
+use cowcord_macros::endpoint;
 use serde::{Deserialize, Serialize};
 
 use crate::types::token::Token;
 
-pub const LOGIN_ACCOUNT: &str = "/auth/login";
+endpoint! {
+	LOGIN_ACCOUNT: POST "/auth/login" body = LoginAccountRequest -> LoginAccountResponse
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LoginAccountRequest {
@@ -109,7 +112,9 @@ pub enum LoginRequiredActionType {
 	update_password,
 }
 
-pub const REMOTE_AUTH_TICKET_EXCHANGE: &str = "/users/@me/remote-auth/login";
+endpoint! {
+	REMOTE_AUTH_TICKET_EXCHANGE: POST "/users/@me/remote-auth/login" body = RemoteAuthTicketExchangeRequest -> RemoteAuthTicketExchangeResponse
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RemoteAuthTicketExchangeRequest {
@@ -137,6 +142,21 @@ pub enum AuthenticatorType {
 	password,
 }
 
+impl AuthenticatorType {
+	/// The name used in the `VERIFY_MFA_LOGIN` path
+	pub fn name(&self) -> &'static str {
+		match self {
+			| AuthenticatorType::totp => "totp",
+			| AuthenticatorType::sms => "sms",
+			| AuthenticatorType::backup => "backup",
+			| AuthenticatorType::webauthn {
+				credential_request_options: _,
+			} => "webauthn",
+			| AuthenticatorType::password => "password",
+		}
+	}
+}
+
 impl Display for AuthenticatorType {
 	fn fmt(
 		&self,
@@ -154,8 +174,8 @@ impl Display for AuthenticatorType {
 	}
 }
 
-pub fn VERIFY_MFA_LOGIN(authenticator_type: &AuthenticatorType) -> String {
-	format!("/auth/mfa/{authenticator_type:?}")
+endpoint! {
+	VERIFY_MFA_LOGIN: POST "/auth/mfa/{authenticator_type}" body = VerifyMfaLoginRequest -> VerifyMfaLoginResponse
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
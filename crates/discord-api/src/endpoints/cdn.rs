@@ -1,6 +1,37 @@
+use crate::types::snowflake::Snowflake;
+
 pub fn USER_AVATAR(
 	user_id: &str,
 	hash: &str,
 ) -> String {
 	format!("/avatars/{user_id}/{hash}.png")
 }
+
+/// `index` is `(user_id >> 22) % 6` for users on the new username system, otherwise `discriminator % 5`
+pub fn DEFAULT_USER_AVATAR(index: u64) -> String {
+	format!("/embed/avatars/{index}.png")
+}
+
+pub fn GUILD_ICON(
+	guild_id: &Snowflake,
+	hash: &str,
+) -> String {
+	let extension = if hash.starts_with("a_") { "gif" } else { "png" };
+	format!("/icons/{guild_id}/{hash}.{extension}")
+}
+
+/// The icon of a group DM
+pub fn CHANNEL_ICON(
+	channel_id: &Snowflake,
+	hash: &str,
+) -> String {
+	format!("/channel-icons/{channel_id}/{hash}.png")
+}
+
+pub fn CUSTOM_EMOJI(
+	emoji_id: &Snowflake,
+	animated: bool,
+) -> String {
+	let extension = if animated { "gif" } else { "png" };
+	format!("/emojis/{emoji_id}.{extension}")
+}
@@ -0,0 +1,19 @@
+use cowcord_macros::endpoint;
+use serde::{Deserialize, Serialize};
+
+endpoint! {
+	/// Shows the current user as typing in the channel for 10 seconds, or until they send a message
+	///
+	/// Returns `204 No Content`, or a [`TriggerTypingResponse`] if the channel has slowmode
+	TRIGGER_TYPING: POST "/channels/{channel_id}/typing" -> TriggerTypingResponse
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TriggerTypingResponse {
+	/// How long until the user can send a message (in milliseconds)
+	#[serde(default)]
+	pub message_send_cooldown_ms: Option<u64>,
+	/// How long until the user can create a thread (in milliseconds)
+	#[serde(default)]
+	pub thread_create_cooldown_ms: Option<u64>,
+}
@@ -1,10 +1,11 @@
+use cowcord_macros::{DeserializeTuple, SerializeTuple, endpoint};
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
 
-use crate::serialize_query_params;
+use crate::types::snowflake::Snowflake;
 
-pub fn GET_EXPERIMENT_ASSIGNMENTS(query_params: &GetExperimentAssignmentsQuery) -> String {
-	format!("/experiments{}", serialize_query_params(query_params))
+endpoint! {
+	GET_EXPERIMENT_ASSIGNMENTS: GET "/experiments" query = GetExperimentAssignmentsQuery -> GetExperimentAssignmentsResponse
 }
 
 #[derive(Serialize, Deserialize)]
@@ -31,17 +32,14 @@ pub struct GetExperimentAssignmentsResponse {
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub fingerprint: Option<String>,
 	/// The experiment assignments for this user or fingerprint
-	// pub assignments: Vec<UserExperiment>,
-	pub assignments: Vec<Value>,
+	pub assignments: Vec<UserExperiment>,
 	/// Guild experiment rollouts for the client to assign
 	#[serde(skip_serializing_if = "Option::is_none")]
-	// pub guild_experiments: Option<Vec<GuildExperiment>>,
-	pub guild_experiments: Option<Value>,
+	pub guild_experiments: Option<Vec<GuildExperiment>>,
 }
 
-// todo: serialize/deserialize as an array
 /// This object is represented as an array of the following fields:
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, SerializeTuple, DeserializeTuple)]
 pub struct UserExperiment {
 	/// 32-bit unsigned Murmur3 hash of the experiment's name
 	pub hash: i64,
@@ -60,15 +58,18 @@ pub struct UserExperiment {
 	/// Whether the experiment's analytics trigger debugging is enabled, represented as an integer-casted boolean
 	pub trigger_debugging: i64,
 	/// A human-readable experiment name (formatted as year-month_name) that disables the experiment
+	#[tuple(default)]
 	pub holdout_name: Option<String>,
 	/// The revision of the holdout experiment
+	#[tuple(default)]
 	pub holdout_revision: Option<i64>,
 	/// The requesting user or fingerprint's assigned bucket for the holdout experiment
+	#[tuple(default)]
 	pub holdout_bucket: Option<i64>,
 }
 
 /// This object is represented as an array of the following fields:
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, SerializeTuple, DeserializeTuple)]
 pub struct GuildExperiment {
 	/// 32-bit unsigned Murmur3 hash of the experiment's name
 	pub hash: i64,
@@ -93,7 +94,7 @@ pub struct GuildExperiment {
 }
 
 /// This object is represented as an array of the following fields:
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, SerializeTuple, DeserializeTuple)]
 pub struct ExperimentPopulation {
 	/// The ranges for this population
 	pub ranges: Vec<ExperimentPopulationRange>,
@@ -102,7 +103,7 @@ pub struct ExperimentPopulation {
 }
 
 /// This object is represented as an array of the following fields:
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, SerializeTuple, DeserializeTuple)]
 pub struct ExperimentPopulationRange {
 	/// The bucket this range grants
 	pub bucket: i64,
@@ -110,7 +111,7 @@ pub struct ExperimentPopulationRange {
 	pub rollout: Vec<ExperimentPopulationRollout>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExperimentPopulationRollout {
 	/// The start of this range
 	pub s: i64,
@@ -118,66 +119,57 @@ pub struct ExperimentPopulationRollout {
 	pub e: i64,
 }
 
-#[derive(Serialize, Deserialize)]
+/// Sent as a list of filters, each one with a list of its fields, where filters and fields are the murmur3 hashes of their names
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(try_from = "RawFilters", into = "RawFilters")]
 pub struct ExperimentPopulationFilters {
 	/// The guild features that are eligible
-	#[serde(skip_serializing_if = "Option::is_none")]
 	pub guild_has_feature: Option<ExperimentPopulationGuildFeatureFilter>,
 	/// The range of snowflake resource IDs that are eligible
-	#[serde(skip_serializing_if = "Option::is_none")]
 	pub guild_id_range: Option<ExperimentPopulationRangeFilter>,
 	/// The range of guild ages (in days) that are eligible
-	#[serde(skip_serializing_if = "Option::is_none")]
 	pub guild_age_range_days: Option<ExperimentPopulationRangeFilter>,
 	/// The range of guild member counts that are eligible
-	#[serde(skip_serializing_if = "Option::is_none")]
 	pub guild_member_count_range: Option<ExperimentPopulationRangeFilter>,
 	/// A list of resource IDs that are eligible
-	#[serde(skip_serializing_if = "Option::is_none")]
 	pub guild_ids: Option<ExperimentPopulationIdFilter>,
 	/// A list of hub types that are eligible
-	#[serde(skip_serializing_if = "Option::is_none")]
 	pub guild_hub_types: Option<ExperimentPopulationHubTypeFilter>,
 	/// Whether the guild must or must not have a vanity to be eligible
-	#[serde(skip_serializing_if = "Option::is_none")]
 	pub guild_has_vanity_url: Option<ExperimentPopulationVanityUrlFilter>,
 	/// The special rollout position limits on the population
-	#[serde(skip_serializing_if = "Option::is_none")]
 	pub guild_in_range_by_hash: Option<ExperimentPopulationRangeByHashFilter>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExperimentPopulationGuildFeatureFilter {
 	/// The guild features eligible for this population; only one feature is required for eligibility
 	pub guild_features: Vec<String>,
 	// pub guild_features: Vec<GuildFeatures>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExperimentPopulationRangeFilter {
-	/// The exclusive minimum for this range, if any
-	pub min_id: Option<String>,
-	// pub min_id: Option<Snowflake>,
-	/// The exclusive maximum for this range, if any
-	pub max_id: Option<String>,
-	// pub max_id: Option<Snowflake>,
+	/// The inclusive minimum for this range, if any
+	pub min_id: Option<u64>,
+	/// The inclusive maximum for this range, if any
+	pub max_id: Option<u64>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExperimentPopulationIdFilter {
 	/// The list of snowflake resource IDs that are eligible for this population
-	pub guild_ids: Vec<String>,
-	// pub guild_ids: Vec<Snowflake>,
+	pub guild_ids: Vec<Snowflake>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExperimentPopulationHubTypeFilter {
 	/// The type of hubs that are eligible for this population
 	// pub guild_hub_types: Vec<HubType>,
 	pub guild_hub_types: Vec<i64>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExperimentPopulationVanityUrlFilter {
 	/// The required vanity URL holding status for this population
 	pub guild_has_vanity_url: bool,
@@ -185,7 +177,7 @@ pub struct ExperimentPopulationVanityUrlFilter {
 
 /// This filter is used to limit rollout position by an additional hash key.
 /// The calculated rollout position must be less than the given target.
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExperimentPopulationRangeByHashFilter {
 	/// The 32-bit unsigned Murmur3 hash of the key used to determine eligibility
 	pub hash_key: i64,
@@ -193,11 +185,139 @@ pub struct ExperimentPopulationRangeByHashFilter {
 	pub target: i64,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExperimentBucketOverride {
 	/// Bucket assigned to these resources
 	pub b: i64,
 	/// Resources granted access to this bucket
-	// pub k: Vec<Snowflake>,
-	pub k: Vec<String>,
+	pub k: Vec<Snowflake>,
+}
+
+type RawFilters = Vec<(u32, Vec<(u32, Value)>)>;
+
+/// The murmur3 hashes of the filters and their fields
+const FILTER_HASHES: [(u32, &str); 13] = [
+	(1604612045, "guild_has_feature"),
+	(1183251248, "guild_features"),
+	(2404720969, "guild_id_range"),
+	(3730341874, "guild_age_range_days"),
+	(2918402255, "guild_member_count_range"),
+	(3399957344, "min_id"),
+	(1238858341, "max_id"),
+	(3013771838, "guild_ids"),
+	(4148745523, "guild_hub_types"),
+	(188952590, "guild_has_vanity_url"),
+	(2294888943, "guild_in_range_by_hash"),
+	(2690752156, "hash_key"),
+	(1982804121, "target"),
+];
+
+fn filter_name(hash: u32) -> Option<&'static str> {
+	FILTER_HASHES
+		.iter()
+		.find(|(h, _)| *h == hash)
+		.map(|(_, name)| *name)
+}
+
+fn filter_hash(name: &str) -> Option<u32> {
+	FILTER_HASHES
+		.iter()
+		.find(|(_, n)| *n == name)
+		.map(|(hash, _)| *hash)
+}
+
+impl TryFrom<RawFilters> for ExperimentPopulationFilters {
+	type Error = serde_json::Error;
+
+	/// Unknown filters and fields are ignored
+	fn try_from(raw: RawFilters) -> Result<Self, Self::Error> {
+		let mut filters = ExperimentPopulationFilters::default();
+
+		for (hash, fields) in raw {
+			let fields: Map<String, Value> = fields
+				.into_iter()
+				.filter_map(|(hash, value)| Some((filter_name(hash)?.to_owned(), value)))
+				.collect();
+			let fields = Value::Object(fields);
+
+			match filter_name(hash) {
+				| Some("guild_has_feature") => {
+					filters.guild_has_feature = Some(serde_json::from_value(fields)?)
+				},
+				| Some("guild_id_range") => {
+					filters.guild_id_range = Some(serde_json::from_value(fields)?)
+				},
+				| Some("guild_age_range_days") => {
+					filters.guild_age_range_days = Some(serde_json::from_value(fields)?)
+				},
+				| Some("guild_member_count_range") => {
+					filters.guild_member_count_range = Some(serde_json::from_value(fields)?)
+				},
+				| Some("guild_ids") => filters.guild_ids = Some(serde_json::from_value(fields)?),
+				| Some("guild_hub_types") => {
+					filters.guild_hub_types = Some(serde_json::from_value(fields)?)
+				},
+				| Some("guild_has_vanity_url") => {
+					filters.guild_has_vanity_url = Some(serde_json::from_value(fields)?)
+				},
+				| Some("guild_in_range_by_hash") => {
+					filters.guild_in_range_by_hash = Some(serde_json::from_value(fields)?)
+				},
+				| _ => {},
+			}
+		}
+
+		Ok(filters)
+	}
+}
+
+impl From<ExperimentPopulationFilters> for RawFilters {
+	fn from(filters: ExperimentPopulationFilters) -> Self {
+		let named = [
+			(
+				"guild_has_feature",
+				filters.guild_has_feature.map(serde_json::to_value),
+			),
+			(
+				"guild_id_range",
+				filters.guild_id_range.map(serde_json::to_value),
+			),
+			(
+				"guild_age_range_days",
+				filters.guild_age_range_days.map(serde_json::to_value),
+			),
+			(
+				"guild_member_count_range",
+				filters.guild_member_count_range.map(serde_json::to_value),
+			),
+			("guild_ids", filters.guild_ids.map(serde_json::to_value)),
+			(
+				"guild_hub_types",
+				filters.guild_hub_types.map(serde_json::to_value),
+			),
+			(
+				"guild_has_vanity_url",
+				filters.guild_has_vanity_url.map(serde_json::to_value),
+			),
+			(
+				"guild_in_range_by_hash",
+				filters.guild_in_range_by_hash.map(serde_json::to_value),
+			),
+		];
+
+		named
+			.into_iter()
+			.filter_map(|(name, filter)| {
+				let Some(Ok(Value::Object(fields))) = filter else {
+					return None;
+				};
+				let fields = fields
+					.into_iter()
+					.filter_map(|(name, value)| Some((filter_hash(&name)?, value)))
+					.collect();
+
+				Some((filter_hash(name)?, fields))
+			})
+			.collect()
+	}
 }
@@ -0,0 +1,10 @@
+use cowcord_macros::endpoint;
+
+use crate::types::channel::Channel;
+
+endpoint! {
+	/// Returns a list of guild channel objects for the guild
+	///
+	/// Doesn't include threads
+	GET_GUILD_CHANNELS: GET "/guilds/{guild_id}/channels" -> Vec<Channel>
+}
@@ -0,0 +1,209 @@
+use cowcord_macros::endpoint;
+use serde::{Deserialize, Serialize};
+
+use crate::types::message::{Message, MessageFlags, MessageReference, Nonce, ReactionType};
+use crate::types::snowflake::Snowflake;
+use crate::types::user::User;
+
+endpoint! {
+	/// Returns a list of message objects in the channel
+	///
+	/// `around`, `before` and `after` are mutually exclusive
+	GET_MESSAGES: GET "/channels/{channel_id}/messages" query = GetMessagesQuery -> Vec<Message>
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GetMessagesQuery {
+	/// Get messages around this message ID
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub around: Option<Snowflake>,
+	/// Get messages before this message ID
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub before: Option<Snowflake>,
+	/// Get messages after this message ID
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub after: Option<Snowflake>,
+	/// Max number of messages to return (1-100, default 50)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub limit: Option<u8>,
+}
+
+endpoint! {
+	/// Posts a message to a guild text, DM or thread channel, returns the message object
+	CREATE_MESSAGE: POST "/channels/{channel_id}/messages" body = CreateMessageRequest -> Message
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateMessageRequest {
+	/// The message contents (up to 2000 characters)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub content: Option<String>,
+	/// Validates a message or interaction was sent, returned in the message create event
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub nonce: Option<Nonce>,
+	/// Whether the nonce should be enforced (if a message with the same nonce was sent in the last few minutes, that message is returned instead)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub enforce_nonce: Option<bool>,
+	/// Whether this is a TTS message
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub tts: Option<bool>,
+	/// Include to make your message a reply or a forward
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub message_reference: Option<MessageReference>,
+	/// The message's flags (only SUPPRESS_EMBEDS, SUPPRESS_NOTIFICATIONS, IS_VOICE_MESSAGE, and IS_COMPONENTS_V2 can be set)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub flags: Option<MessageFlags>,
+	/// Which mentions in the content actually ping, everything pings if not set
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub allowed_mentions: Option<AllowedMentions>,
+	/// The attachments to include, matched to the uploaded `files[n]` by id
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub attachments: Option<Vec<PartialAttachment>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AllowedMentions {
+	/// The types of mentions to parse from the content (`users`, `roles` and `everyone`)
+	#[serde(default)]
+	pub parse: Vec<String>,
+	/// Whether to mention the author of the replied message
+	#[serde(default)]
+	pub replied_user: bool,
+}
+
+/// An attachment being sent with a message
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PartialAttachment {
+	/// The index of the file in the multipart request, or the ID of an existing attachment when editing
+	pub id: String,
+	/// The name of the file
+	pub filename: String,
+	/// The alt text of the file (max 1024 characters)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub description: Option<String>,
+	/// The `upload_filename` of a file uploaded to an upload URL, instead of sending it in the request
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub uploaded_filename: Option<String>,
+}
+
+endpoint! {
+	/// Creates upload URLs for attachments, the files are then uploaded to them with a `PUT` and referenced in the message by their `upload_filename`
+	CREATE_ATTACHMENTS: POST "/channels/{channel_id}/attachments" body = CreateAttachmentsRequest -> CreateAttachmentsResponse
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateAttachmentsRequest {
+	/// The files to upload (max 10)
+	pub files: Vec<AttachmentSlotRequest>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AttachmentSlotRequest {
+	/// An ID for the file, sent back with its upload URL
+	pub id: String,
+	/// The name of the file
+	pub filename: String,
+	/// The size of the file in bytes
+	pub file_size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateAttachmentsResponse {
+	/// The upload URLs, in the same order as the requested files
+	pub attachments: Vec<CloudAttachment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloudAttachment {
+	/// The URL to `PUT` the file's bytes to
+	pub upload_url: String,
+	/// The name to reference the uploaded file by when sending the message
+	pub upload_filename: String,
+}
+
+endpoint! {
+	/// Deletes a file uploaded to an upload URL that wasn't sent in a message
+	DELETE_ATTACHMENT_UPLOAD: DELETE "/attachments/{upload_filename}"
+}
+
+endpoint! {
+	/// Edits a message sent by the current user, returns the updated message object
+	EDIT_MESSAGE: PATCH "/channels/{channel_id}/messages/{message_id}" body = EditMessageRequest -> Message
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EditMessageRequest {
+	/// The new message contents (up to 2000 characters)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub content: Option<String>,
+	/// The message's flags (only SUPPRESS_EMBEDS can be changed)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub flags: Option<MessageFlags>,
+	/// The attachments to keep
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub attachments: Option<Vec<PartialAttachment>>,
+}
+
+endpoint! {
+	/// Adds a reaction to a message, `emoji` is a unicode emoji or `name:id` for custom emojis
+	///
+	/// Burst reactions need nitro, and use up one of the user's super reactions
+	CREATE_REACTION: PUT "/channels/{channel_id}/messages/{message_id}/reactions/{emoji}/@me" query = CreateReactionQuery
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateReactionQuery {
+	/// Where the reaction was added from, for analytics (e.g. `Message`, `Picker`)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub location: Option<String>,
+	/// The type of reaction to add
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub r#type: Option<ReactionType>,
+}
+
+endpoint! {
+	/// Removes a reaction the current user added to a message
+	DELETE_OWN_REACTION: DELETE "/channels/{channel_id}/messages/{message_id}/reactions/{emoji}/{reaction_type}/@me"
+}
+
+endpoint! {
+	/// Returns a list of user objects that reacted to the message with the emoji
+	GET_REACTIONS: GET "/channels/{channel_id}/messages/{message_id}/reactions/{emoji}" query = GetReactionsQuery -> Vec<User>
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GetReactionsQuery {
+	/// The type of reaction to get users for
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub r#type: Option<ReactionType>,
+	/// Get users after this user ID
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub after: Option<Snowflake>,
+	/// Max number of users to return (1-100, default 25)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub limit: Option<u8>,
+}
+
+endpoint! {
+	/// Marks the message and everything before it in the channel as read, returns an [`AckMessageResponse`]
+	ACK_MESSAGE: POST "/channels/{channel_id}/messages/{message_id}/ack" body = AckMessageRequest -> AckMessageResponse
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AckMessageRequest {
+	/// The token returned by the last ack, `None` for the first one
+	pub token: Option<String>,
+	/// Whether the message was marked as unread or read by the user, instead of by viewing the channel
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub manual: Option<bool>,
+	/// The mentions left after the acked message, only used with `manual`
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub mention_count: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AckMessageResponse {
+	/// Passed to the next ack
+	#[serde(default)]
+	pub token: Option<String>,
+}
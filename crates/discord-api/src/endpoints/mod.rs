@@ -1,3 +1,31 @@
 pub mod auth;
 pub mod cdn;
+pub mod channels;
 pub mod experiments;
+pub mod guilds;
+pub mod messages;
+pub mod read_states;
+pub mod users;
+
+/// The HTTP method an endpoint is requested with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+	GET,
+	POST,
+	PUT,
+	PATCH,
+	DELETE,
+}
+
+/// What an endpoint sends and gets back, implemented by [`endpoint!`](cowcord_macros::endpoint) for a type with the endpoint's name
+pub trait Endpoint {
+	const METHOD: Method;
+	/// The path with `{param}` placeholders where the parameters go
+	const PATH: &'static str;
+	/// The query string parameters, `()` if it doesn't take any
+	type Query;
+	/// The json request body, `()` if it doesn't take one
+	type Request;
+	/// The json response body, `()` if it returns `204 No Content`
+	type Response;
+}
@@ -0,0 +1,28 @@
+use cowcord_macros::endpoint;
+use serde::{Deserialize, Serialize};
+
+use crate::types::snowflake::Snowflake;
+
+endpoint! {
+	/// Marks several channels as read at once, up to 100 at a time
+	///
+	/// Returns `204 No Content`
+	ACK_BULK: POST "/read-states/ack-bulk" body = AckBulkRequest
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AckBulkRequest {
+	/// The read states to update
+	pub read_states: Vec<BulkReadState>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkReadState {
+	/// The ID of the channel
+	pub channel_id: Snowflake,
+	/// The ID of the last read message
+	pub message_id: Snowflake,
+	/// The type of read state, `0` for channels
+	#[serde(default)]
+	pub read_state_type: u8,
+}
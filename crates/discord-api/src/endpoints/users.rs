@@ -0,0 +1,32 @@
+use cowcord_macros::endpoint;
+use serde::{Deserialize, Serialize};
+
+use crate::types::guild::UserGuild;
+use crate::types::snowflake::Snowflake;
+use crate::types::user::User;
+
+endpoint! {
+	/// Returns the user object of the requesting account
+	GET_CURRENT_USER: GET "/users/@me" -> User
+}
+
+endpoint! {
+	/// Returns a list of user guild objects representing the guilds the current user is a member of
+	GET_CURRENT_USER_GUILDS: GET "/users/@me/guilds" query = GetCurrentUserGuildsQuery -> Vec<UserGuild>
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GetCurrentUserGuildsQuery {
+	/// Get guilds before this guild ID
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub before: Option<Snowflake>,
+	/// Get guilds after this guild ID
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub after: Option<Snowflake>,
+	/// Max number of guilds to return (1-200, default 200)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub limit: Option<u8>,
+	/// Whether to include approximate member and presence counts (default false)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub with_counts: Option<bool>,
+}
@@ -4,6 +4,8 @@
 //!
 //! Each endpoint will have a const unless it requires params then it will be a function that returns a `String`
 //!
+//! Endpoints are defined with [`endpoint!`](cowcord_macros::endpoint), which also makes a type with the endpoint's name that implements [`Endpoint`](endpoints::Endpoint) with its method and body types
+//!
 //! If the endpoint takes request body data, a typed struct with correct se/deserialization will be included
 //!
 //! If the endpoint returns response body data, a typed struct with correct se/deserialization will be included
@@ -21,10 +23,12 @@ use serde::{Deserialize, Serialize};
 use crate::types::error::Error;
 
 pub mod endpoints;
+mod tests;
 pub mod types;
 
 pub const DISCORD_URL: &str = "https://discord.com";
 pub const CDN_URL: &str = "https://cdn.discordapp.com";
+pub const GATEWAY_URL: &str = "wss://gateway.discord.gg/?v=9&encoding=json";
 pub const REMOTE_AUTH_WS_URL: &str = "wss://remote-auth-gateway.discord.gg/?v=2"; // 2 is currently the only valid version
 
 #[derive(Debug, Clone)]
@@ -47,8 +51,41 @@ pub enum ApiResponse<T> {
 	Error(Error),
 }
 
+/// The query string with its `?`, empty when there are no params
 pub(crate) fn serialize_query_params(s: impl Serialize) -> String {
 	serde_urlencoded::to_string(s)
+		.ok()
+		.filter(|ser| !ser.is_empty())
 		.map(|ser| format!("?{ser}"))
 		.unwrap_or_default()
 }
+
+/// Days since the unix epoch for a date in the gregorian calendar
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+pub fn days_from_civil(
+	year: i64,
+	month: i64,
+	day: i64,
+) -> i64 {
+	let year = if month <= 2 { year - 1 } else { year };
+	let era = year.div_euclid(400);
+	let year_of_era = year - era * 400;
+	let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+	let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+	era * 146097 + day_of_era - 719468
+}
+
+/// Percent encodes everything but unreserved characters and `:`, so the string stays a single path segment
+///
+/// `:` is kept for custom emojis in reaction paths, which are sent as `name:id`
+pub(crate) fn encode_path_segment(s: &str) -> String {
+	s.bytes()
+		.map(|b| match b {
+			| b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b':' => {
+				(b as char).to_string()
+			},
+			| _ => format!("%{b:02X}"),
+		})
+		.collect()
+}
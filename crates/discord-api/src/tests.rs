@@ -0,0 +1,687 @@
+#![cfg(test)]
+
+mod rollout {
+	use serde_json::json;
+
+	use crate::endpoints::experiments::{GuildExperiment, UserExperiment};
+	use crate::types::rollout::*;
+	use crate::types::snowflake::Snowflake;
+
+	const NAME: &str = "2021-06_guild_role_subscriptions";
+	/// rollout position 3745 in [`NAME`], 251 for the hash key `1234`
+	const GUILD_A: Snowflake = Snowflake(613425648685547541);
+	/// rollout position 380 in [`NAME`], 5120 for the hash key `1234`
+	const GUILD_B: Snowflake = Snowflake(1);
+
+	fn guild(id: Snowflake) -> RolloutGuild<'static> {
+		RolloutGuild {
+			id,
+			features: &[],
+			member_count: None,
+			hub_type: None,
+			has_vanity_url: false,
+		}
+	}
+
+	/// An experiment with bucket 1 for positions `0..1000` and bucket 2 for the rest, behind the filters
+	fn experiment(filters: serde_json::Value) -> GuildExperiment {
+		serde_json::from_value(json!([
+			experiment_hash(NAME),
+			null,
+			3,
+			[[
+				[[1, [{"s": 0, "e": 1000}]], [2, [{"s": 1000, "e": 10000}]]],
+				filters,
+			]],
+			[],
+			[],
+			null,
+			null,
+			0,
+			0,
+		]))
+		.unwrap()
+	}
+
+	#[test]
+	fn murmur3_vectors() {
+		assert_eq!(murmur3(b""), 0);
+		assert_eq!(murmur3(b"a"), 1009084850);
+		assert_eq!(murmur3(b"ab"), 2613040991);
+		assert_eq!(murmur3(b"abc"), 3017643002);
+		assert_eq!(murmur3(b"abcd"), 1139631978);
+		assert_eq!(murmur3(b"hello"), 613153351);
+		assert_eq!(
+			murmur3(b"The quick brown fox jumps over the lazy dog"),
+			776992547
+		);
+
+		// the hashes discord sends population filters as
+		assert_eq!(murmur3(b"guild_has_feature"), 1604612045);
+		assert_eq!(murmur3(b"guild_in_range_by_hash"), 2294888943);
+		assert_eq!(experiment_hash(NAME), 1405831955);
+	}
+
+	#[test]
+	fn positions() {
+		assert_eq!(rollout_position(NAME, GUILD_A), 3745);
+		assert_eq!(rollout_position(NAME, GUILD_B), 380);
+		assert_eq!(rollout_position(NAME, Snowflake(81384788765712384)), 258);
+		assert_eq!(rollout_position("1234", GUILD_A), 251);
+		assert_eq!(rollout_position("1234", GUILD_B), 5120);
+	}
+
+	#[test]
+	fn populations() {
+		let experiments = [experiment(json!([]))];
+
+		let a = guild_assignment(&experiments, NAME, &guild(GUILD_A), 0).unwrap();
+		assert_eq!(a.bucket, 2);
+		assert_eq!(a.position, 3745);
+		assert_eq!(a.revision, 3);
+
+		let b = guild_assignment(&experiments, NAME, &guild(GUILD_B), 0).unwrap();
+		assert_eq!(b.bucket, 1);
+
+		assert_eq!(
+			guild_assignment(&experiments, "2021-06_other", &guild(GUILD_A), 0),
+			None
+		);
+
+		// the hash key is used for the position instead of the name
+		let mut keyed = experiment(json!([]));
+		keyed.hash_key = Some("1234".to_owned());
+		let a = keyed.assign(NAME, &[], &guild(GUILD_A), 0).unwrap();
+		assert_eq!((a.position, a.bucket), (251, 1));
+	}
+
+	#[test]
+	fn filters() {
+		let features = ["COMMUNITY".to_owned()];
+		let now = GUILD_A.timestamp() + 10 * 24 * 60 * 60 * 1000;
+		let full = RolloutGuild {
+			id: GUILD_A,
+			features: &features,
+			member_count: Some(500),
+			hub_type: Some(1),
+			has_vanity_url: true,
+		};
+		let bucket = |filters, guild: &RolloutGuild| {
+			experiment(filters)
+				.assign(NAME, &[], guild, now)
+				.map(|a| a.bucket)
+		};
+
+		let cases = [
+			// guild_has_feature
+			json!([[1604612045u32, [[1183251248u32, ["COMMUNITY", "VERIFIED"]]]]]),
+			// guild_id_range
+			json!([[2404720969u32, [[3399957344u32, GUILD_A.0], [
+				1238858341u32,
+				null
+			]]]]),
+			// guild_age_range_days
+			json!([[3730341874u32, [[3399957344u32, 10], [1238858341u32, 30]]]]),
+			// guild_member_count_range
+			json!([[2918402255u32, [[3399957344u32, 100], [1238858341u32, 500]]]]),
+			// guild_ids
+			json!([[3013771838u32, [[3013771838u32, [GUILD_A.to_string()]]]]]),
+			// guild_hub_types
+			json!([[4148745523u32, [[4148745523u32, [0, 1]]]]]),
+			// guild_has_vanity_url
+			json!([[188952590u32, [[188952590u32, true]]]]),
+			// guild_in_range_by_hash
+			json!([[2294888943u32, [[2690752156u32, 1234], [1982804121u32, 300]]]]),
+		];
+
+		for filters in cases {
+			assert_eq!(bucket(filters.clone(), &full), Some(2), "{filters}");
+			assert_eq!(bucket(filters.clone(), &guild(GUILD_B)), None, "{filters}");
+		}
+
+		// every filter has to pass
+		let both = json!([[1604612045u32, [[1183251248u32, ["COMMUNITY"]]]], [
+			188952590u32,
+			[[188952590u32, false]]
+		],]);
+		assert_eq!(bucket(both, &full), None);
+
+		// unknown filters are ignored
+		assert_eq!(bucket(json!([[1, [[2, 3]]]]), &guild(GUILD_B)), Some(1));
+	}
+
+	#[test]
+	fn overrides() {
+		let mut experiment = experiment(json!([]));
+		experiment.overrides =
+			serde_json::from_value(json!([{"b": 5, "k": [GUILD_A.to_string()]}])).unwrap();
+		// guilds in the first range get bucket 7 from the override population
+		experiment.overrides_formatted = serde_json::from_value(json!([[[
+			[[7, [{"s": 0, "e": 1000}]]],
+			[],
+		]]]))
+		.unwrap();
+		let experiments = [experiment];
+
+		let a = guild_assignment(&experiments, NAME, &guild(GUILD_A), 0).unwrap();
+		assert_eq!(a.bucket, 5);
+		let b = guild_assignment(&experiments, NAME, &guild(GUILD_B), 0).unwrap();
+		assert_eq!(b.bucket, 7);
+		let c = guild_assignment(&experiments, NAME, &guild(Snowflake(81384788765712384)), 0);
+		assert_eq!(c.map(|c| c.bucket), Some(7));
+	}
+
+	#[test]
+	fn holdouts() {
+		let holdout_name = "2021-06_holdout";
+		let mut holdout = experiment(json!([]));
+		holdout.hash = experiment_hash(holdout_name);
+		holdout.hash_key = Some(NAME.to_owned());
+
+		let mut held = experiment(json!([]));
+		held.holdout_name = Some(holdout_name.to_owned());
+		held.holdout_bucket = Some(1);
+		let experiments = [held, holdout];
+
+		// GUILD_B is in bucket 1 of the holdout, so it's left out
+		assert_eq!(
+			guild_assignment(&experiments, NAME, &guild(GUILD_B), 0),
+			None
+		);
+		let a = guild_assignment(&experiments, NAME, &guild(GUILD_A), 0).unwrap();
+		assert_eq!(a.bucket, 2);
+
+		// experiments holding each other out, or themselves, are treated as not held out
+		let [mut held, mut holdout] = experiments;
+		holdout.holdout_name = Some(NAME.to_owned());
+		holdout.holdout_bucket = Some(1);
+		let experiments = [held.clone(), holdout];
+		let b = guild_assignment(&experiments, NAME, &guild(GUILD_B), 0).unwrap();
+		assert_eq!(b.bucket, 1);
+
+		held.holdout_name = Some(NAME.to_owned());
+		let b = held
+			.assign(NAME, &[held.clone()], &guild(GUILD_B), 0)
+			.unwrap();
+		assert_eq!(b.bucket, 1);
+	}
+
+	#[test]
+	fn aa_mode() {
+		let mut experiment = experiment(json!([]));
+		experiment.aa_mode = 1;
+
+		let a = experiment.assign(NAME, &[], &guild(GUILD_A), 0).unwrap();
+		assert_eq!(a.bucket, 2);
+		assert_eq!(a.effective_bucket(), 0);
+
+		let user: Vec<UserExperiment> = serde_json::from_value(json!([
+			[experiment_hash(NAME), 2, 1, -1, 0, 1234, 1, 0],
+			[experiment_hash("2021-06_other"), 2, 1, -1, 0, 1234, 0, 0],
+		]))
+		.unwrap();
+		assert_eq!(user_assignment(&user, NAME).unwrap().effective_bucket(), 0);
+		assert_eq!(
+			user_assignment(&user, "2021-06_other")
+				.unwrap()
+				.effective_bucket(),
+			1
+		);
+		assert!(user_assignment(&user, "2021-06_missing").is_none());
+	}
+}
+
+mod flags {
+	use serde_json::json;
+
+	use crate::types::message::MessageFlags;
+	use crate::types::permissions::Permissions;
+	use crate::types::user::UserFlags;
+
+	#[test]
+	fn serde() {
+		let flags: MessageFlags = serde_json::from_value(json!(4100)).unwrap();
+		assert_eq!(
+			flags,
+			MessageFlags::SUPPRESS_EMBEDS | MessageFlags::SUPPRESS_NOTIFICATIONS
+		);
+		assert_eq!(serde_json::to_value(flags).unwrap(), json!(4100));
+
+		// flags sent as strings can be read either way, and go back the way they came
+		let from_str: MessageFlags = serde_json::from_value(json!("4100")).unwrap();
+		assert_eq!(from_str, flags);
+
+		let permissions: Permissions = serde_json::from_value(json!(8)).unwrap();
+		assert_eq!(permissions, Permissions::ADMINISTRATOR);
+		assert_eq!(serde_json::to_value(permissions).unwrap(), json!("8"));
+
+		assert!(serde_json::from_value::<MessageFlags>(json!(-1)).is_err());
+		assert!(serde_json::from_value::<MessageFlags>(json!("staff")).is_err());
+	}
+
+	#[test]
+	fn unknown_bits() {
+		let flags: UserFlags = serde_json::from_value(json!((1u64 << 60) | 1)).unwrap();
+		assert!(flags.contains(UserFlags::STAFF));
+		assert_eq!(flags.unknown_bits(), 1 << 60);
+		assert_eq!(flags.truncate(), UserFlags::STAFF);
+		// unknown bits are sent back as they came
+		assert_eq!(
+			serde_json::to_value(flags).unwrap(),
+			json!((1u64 << 60) | 1)
+		);
+	}
+
+	#[test]
+	fn display() {
+		assert_eq!(UserFlags::empty().to_string(), "");
+		assert_eq!(
+			(UserFlags::STAFF | UserFlags::ACTIVE_DEVELOPER).to_string(),
+			"STAFF | ACTIVE_DEVELOPER"
+		);
+		assert_eq!(UserFlags(1 << 60).to_string(), "0x1000000000000000");
+		assert_eq!(
+			format!("{:?}", UserFlags::PARTNER | UserFlags(1 << 60)),
+			"UserFlags(PARTNER | 0x1000000000000000)"
+		);
+	}
+
+	#[test]
+	fn set() {
+		let mut flags = MessageFlags::empty();
+		flags.set(MessageFlags::SUPPRESS_EMBEDS, true);
+		flags.insert(MessageFlags::EPHEMERAL);
+		assert!(flags.intersects(MessageFlags::EPHEMERAL | MessageFlags::URGENT));
+		assert!(!flags.contains(MessageFlags::EPHEMERAL | MessageFlags::URGENT));
+
+		flags.set(MessageFlags::SUPPRESS_EMBEDS, false);
+		flags.remove(MessageFlags::EPHEMERAL);
+		assert!(flags.is_empty());
+	}
+}
+
+mod permissions {
+	use serde_json::json;
+
+	use crate::types::channel::{Channel, ChannelType};
+	use crate::types::guild::{Guild, GuildMember};
+	use crate::types::permissions::*;
+	use crate::types::snowflake::Snowflake;
+
+	const GUILD: Snowflake = Snowflake(1);
+	const USER: Snowflake = Snowflake(2);
+	const OWNER: Snowflake = Snowflake(3);
+	/// gives [`Permissions::MANAGE_MESSAGES`]
+	const MODERATOR: Snowflake = Snowflake(10);
+	/// gives [`Permissions::ATTACH_FILES`]
+	const MEMBER: Snowflake = Snowflake(11);
+	/// gives [`Permissions::ADMINISTRATOR`]
+	const ADMIN: Snowflake = Snowflake(12);
+	/// 2023-11-14, between the timeouts below
+	const NOW: u64 = 1_700_000_000_000;
+
+	fn everyone() -> Permissions {
+		Permissions::VIEW_CHANNEL
+			| Permissions::READ_MESSAGE_HISTORY
+			| Permissions::SEND_MESSAGES
+			| Permissions::EMBED_LINKS
+			| Permissions::CONNECT
+			| Permissions::SPEAK
+	}
+
+	fn guild() -> Guild {
+		let role = |id: Snowflake, permissions: Permissions| {
+			json!({
+				"id": id,
+				"name": "role",
+				"color": 0,
+				"position": 1,
+				"permissions": permissions,
+			})
+		};
+		serde_json::from_value(json!({
+			"id": GUILD,
+			"owner_id": OWNER,
+			"roles": [
+				role(GUILD, everyone()),
+				role(MODERATOR, Permissions::MANAGE_MESSAGES),
+				role(MEMBER, Permissions::ATTACH_FILES),
+				role(ADMIN, Permissions::ADMINISTRATOR),
+			],
+		}))
+		.unwrap()
+	}
+
+	fn member(
+		roles: &[Snowflake],
+		timed_out_until: Option<&str>,
+	) -> GuildMember {
+		serde_json::from_value(json!({
+			"roles": roles,
+			"communication_disabled_until": timed_out_until,
+		}))
+		.unwrap()
+	}
+
+	/// `(id, is_member, allow, deny)`
+	type Overwrite = (Snowflake, bool, Permissions, Permissions);
+
+	fn channel(
+		r#type: ChannelType,
+		overwrites: &[Overwrite],
+	) -> Channel {
+		let overwrites: Vec<_> = overwrites
+			.iter()
+			.map(|(id, is_member, allow, deny)| {
+				json!({"id": id, "type": u8::from(*is_member), "allow": allow, "deny": deny})
+			})
+			.collect();
+		serde_json::from_value(json!({
+			"id": 100,
+			"type": r#type,
+			"permission_overwrites": overwrites,
+		}))
+		.unwrap()
+	}
+
+	#[test]
+	fn owner_and_admin() {
+		let guild = guild();
+		let text = channel(ChannelType::GuildText, &[(
+			GUILD,
+			false,
+			Permissions::NONE,
+			Permissions::VIEW_CHANNEL,
+		)]);
+
+		assert_eq!(
+			guild_permissions(&guild, &member(&[], None), OWNER),
+			Permissions::ALL
+		);
+		assert_eq!(
+			guild_permissions(&guild, &member(&[ADMIN], None), USER),
+			Permissions::ALL
+		);
+		// overwrites don't apply to either
+		assert_eq!(
+			channel_permissions(&guild, &text, None, &member(&[], None), OWNER, NOW),
+			Permissions::ALL
+		);
+		assert_eq!(
+			channel_permissions(&guild, &text, None, &member(&[ADMIN], None), USER, NOW),
+			Permissions::ALL
+		);
+	}
+
+	#[test]
+	fn roles() {
+		let guild = guild();
+
+		assert_eq!(
+			guild_permissions(&guild, &member(&[], None), USER),
+			everyone()
+		);
+		assert_eq!(
+			guild_permissions(&guild, &member(&[MODERATOR, MEMBER], None), USER),
+			everyone() | Permissions::MANAGE_MESSAGES | Permissions::ATTACH_FILES
+		);
+	}
+
+	#[test]
+	fn overwrite_order() {
+		let guild = guild();
+		let member = member(&[MODERATOR, MEMBER], None);
+		let permissions = |overwrites: &[Overwrite]| {
+			channel_permissions(
+				&guild,
+				&channel(ChannelType::GuildText, overwrites),
+				None,
+				&member,
+				USER,
+				NOW,
+			)
+		};
+
+		// role overwrites win over @everyone's
+		let everyone_denied = (GUILD, false, Permissions::NONE, Permissions::EMBED_LINKS);
+		let role_allowed = (MEMBER, false, Permissions::EMBED_LINKS, Permissions::NONE);
+		assert!(!permissions(&[everyone_denied]).contains(Permissions::EMBED_LINKS));
+		assert!(permissions(&[role_allowed, everyone_denied]).contains(Permissions::EMBED_LINKS));
+
+		// role overwrites are combined, so an allow wins over another role's deny
+		let role_denied = (
+			MODERATOR,
+			false,
+			Permissions::NONE,
+			Permissions::EMBED_LINKS,
+		);
+		assert!(permissions(&[role_denied, role_allowed]).contains(Permissions::EMBED_LINKS));
+		assert!(permissions(&[role_allowed, role_denied]).contains(Permissions::EMBED_LINKS));
+
+		// roles the member doesn't have are skipped
+		let other_role = (ADMIN, false, Permissions::NONE, Permissions::EMBED_LINKS);
+		assert!(permissions(&[other_role]).contains(Permissions::EMBED_LINKS));
+
+		// the member's own overwrite goes last
+		let member_denied = (USER, true, Permissions::NONE, Permissions::EMBED_LINKS);
+		assert!(!permissions(&[member_denied, role_allowed]).contains(Permissions::EMBED_LINKS));
+		// a role overwrite with the member's id isn't theirs
+		let role_with_user_id = (USER, false, Permissions::NONE, Permissions::EMBED_LINKS);
+		assert!(permissions(&[role_with_user_id]).contains(Permissions::EMBED_LINKS));
+	}
+
+	#[test]
+	fn timeouts() {
+		let guild = guild();
+		let text = channel(ChannelType::GuildText, &[]);
+		let permissions = |until| {
+			channel_permissions(&guild, &text, None, &member(&[MODERATOR], until), USER, NOW)
+		};
+
+		assert_eq!(
+			permissions(Some("2100-01-01T00:00:00.000000+00:00")),
+			Permissions::VIEW_CHANNEL | Permissions::READ_MESSAGE_HISTORY
+		);
+		let everything = everyone() | Permissions::MANAGE_MESSAGES;
+		assert_eq!(
+			permissions(Some("2000-01-01T00:00:00.000000+00:00")),
+			everything
+		);
+		assert_eq!(permissions(None), everything);
+		// unparsable timestamps are ignored instead of panicking
+		assert_eq!(
+			permissions(Some("2100-01-01T00:00:00.12\u{e9}+00:00")),
+			everything
+		);
+	}
+
+	#[test]
+	fn no_view_channel() {
+		let guild = guild();
+		let hidden = channel(ChannelType::GuildText, &[(
+			GUILD,
+			false,
+			Permissions::NONE,
+			Permissions::VIEW_CHANNEL,
+		)]);
+
+		assert_eq!(
+			channel_permissions(
+				&guild,
+				&hidden,
+				None,
+				&member(&[MODERATOR], None),
+				USER,
+				NOW
+			),
+			Permissions::NONE
+		);
+	}
+
+	#[test]
+	fn dependents() {
+		let guild = guild();
+		let member = member(&[MEMBER], None);
+		let deny = |r#type, deny| {
+			let channel = channel(r#type, &[(GUILD, false, Permissions::NONE, deny)]);
+			channel_permissions(&guild, &channel, None, &member, USER, NOW)
+		};
+
+		let no_send = deny(ChannelType::GuildText, Permissions::SEND_MESSAGES);
+		assert!(!no_send.intersects(Permissions::EMBED_LINKS | Permissions::ATTACH_FILES));
+		assert!(no_send.contains(Permissions::VIEW_CHANNEL | Permissions::READ_MESSAGE_HISTORY));
+
+		// connect only matters in voice channels
+		let no_connect = deny(ChannelType::GuildText, Permissions::CONNECT);
+		assert!(no_connect.contains(Permissions::SPEAK));
+		let no_connect = deny(ChannelType::GuildVoice, Permissions::CONNECT);
+		assert!(!no_connect.contains(Permissions::SPEAK));
+		assert!(no_connect.contains(Permissions::SEND_MESSAGES));
+	}
+
+	#[test]
+	fn threads() {
+		let guild = guild();
+		let member = member(&[MEMBER], None);
+		let thread = channel(ChannelType::PublicThread, &[]);
+		let permissions = |parent: &Channel| {
+			(
+				channel_permissions(&guild, parent, None, &member, USER, NOW),
+				channel_permissions(&guild, &thread, Some(parent), &member, USER, NOW),
+			)
+		};
+
+		// only sending in threads keeps attachments and embeds there
+		let parent = channel(ChannelType::GuildText, &[(
+			GUILD,
+			false,
+			Permissions::SEND_MESSAGES_IN_THREADS,
+			Permissions::SEND_MESSAGES,
+		)]);
+		let (in_parent, in_thread) = permissions(&parent);
+		assert!(!in_parent.intersects(Permissions::EMBED_LINKS | Permissions::ATTACH_FILES));
+		assert!(in_thread.contains(Permissions::EMBED_LINKS | Permissions::ATTACH_FILES));
+
+		// the parent's overwrites apply to its threads
+		let hidden = channel(ChannelType::GuildText, &[(
+			GUILD,
+			false,
+			Permissions::NONE,
+			Permissions::VIEW_CHANNEL,
+		)]);
+		assert_eq!(permissions(&hidden), (Permissions::NONE, Permissions::NONE));
+	}
+}
+
+mod endpoints {
+	use crate::endpoints::messages::*;
+	use crate::endpoints::users::GET_CURRENT_USER;
+	use crate::endpoints::{Endpoint, Method};
+	use crate::types::message::{Message, ReactionType};
+	use crate::types::snowflake::Snowflake;
+
+	#[test]
+	fn paths() {
+		let channel_id = Snowflake(81384788765712384);
+		let message_id = Snowflake(1);
+
+		assert_eq!(
+			GET_MESSAGES(channel_id, &GetMessagesQuery::default()),
+			"/channels/81384788765712384/messages"
+		);
+		assert_eq!(
+			GET_MESSAGES(channel_id, &GetMessagesQuery {
+				limit: Some(50),
+				..Default::default()
+			}),
+			"/channels/81384788765712384/messages?limit=50"
+		);
+		assert_eq!(GET_CURRENT_USER, "/users/@me");
+
+		// parameters are encoded so they stay one segment, the rest of the path isn't
+		assert_eq!(
+			DELETE_OWN_REACTION(channel_id, message_id, "👍", ReactionType::Burst),
+			"/channels/81384788765712384/messages/1/reactions/%F0%9F%91%8D/1/@me"
+		);
+		assert_eq!(
+			DELETE_ATTACHMENT_UPLOAD("a/b c.png"),
+			"/attachments/a%2Fb%20c.png"
+		);
+		assert_eq!(
+			GET_REACTIONS(
+				channel_id,
+				message_id,
+				"cow:1234",
+				&GetReactionsQuery::default()
+			),
+			"/channels/81384788765712384/messages/1/reactions/cow:1234"
+		);
+	}
+
+	#[test]
+	fn bindings() {
+		fn types<E: Endpoint>() -> (Method, &'static str) {
+			(E::METHOD, E::PATH)
+		}
+		fn response<E: Endpoint<Response = R>, R>() {}
+		fn request<E: Endpoint<Request = R>, R>() {}
+
+		assert_eq!(
+			types::<EDIT_MESSAGE>(),
+			(
+				Method::PATCH,
+				"/channels/{channel_id}/messages/{message_id}"
+			)
+		);
+		assert_eq!(types::<DELETE_OWN_REACTION>().0, Method::DELETE);
+
+		response::<GET_MESSAGES, Vec<Message>>();
+		response::<DELETE_OWN_REACTION, ()>();
+		request::<CREATE_MESSAGE, CreateMessageRequest>();
+		request::<GET_MESSAGES, ()>();
+	}
+}
+
+mod gateway {
+	use serde_json::json;
+
+	use crate::types::message::Message;
+	use crate::types::ws::gateway::GatewayEvent;
+
+	#[test]
+	fn message_update() {
+		let message: Message = serde_json::from_value(json!({
+			"id": "2",
+			"channel_id": "1",
+			"author": {"id": "3", "username": "cow", "discriminator": "0"},
+			"content": "moo",
+			"timestamp": "2024-01-01T00:00:00.000000+00:00",
+			"type": 0,
+		}))
+		.unwrap();
+
+		// only the changed fields are sent
+		let event = GatewayEvent::from_dispatch(
+			"MESSAGE_UPDATE",
+			json!({
+				"id": "2",
+				"channel_id": "1",
+				"content": "moo!",
+				"edited_timestamp": "2024-01-01T00:01:00.000000+00:00",
+			}),
+		)
+		.unwrap();
+		let GatewayEvent::MessageUpdate(update) = event else {
+			panic!("expected a message update");
+		};
+
+		let edited = update.apply(&message).unwrap();
+		assert_eq!(edited.content, "moo!");
+		assert_eq!(
+			edited.edited_timestamp.as_deref(),
+			Some("2024-01-01T00:01:00.000000+00:00")
+		);
+		assert_eq!(edited.author.username, "cow");
+		assert_eq!(edited.timestamp, message.timestamp);
+	}
+}
@@ -0,0 +1,27 @@
+use cowcord_macros::bitflags;
+
+bitflags! {
+	/// What an activity supports
+	pub struct ActivityFlags: u64 {
+		/// The activity is an instanced game session, a match that will end
+		const INSTANCE = 1 << 0;
+		/// The activity can be joined by other users
+		const JOIN = 1 << 1;
+		/// The activity can be spectated by other users
+		const SPECTATE = 1 << 2;
+		/// Other users can ask to join the activity
+		const JOIN_REQUEST = 1 << 3;
+		/// The activity can be synced, like listening along on Spotify
+		const SYNC = 1 << 4;
+		/// The activity can be played
+		const PLAY = 1 << 5;
+		/// Friends can join the activity's party
+		const PARTY_PRIVACY_FRIENDS = 1 << 6;
+		/// Users in the same voice channel can join the activity's party
+		const PARTY_PRIVACY_VOICE_CHANNEL = 1 << 7;
+		/// The activity is an embedded activity in a voice channel
+		const EMBEDDED = 1 << 8;
+		/// The activity isn't tied to a channel
+		const CONTEXTLESS = 1 << 9;
+	}
+}
@@ -0,0 +1,198 @@
+use cowcord_macros::bitflags;
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+use crate::types::permissions::Permissions;
+use crate::types::snowflake::Snowflake;
+use crate::types::user::User;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Channel {
+	/// The ID of the channel
+	pub id: Snowflake,
+	/// The type of channel
+	pub r#type: ChannelType,
+	/// The ID of the guild the channel is in
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub guild_id: Option<Snowflake>,
+	/// Sorting position of the channel
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub position: Option<i32>,
+	/// Explicit permission overwrites for members and roles
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub permission_overwrites: Option<Vec<PermissionOverwrite>>,
+	/// The name of the channel (1-100 characters)
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub name: Option<String>,
+	/// The channel topic (max 4096 characters for thread-only channels, max 1024 characters otherwise)
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub topic: Option<String>,
+	/// Whether the channel is NSFW
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub nsfw: Option<bool>,
+	/// The ID of the last message sent in this channel (or thread for thread-only channels) (may not point to an existing or valid message or thread)
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub last_message_id: Option<Snowflake>,
+	/// The bitrate (in bits) of the voice channel
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub bitrate: Option<u32>,
+	/// The user limit of the voice channel
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub user_limit: Option<u32>,
+	/// Duration in seconds a user has to wait before sending another message (max 21600)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub rate_limit_per_user: Option<u32>,
+	/// The recipients of the private channel, excluding the requesting user
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub recipients: Option<Vec<User>>,
+	/// The icon hash of the group DM
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub icon: Option<String>,
+	/// The ID of the owner of the group DM or thread
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub owner_id: Option<Snowflake>,
+	/// The ID of the parent category/channel for the guild channel/thread
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub parent_id: Option<Snowflake>,
+	/// When the last pinned message was pinned, if any
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub last_pin_timestamp: Option<String>,
+	/// The channel's flags
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub flags: Option<ChannelFlags>,
+}
+
+bitflags! {
+	/// Where a channel is shown and how it behaves
+	pub struct ChannelFlags: u64 {
+		/// The channel is hidden from the guild's feed
+		const GUILD_FEED_REMOVED = 1 << 0;
+		/// The thread is pinned to the top of its forum
+		const PINNED = 1 << 1;
+		/// The channel is hidden from the guild's active channels
+		const ACTIVE_CHANNELS_REMOVED = 1 << 2;
+		/// Threads in the forum need a tag
+		const REQUIRE_TAG = 1 << 4;
+		/// The channel is marked as spam
+		const IS_SPAM = 1 << 5;
+		/// The channel is a resource channel in the guild's home page
+		const IS_GUILD_RESOURCE_CHANNEL = 1 << 7;
+		/// The channel was made by Clyde
+		const CLYDE_AI = 1 << 8;
+		/// The channel is scheduled for deletion
+		const IS_SCHEDULED_FOR_DELETION = 1 << 9;
+		/// The channel is a media channel
+		const IS_MEDIA_CHANNEL = 1 << 10;
+		/// Conversation summaries are disabled in the channel
+		const SUMMARIES_DISABLED = 1 << 11;
+		/// The channel previews a role subscription
+		const IS_ROLE_SUBSCRIPTION_TEMPLATE_PREVIEW_CHANNEL = 1 << 13;
+		/// The group DM is broadcasting
+		const IS_BROADCASTING = 1 << 14;
+		/// Media in the channel can't be downloaded from the media viewer
+		const HIDE_MEDIA_DOWNLOAD_OPTIONS = 1 << 15;
+		/// The group DM is for a guild join request interview
+		const IS_JOIN_REQUEST_INTERVIEW_CHANNEL = 1 << 16;
+		/// The channel's contents are hidden from the user
+		const OBFUSCATED = 1 << 17;
+	}
+}
+
+impl Channel {
+	/// the name shown in the client, private channels without a name use their recipients instead
+	pub fn display_name(&self) -> String {
+		match (&self.name, &self.recipients) {
+			| (Some(name), _) => name.to_owned(),
+			| (None, Some(recipients)) => recipients
+				.iter()
+				.map(|r| r.display_name())
+				.collect::<Vec<_>>()
+				.join(", "),
+			| (None, None) => self.id.to_string(),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum ChannelType {
+	/// A text channel within a guild
+	GuildText = 0,
+	/// A private channel between two users
+	Dm = 1,
+	/// A voice channel within a guild
+	GuildVoice = 2,
+	/// A private channel between multiple users
+	GroupDm = 3,
+	/// An organizational category that contains up to 50 channels
+	GuildCategory = 4,
+	/// Almost identical to GUILD_TEXT, a channel that users can follow and crosspost into their own guild
+	GuildNews = 5,
+	/// A channel in which game developers can sell their game on Discord
+	GuildStore = 6,
+	/// A channel where users can match up for various games
+	GuildLfg = 7,
+	/// A private channel between multiple users for a group within an LFG channel
+	LfgGroupDm = 8,
+	/// The first iteration of the threads feature, never widely used
+	ThreadAlpha = 9,
+	/// A temporary sub-channel within a GUILD_NEWS channel
+	NewsThread = 10,
+	/// A temporary sub-channel within a GUILD_TEXT, GUILD_FORUM, or GUILD_MEDIA channel
+	PublicThread = 11,
+	/// A temporary sub-channel within a GUILD_TEXT channel that is only viewable by those invited and those with the MANAGE_THREADS permission
+	PrivateThread = 12,
+	/// A voice channel for hosting events with an audience in a guild
+	GuildStageVoice = 13,
+	/// The main channel in a hub containing the listed guilds
+	GuildDirectory = 14,
+	/// A channel that can only contain threads
+	GuildForum = 15,
+	/// A channel that can only contain threads in a gallery view
+	GuildMedia = 16,
+	/// A game lobby channel
+	Lobby = 17,
+	/// A private channel created by the social layer SDK
+	EphemeralDm = 18,
+}
+
+impl ChannelType {
+	pub fn is_thread(&self) -> bool {
+		matches!(
+			self,
+			Self::NewsThread | Self::PublicThread | Self::PrivateThread | Self::ThreadAlpha
+		)
+	}
+
+	pub fn is_voice(&self) -> bool {
+		matches!(self, Self::GuildVoice | Self::GuildStageVoice)
+	}
+
+	pub fn is_private(&self) -> bool {
+		matches!(
+			self,
+			Self::Dm | Self::GroupDm | Self::LfgGroupDm | Self::EphemeralDm
+		)
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PermissionOverwrite {
+	/// The role or user ID the overwrite applies to
+	pub id: Snowflake,
+	/// The type of overwritten entity
+	pub r#type: PermissionOverwriteType,
+	/// The permissions allowed by the overwrite
+	pub allow: Permissions,
+	/// The permissions denied by the overwrite
+	pub deny: Permissions,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum PermissionOverwriteType {
+	/// The overwrite applies to a role
+	Role = 0,
+	/// The overwrite applies to a member
+	Member = 1,
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::snowflake::Snowflake;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Emoji {
+	/// The ID of the emoji, `None` for unicode emojis
+	pub id: Option<Snowflake>,
+	/// The name of the emoji, only `None` in reactions of deleted emojis
+	pub name: Option<String>,
+	/// The roles allowed to use this emoji
+	#[serde(default)]
+	pub roles: Vec<Snowflake>,
+	/// Whether this emoji must be wrapped in colons
+	#[serde(default)]
+	pub require_colons: bool,
+	/// Whether this emoji is managed by an integration
+	#[serde(default)]
+	pub managed: bool,
+	/// Whether this emoji is animated
+	#[serde(default)]
+	pub animated: bool,
+	/// Whether this emoji can be used, may be false due to loss of server boosts
+	#[serde(default = "default_available")]
+	pub available: bool,
+}
+
+impl Emoji {
+	/// How the emoji is written in reaction endpoints, the emoji itself for unicode emojis or `name:id` for custom ones
+	pub fn reaction_id(&self) -> String {
+		let name = self.name.as_deref().unwrap_or_default();
+		match self.id {
+			| Some(id) => format!("{name}:{id}"),
+			| None => name.to_owned(),
+		}
+	}
+
+	/// Whether both are the same emoji, custom emojis are compared by ID since they can be renamed
+	pub fn is_same(
+		&self,
+		other: &Emoji,
+	) -> bool {
+		match (self.id, other.id) {
+			| (Some(a), Some(b)) => a == b,
+			| (None, None) => self.name == other.name,
+			| _ => false,
+		}
+	}
+}
+
+fn default_available() -> bool {
+	true
+}
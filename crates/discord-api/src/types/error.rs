@@ -28,3 +28,17 @@ pub struct Error {
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub errors: Option<FormErrors>,
 }
+
+/// Returned with a `429` status code when a rate limit is hit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimitError {
+	/// A message saying you are being rate limited
+	pub message: String,
+	/// The number of seconds to wait before submitting another request
+	pub retry_after: f64,
+	/// Whether this is a global rate limit
+	pub global: bool,
+	/// An error code for some limits
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub code: Option<i32>,
+}
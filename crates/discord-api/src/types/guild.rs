@@ -0,0 +1,295 @@
+use cowcord_macros::bitflags;
+use hex::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::days_from_civil;
+use crate::types::channel::Channel;
+use crate::types::emoji::Emoji;
+use crate::types::permissions::Permissions;
+use crate::types::snowflake::Snowflake;
+use crate::types::user::User;
+
+/// A partial guild object as returned from the user guilds endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserGuild {
+	/// The ID of the guild
+	pub id: Snowflake,
+	/// The name of the guild (2-100 characters)
+	pub name: String,
+	/// The guild's icon hash
+	#[serde(default)]
+	pub icon: Option<String>,
+	/// The guild's banner hash
+	#[serde(default)]
+	pub banner: Option<String>,
+	/// Whether the user is the owner of the guild
+	pub owner: bool,
+	/// Total permissions for the user in the guild (excludes overwrites and implicit permissions)
+	pub permissions: Permissions,
+	/// The custom emojis in the guild
+	#[serde(default)]
+	pub emojis: Vec<Emoji>,
+	/// Enabled guild features
+	#[serde(default)]
+	pub features: Vec<String>,
+	/// Approximate number of members in the guild
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub approximate_member_count: Option<u32>,
+	/// Approximate number of non-offline members in the guild
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub approximate_presence_count: Option<u32>,
+}
+
+/// A guild as sent in the gateway `READY` and `GUILD_CREATE` events
+///
+/// Unavailable guilds only have their `id` and `unavailable` set
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Guild {
+	/// The ID of the guild
+	pub id: Snowflake,
+	/// The name of the guild (2-100 characters)
+	#[serde(default)]
+	pub name: String,
+	/// The guild's icon hash
+	#[serde(default)]
+	pub icon: Option<String>,
+	/// The ID of the user that owns the guild
+	#[serde(default)]
+	pub owner_id: Snowflake,
+	/// The roles in the guild
+	#[serde(default)]
+	pub roles: Vec<Role>,
+	/// The custom emojis in the guild
+	#[serde(default)]
+	pub emojis: Vec<Emoji>,
+	/// Enabled guild features
+	#[serde(default)]
+	pub features: Vec<String>,
+	/// The guild's boost level (0-3), higher levels raise the upload limit
+	#[serde(default)]
+	pub premium_tier: u8,
+	/// The vanity invite code of the guild
+	#[serde(default)]
+	pub vanity_url_code: Option<String>,
+	/// The type of student hub the guild is, if it's one
+	#[serde(default)]
+	pub hub_type: Option<i64>,
+	/// Total number of members in the guild
+	#[serde(default)]
+	pub member_count: Option<u64>,
+	/// Which messages the system channel doesn't get
+	#[serde(default)]
+	pub system_channel_flags: SystemChannelFlags,
+	/// The channels in the guild, including ones the user can't view
+	#[serde(default)]
+	pub channels: Vec<Channel>,
+	/// The members of the guild sent with the guild, always includes the current user
+	#[serde(default)]
+	pub members: Vec<GuildMember>,
+	/// The voice states of members currently in voice channels
+	#[serde(default)]
+	pub voice_states: Vec<VoiceState>,
+	/// Whether the guild is unavailable due to an outage
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub unavailable: Option<bool>,
+}
+
+bitflags! {
+	/// Which messages the guild's system channel doesn't get
+	pub struct SystemChannelFlags: u64 {
+		/// No member join messages
+		const SUPPRESS_JOIN_NOTIFICATIONS = 1 << 0;
+		/// No boost messages
+		const SUPPRESS_PREMIUM_SUBSCRIPTIONS = 1 << 1;
+		/// No guild setup tips
+		const SUPPRESS_GUILD_REMINDER_NOTIFICATIONS = 1 << 2;
+		/// No sticker reply buttons on member join messages
+		const SUPPRESS_JOIN_NOTIFICATION_REPLIES = 1 << 3;
+		/// No role subscription purchase and renewal messages
+		const SUPPRESS_ROLE_SUBSCRIPTION_PURCHASE_NOTIFICATIONS = 1 << 4;
+		/// No sticker reply buttons on role subscription purchase messages
+		const SUPPRESS_ROLE_SUBSCRIPTION_PURCHASE_NOTIFICATION_REPLIES = 1 << 5;
+		/// No prompts to revive quiet channels
+		const SUPPRESS_CHANNEL_PROMPT_DEADCHAT = 1 << 7;
+	}
+}
+
+impl Guild {
+	/// The colors of the member's highest role that has any, which is what their name is shown in
+	pub fn member_colors(
+		&self,
+		member: &GuildMember,
+	) -> Option<RoleColors> {
+		self.roles
+			.iter()
+			.filter(|r| member.roles.contains(&r.id))
+			.filter_map(|r| Some((r, r.display_colors()?)))
+			// roles with the same position are sorted by ID, older roles are higher
+			.max_by_key(|(r, _)| (r.position, std::cmp::Reverse(r.id)))
+			.map(|(_, colors)| colors)
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Role {
+	/// The ID of the role
+	pub id: Snowflake,
+	/// The name of the role (max 100 characters)
+	pub name: String,
+	/// The role's color, `0` is no color
+	#[serde(default, with = "hex::as_num")]
+	pub color: Color,
+	/// The role's gradient or holographic colors, `color` is the same as the primary color
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub colors: Option<RoleColors>,
+	/// Whether this role is pinned in the user listing
+	#[serde(default)]
+	pub hoist: bool,
+	/// The role's icon hash
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub icon: Option<String>,
+	/// The role's unicode emoji
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub unicode_emoji: Option<String>,
+	/// Position of this role, roles with the same position are sorted by ID
+	pub position: i32,
+	/// The permissions given by the role
+	pub permissions: Permissions,
+	/// Whether this role is managed by an integration
+	#[serde(default)]
+	pub managed: bool,
+	/// Whether this role is mentionable
+	#[serde(default)]
+	pub mentionable: bool,
+}
+
+impl Role {
+	/// The colors names are shown in, `None` if the role doesn't color names
+	pub fn display_colors(&self) -> Option<RoleColors> {
+		match &self.colors {
+			| Some(colors) if colors.primary_color != Color::BLACK => Some(colors.clone()),
+			| _ if self.color != Color::BLACK => Some(RoleColors {
+				primary_color: self.color,
+				secondary_color: None,
+				tertiary_color: None,
+			}),
+			| _ => None,
+		}
+	}
+}
+
+/// A role's colors, a secondary color makes it a gradient and a tertiary color makes it holographic
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoleColors {
+	#[serde(with = "hex::as_num")]
+	pub primary_color: Color,
+	#[serde(default, with = "hex::as_num")]
+	pub secondary_color: Option<Color>,
+	/// Only set for holographic roles
+	#[serde(default, with = "hex::as_num")]
+	pub tertiary_color: Option<Color>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuildMember {
+	/// The user this guild member represents, missing when it's sent alongside a user object
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub user: Option<User>,
+	/// The guild-specific nickname of the member (1-32 characters)
+	#[serde(default)]
+	pub nick: Option<String>,
+	/// The member's guild avatar hash
+	#[serde(default)]
+	pub avatar: Option<String>,
+	/// The role IDs assigned to this member
+	#[serde(default)]
+	pub roles: Vec<Snowflake>,
+	/// When the user joined the guild
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub joined_at: Option<String>,
+	/// Whether the member is server deafened in voice channels
+	#[serde(default)]
+	pub deaf: bool,
+	/// Whether the member is server muted in voice channels
+	#[serde(default)]
+	pub mute: bool,
+	/// When the member's timeout will expire and they will be able to communicate in the guild again
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub communication_disabled_until: Option<String>,
+}
+
+impl GuildMember {
+	/// When the member's timeout ends, in unix milliseconds
+	///
+	/// Timeouts that already ended can still be set, compare with the current time
+	pub fn timed_out_until(&self) -> Option<u64> {
+		parse_timestamp(self.communication_disabled_until.as_deref()?)
+	}
+}
+
+/// A user's connection to a voice channel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VoiceState {
+	/// The ID of the guild this voice state is for
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub guild_id: Option<Snowflake>,
+	/// The ID of the channel this user is connected to, `None` once they leave
+	pub channel_id: Option<Snowflake>,
+	/// The ID of the user this voice state is for
+	pub user_id: Snowflake,
+	/// The guild member this voice state is for
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub member: Option<GuildMember>,
+	/// The session ID for this voice state
+	pub session_id: String,
+	/// Whether this user is deafened by the guild
+	#[serde(default)]
+	pub deaf: bool,
+	/// Whether this user is muted by the guild
+	#[serde(default)]
+	pub mute: bool,
+	/// Whether this user is locally deafened
+	#[serde(default)]
+	pub self_deaf: bool,
+	/// Whether this user is locally muted
+	#[serde(default)]
+	pub self_mute: bool,
+	/// Whether this user is streaming using "Go Live"
+	#[serde(default)]
+	pub self_stream: bool,
+	/// Whether this user's camera is enabled
+	#[serde(default)]
+	pub self_video: bool,
+	/// Whether this user's permission to speak is denied
+	#[serde(default)]
+	pub suppress: bool,
+}
+
+/// Parses an ISO8601 timestamp like `2021-12-23T00:00:00.000000+00:00` into unix milliseconds
+fn parse_timestamp(timestamp: &str) -> Option<u64> {
+	let (date, time) = timestamp.split_once('T')?;
+
+	let mut date = date.splitn(3, '-').map(|p| p.parse::<i64>().ok());
+	let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+
+	// the offset starts at the first `+`, `-` or `Z` after the seconds
+	let offset_start = time.find(['+', '-', 'Z']).unwrap_or(time.len());
+	let (time, offset) = time.split_at(offset_start);
+	let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+	let mut time = time.splitn(3, ':').map(|p| p.parse::<i64>().ok());
+	let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+	let millis = format!("{fraction:0<3}").get(..3)?.parse::<i64>().ok()?;
+
+	let offset_minutes = match offset.split_at_checked(1) {
+		| Some((sign @ ("+" | "-"), offset)) => {
+			let (hours, minutes) = offset.split_once(':')?;
+			let minutes = hours.parse::<i64>().ok()? * 60 + minutes.parse::<i64>().ok()?;
+			if sign == "-" { -minutes } else { minutes }
+		},
+		| _ => 0,
+	};
+
+	let days = days_from_civil(year, month, day);
+	let seconds = days * 86_400 + hour * 3600 + (minute - offset_minutes) * 60 + second;
+	u64::try_from(seconds * 1000 + millis).ok()
+}
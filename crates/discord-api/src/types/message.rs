@@ -0,0 +1,492 @@
+use std::fmt::{self, Display};
+
+use cowcord_macros::bitflags;
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+use crate::types::emoji::Emoji;
+use crate::types::snowflake::Snowflake;
+use crate::types::user::User;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Message {
+	/// The ID of the message
+	pub id: Snowflake,
+	/// The ID of the channel the message was sent in
+	pub channel_id: Snowflake,
+	/// The author of the message
+	pub author: User,
+	/// Contents of the message
+	pub content: String,
+	/// When this message was sent
+	pub timestamp: String,
+	/// When this message was last edited
+	#[serde(default)]
+	pub edited_timestamp: Option<String>,
+	/// Whether this was a TTS message
+	#[serde(default)]
+	pub tts: bool,
+	/// Whether this message mentions everyone
+	#[serde(default)]
+	pub mention_everyone: bool,
+	/// Users specifically mentioned in the message
+	#[serde(default)]
+	pub mentions: Vec<User>,
+	/// Roles specifically mentioned in this message
+	#[serde(default)]
+	pub mention_roles: Vec<Snowflake>,
+	/// The attached files
+	#[serde(default)]
+	pub attachments: Vec<Attachment>,
+	/// Content embedded in the message
+	#[serde(default)]
+	pub embeds: Vec<Embed>,
+	/// Reactions to the message
+	#[serde(default)]
+	pub reactions: Vec<Reaction>,
+	/// Validates a message or interaction was sent, returned in the message create event
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub nonce: Option<Nonce>,
+	/// Whether this message is pinned
+	#[serde(default)]
+	pub pinned: bool,
+	/// If the message is generated by a webhook, this is the webhook's ID
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub webhook_id: Option<Snowflake>,
+	/// The type of message
+	pub r#type: MessageType,
+	/// The message's flags
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub flags: Option<MessageFlags>,
+	/// Data showing the source of a crosspost, channel follow add, pin, or reply message
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub message_reference: Option<MessageReference>,
+	/// The message associated with the message_reference
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub referenced_message: Option<Option<Box<Message>>>,
+	/// The ID of the guild the message was sent in, only sent in gateway events
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub guild_id: Option<Snowflake>,
+}
+
+bitflags! {
+	/// How a message is shown and where it came from
+	pub struct MessageFlags: u64 {
+		/// The message was published to the channels following it
+		const CROSSPOSTED = 1 << 0;
+		/// The message came from a followed announcement channel
+		const IS_CROSSPOST = 1 << 1;
+		/// The message doesn't show embeds
+		const SUPPRESS_EMBEDS = 1 << 2;
+		/// The message this crosspost came from was deleted
+		const SOURCE_MESSAGE_DELETED = 1 << 3;
+		/// The message came from the urgent message system
+		const URGENT = 1 << 4;
+		/// The message has a thread
+		const HAS_THREAD = 1 << 5;
+		/// The message is only visible to the user who invoked the interaction
+		const EPHEMERAL = 1 << 6;
+		/// The message is an interaction response and the bot is "thinking"
+		const LOADING = 1 << 7;
+		/// Some roles weren't mentioned and added to the thread
+		const FAILED_TO_MENTION_SOME_ROLES_IN_THREAD = 1 << 8;
+		/// The message is hidden from the guild's feed
+		const GUILD_FEED_HIDDEN = 1 << 9;
+		/// The message has a link that should warn it isn't from Discord
+		const SHOULD_SHOW_LINK_NOT_DISCORD_WARNING = 1 << 10;
+		/// The message doesn't trigger push or desktop notifications
+		const SUPPRESS_NOTIFICATIONS = 1 << 12;
+		/// The message is a voice message
+		const IS_VOICE_MESSAGE = 1 << 13;
+		/// The message has a snapshot of a forwarded message
+		const HAS_SNAPSHOT = 1 << 14;
+		/// The message uses the new components system
+		const IS_COMPONENTS_V2 = 1 << 15;
+		/// The message was sent from a game's social layer integration
+		const SENT_BY_SOCIAL_LAYER_INTEGRATION = 1 << 16;
+	}
+}
+
+/// Nonces are sent back by discord as whatever type the client sent them as
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Nonce {
+	Integer(u64),
+	String(String),
+}
+
+impl Display for Nonce {
+	fn fmt(
+		&self,
+		f: &mut fmt::Formatter<'_>,
+	) -> fmt::Result {
+		match self {
+			| Nonce::Integer(n) => write!(f, "{n}"),
+			| Nonce::String(s) => write!(f, "{s}"),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum MessageType {
+	/// A default message
+	Default = 0,
+	/// A message sent when a user is added to a group DM or thread
+	RecipientAdd = 1,
+	/// A message sent when a user is removed from a group DM or thread
+	RecipientRemove = 2,
+	/// A message sent when a user creates a call in a private channel
+	Call = 3,
+	/// A message sent when a group DM or thread's name is changed
+	ChannelNameChange = 4,
+	/// A message sent when a group DM's icon is changed
+	ChannelIconChange = 5,
+	/// A message sent when a message is pinned in a channel
+	ChannelPinnedMessage = 6,
+	/// A message sent when a user joins a guild
+	UserJoin = 7,
+	/// A message sent when a user subscribes to (boosts) a guild
+	PremiumGuildSubscription = 8,
+	/// A message sent when a user subscribes to (boosts) a guild to tier 1
+	PremiumGuildSubscriptionTier1 = 9,
+	/// A message sent when a user subscribes to (boosts) a guild to tier 2
+	PremiumGuildSubscriptionTier2 = 10,
+	/// A message sent when a user subscribes to (boosts) a guild to tier 3
+	PremiumGuildSubscriptionTier3 = 11,
+	/// A message sent when a news channel is followed
+	ChannelFollowAdd = 12,
+	/// A message sent when a user starts streaming in a guild
+	GuildStream = 13,
+	/// A message sent when a guild is disqualified from discovery
+	GuildDiscoveryDisqualified = 14,
+	/// A message sent when a guild requalifies for discovery
+	GuildDiscoveryRequalified = 15,
+	/// A message sent when a guild has failed discovery requirements for a week
+	GuildDiscoveryGracePeriodInitialWarning = 16,
+	/// A message sent when a guild has failed discovery requirements for 3 weeks
+	GuildDiscoveryGracePeriodFinalWarning = 17,
+	/// A message sent when a thread is created
+	ThreadCreated = 18,
+	/// A message sent when a user replies to a message
+	Reply = 19,
+	/// A message sent when a user uses a slash command
+	ChatInputCommand = 20,
+	/// A message sent when a thread starter message is added to a thread
+	ThreadStarterMessage = 21,
+	/// A message sent to remind users to invite friends to a guild
+	GuildInviteReminder = 22,
+	/// A message sent when a user uses a context menu command
+	ContextMenuCommand = 23,
+	/// A message sent when auto moderation takes an action
+	AutoModerationAction = 24,
+	/// A message sent when a user purchases or renews a role subscription
+	RoleSubscriptionPurchase = 25,
+	/// A message sent when a user is upsold to a premium interaction
+	InteractionPremiumUpsell = 26,
+	/// A message sent when a stage channel starts
+	StageStart = 27,
+	/// A message sent when a stage channel ends
+	StageEnd = 28,
+	/// A message sent when a user starts speaking in a stage channel
+	StageSpeaker = 29,
+	/// A message sent when a user raises their hand in a stage channel
+	StageRaiseHand = 30,
+	/// A message sent when a stage channel's topic is changed
+	StageTopic = 31,
+	/// A message sent when a user purchases an application premium subscription
+	GuildApplicationPremiumSubscription = 32,
+	/// A message sent when a user adds an application to group DM
+	PrivateChannelIntegrationAdded = 33,
+	/// A message sent when a user removed an application from a group DM
+	PrivateChannelIntegrationRemoved = 34,
+	/// A message sent when a user gifts a premium (Nitro) referral
+	PremiumReferral = 35,
+	/// A message sent when a user enabled lockdown for the guild
+	GuildIncidentAlertModeEnabled = 36,
+	/// A message sent when a user disables lockdown for the guild
+	GuildIncidentAlertModeDisabled = 37,
+	/// A message sent when a user reports a raid for the guild
+	GuildIncidentReportRaid = 38,
+	/// A message sent when a user reports a false alarm for the guild
+	GuildIncidentReportFalseAlarm = 39,
+	/// A message sent when no one sends a message in the current channel for 1 hour
+	GuildDeadchatRevivePrompt = 40,
+	/// A message sent when a user buys another user a gift
+	CustomGift = 41,
+	/// A message sent to remind users of their guild gaming stats
+	GuildGamingStatsPrompt = 42,
+	/// A message sent when a user purchases a guild product
+	PurchaseNotification = 44,
+	/// A message sent when a poll is finalized
+	PollResult = 46,
+	/// A message sent by the Discord Updates account when a new changelog is posted
+	Changelog = 47,
+	/// A message sent when a Nitro promotion is triggered
+	NitroNotification = 48,
+	/// A message sent when a voice channel is linked to a lobby
+	ChannelLinkedToLobby = 49,
+	/// A local-only ephemeral message sent when a user is prompted to gift Nitro to a friend on their friendship anniversary
+	GiftingPrompt = 50,
+	/// A local-only message sent when a user receives an in-game message NUX
+	InGameMessageNux = 51,
+	/// A message sent when a user accepts a guild join request
+	GuildJoinRequestAcceptNotification = 52,
+	/// A message sent when a user rejects a guild join request
+	GuildJoinRequestRejectNotification = 53,
+	/// A message sent when a user withdraws a guild join request
+	GuildJoinRequestWithdrawnNotification = 54,
+	/// A message sent when a user upgrades to HD streaming
+	HdStreamingUpgraded = 55,
+	/// A message sent when a user resolves a moderation report by deleting the offending message
+	ReportToModDeletedMessage = 58,
+	/// A message sent when a user resolves a moderation report by timing out the offending user
+	ReportToModTimeoutUser = 59,
+	/// A message sent when a user resolves a moderation report by kicking the offending user
+	ReportToModKickUser = 60,
+	/// A message sent when a user resolves a moderation report by banning the offending user
+	ReportToModBanUser = 61,
+	/// A message sent when a user resolves a moderation report
+	ReportToModClosedReport = 62,
+	/// A message sent when a user adds a new emoji to a guild
+	EmojiAdded = 63,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageReference {
+	/// The type of reference (default DEFAULT)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub r#type: Option<MessageReferenceType>,
+	/// The ID of the originating message
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub message_id: Option<Snowflake>,
+	/// The ID of the originating message's channel
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub channel_id: Option<Snowflake>,
+	/// The ID of the originating message's guild
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub guild_id: Option<Snowflake>,
+	/// Whether to error if the referenced message doesn't exist instead of sending as a normal (non-reply) message (default true)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub fail_if_not_exists: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum MessageReferenceType {
+	/// A standard reference used by replies and system messages
+	Default = 0,
+	/// A reference used to point to a message at a point in time
+	Forward = 1,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attachment {
+	/// The ID of the attachment
+	pub id: Snowflake,
+	/// The name of the attached file
+	pub filename: String,
+	/// The title of the file
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub title: Option<String>,
+	/// The description (alt text) for the file (max 1024 characters)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub description: Option<String>,
+	/// The attachment's media type
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub content_type: Option<String>,
+	/// The size of the file in bytes
+	pub size: u64,
+	/// The source URL of the file
+	pub url: String,
+	/// A proxied URL of the file
+	pub proxy_url: String,
+	/// The height of the file (if image)
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub height: Option<u32>,
+	/// The width of the file (if image)
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub width: Option<u32>,
+	/// The duration of the audio file (if voice message)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub duration_secs: Option<f64>,
+	/// Base64 encoded bytearray representing a sampled waveform (if voice message)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub waveform: Option<String>,
+	/// The attachment's flags
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub flags: Option<AttachmentFlags>,
+}
+
+bitflags! {
+	/// What an attachment is
+	pub struct AttachmentFlags: u64 {
+		/// The attachment is a clip from a stream
+		const IS_CLIP = 1 << 0;
+		/// The attachment is a thumbnail
+		const IS_THUMBNAIL = 1 << 1;
+		/// The attachment was edited with remix
+		const IS_REMIX = 1 << 2;
+		/// The attachment is a spoiler
+		const IS_SPOILER = 1 << 3;
+		/// The attachment was flagged as explicit
+		const CONTAINS_EXPLICIT_MEDIA = 1 << 4;
+		/// The attachment is an animated image
+		const IS_ANIMATED = 1 << 5;
+		/// The attachment was flagged as gore
+		const CONTAINS_GORE_CONTENT = 1 << 6;
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reaction {
+	/// Total number of times this emoji has been used to react (including super reacts)
+	pub count: u32,
+	/// The reaction count split by type
+	#[serde(default)]
+	pub count_details: ReactionCountDetails,
+	/// Whether the current user reacted using this emoji
+	#[serde(default)]
+	pub me: bool,
+	/// Whether the current user super-reacted using this emoji
+	#[serde(default)]
+	pub me_burst: bool,
+	/// The emoji, only `id`, `name` and `animated` are set
+	pub emoji: Emoji,
+	/// HEX colors used for the super reaction
+	#[serde(default)]
+	pub burst_colors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct ReactionCountDetails {
+	/// Count of super reactions
+	pub burst: u32,
+	/// Count of normal reactions
+	pub normal: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum ReactionType {
+	#[default]
+	Normal = 0,
+	/// A super reaction, which plays an animation and needs nitro
+	Burst = 1,
+}
+
+/// The number the reaction type is sent as, which is also how it goes in paths
+impl Display for ReactionType {
+	fn fmt(
+		&self,
+		f: &mut fmt::Formatter<'_>,
+	) -> fmt::Result {
+		write!(f, "{}", *self as u8)
+	}
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Embed {
+	/// The title of the embed (max 256 characters)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub title: Option<String>,
+	/// The type of embed (always rich for bot and user embeds)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub r#type: Option<String>,
+	/// The description of the embed (max 4096 characters)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub description: Option<String>,
+	/// The URL of the embed
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub url: Option<String>,
+	/// The timestamp of the embed content
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub timestamp: Option<String>,
+	/// The color code of the embed
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub color: Option<u32>,
+	/// Footer information
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub footer: Option<EmbedFooter>,
+	/// Image information
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub image: Option<EmbedMedia>,
+	/// Thumbnail information
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub thumbnail: Option<EmbedMedia>,
+	/// Video information
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub video: Option<EmbedMedia>,
+	/// Provider information
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub provider: Option<EmbedProvider>,
+	/// Author information
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub author: Option<EmbedAuthor>,
+	/// Fields information (max 25)
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub fields: Vec<EmbedField>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbedFooter {
+	/// Footer text (max 2048 characters)
+	pub text: String,
+	/// URL of footer icon (only supports HTTP(S) and attachments)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub icon_url: Option<String>,
+	/// A proxied URL of the footer icon
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub proxy_icon_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbedMedia {
+	/// Source URL of the media (only supports HTTP(S) and attachments)
+	pub url: String,
+	/// A proxied URL of the media
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub proxy_url: Option<String>,
+	/// Height of the media
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub height: Option<u32>,
+	/// Width of the media
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub width: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbedProvider {
+	/// Name of the provider
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub name: Option<String>,
+	/// URL of the provider
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbedAuthor {
+	/// Name of the author (max 256 characters)
+	pub name: String,
+	/// URL of the author (only supports HTTP(S))
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub url: Option<String>,
+	/// URL of the author icon (only supports HTTP(S) and attachments)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub icon_url: Option<String>,
+	/// A proxied URL of the author icon
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub proxy_icon_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbedField {
+	/// Name of the field (max 256 characters)
+	pub name: String,
+	/// Value of the field (max 1024 characters)
+	pub value: String,
+	/// Whether or not this field should display inline (default false)
+	#[serde(default)]
+	pub inline: bool,
+}
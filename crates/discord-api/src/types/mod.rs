@@ -1,5 +1,16 @@
+pub mod activity;
+pub mod channel;
+pub mod emoji;
 pub mod error;
+pub mod guild;
 pub mod locale;
+pub mod message;
+pub mod permissions;
+pub mod read_state;
+pub mod rollout;
+pub mod settings;
+pub mod snowflake;
 pub mod super_properties;
 pub mod token;
+pub mod user;
 pub mod ws;
@@ -0,0 +1,258 @@
+use cowcord_macros::bitflags;
+
+use crate::types::channel::{Channel, PermissionOverwriteType};
+use crate::types::guild::{Guild, GuildMember};
+use crate::types::snowflake::Snowflake;
+
+bitflags! {
+	/// A set of permissions
+	///
+	/// Sent and recieved as a stringified integer in json, since there are more than 32 of them
+	#[flags(string)]
+	pub struct Permissions: u64 {
+		/// Allows creation of instant invites
+		const CREATE_INSTANT_INVITE = 1 << 0;
+		/// Allows kicking members
+		const KICK_MEMBERS = 1 << 1;
+		/// Allows banning members
+		const BAN_MEMBERS = 1 << 2;
+		/// Allows all permissions and bypasses channel permission overwrites
+		const ADMINISTRATOR = 1 << 3;
+		/// Allows management and editing of channels
+		const MANAGE_CHANNELS = 1 << 4;
+		/// Allows management and editing of the guild
+		const MANAGE_GUILD = 1 << 5;
+		/// Allows for adding new reactions to messages, existing reactions can still be added to without it
+		const ADD_REACTIONS = 1 << 6;
+		/// Allows for viewing of audit logs
+		const VIEW_AUDIT_LOG = 1 << 7;
+		/// Allows for using priority speaker in a voice channel
+		const PRIORITY_SPEAKER = 1 << 8;
+		/// Allows the user to go live
+		const STREAM = 1 << 9;
+		/// Allows guild members to view a channel, which includes reading messages in text channels and joining voice channels
+		const VIEW_CHANNEL = 1 << 10;
+		/// Allows for sending messages in a channel and creating threads in a forum
+		const SEND_MESSAGES = 1 << 11;
+		/// Allows for sending of `/tts` messages
+		const SEND_TTS_MESSAGES = 1 << 12;
+		/// Allows for deletion of other users messages
+		const MANAGE_MESSAGES = 1 << 13;
+		/// Links sent by users with this permission will be auto-embedded
+		const EMBED_LINKS = 1 << 14;
+		/// Allows for uploading images and files
+		const ATTACH_FILES = 1 << 15;
+		/// Allows for reading of message history
+		const READ_MESSAGE_HISTORY = 1 << 16;
+		/// Allows for using the `@everyone` and `@here` tags, and mentioning all roles
+		const MENTION_EVERYONE = 1 << 17;
+		/// Allows the usage of custom emojis from other guilds
+		const USE_EXTERNAL_EMOJIS = 1 << 18;
+		/// Allows for viewing guild insights
+		const VIEW_GUILD_INSIGHTS = 1 << 19;
+		/// Allows for joining of a voice channel
+		const CONNECT = 1 << 20;
+		/// Allows for speaking in a voice channel
+		const SPEAK = 1 << 21;
+		/// Allows for muting members in a voice channel
+		const MUTE_MEMBERS = 1 << 22;
+		/// Allows for deafening of members in a voice channel
+		const DEAFEN_MEMBERS = 1 << 23;
+		/// Allows for moving of members between voice channels
+		const MOVE_MEMBERS = 1 << 24;
+		/// Allows for using voice activity detection in a voice channel
+		const USE_VAD = 1 << 25;
+		/// Allows for modification of own nickname
+		const CHANGE_NICKNAME = 1 << 26;
+		/// Allows for modification of other users nicknames
+		const MANAGE_NICKNAMES = 1 << 27;
+		/// Allows management and editing of roles
+		const MANAGE_ROLES = 1 << 28;
+		/// Allows management and editing of webhooks
+		const MANAGE_WEBHOOKS = 1 << 29;
+		/// Allows for editing and deleting emojis, stickers and soundboard sounds created by all users
+		const MANAGE_GUILD_EXPRESSIONS = 1 << 30;
+		/// Allows members to use application commands
+		const USE_APPLICATION_COMMANDS = 1 << 31;
+		/// Allows for requesting to speak in stage channels
+		const REQUEST_TO_SPEAK = 1 << 32;
+		/// Allows for editing and deleting scheduled events created by all users
+		const MANAGE_EVENTS = 1 << 33;
+		/// Allows for deleting and archiving threads, and viewing all private threads
+		const MANAGE_THREADS = 1 << 34;
+		/// Allows for creating public and announcement threads
+		const CREATE_PUBLIC_THREADS = 1 << 35;
+		/// Allows for creating private threads
+		const CREATE_PRIVATE_THREADS = 1 << 36;
+		/// Allows the usage of custom stickers from other guilds
+		const USE_EXTERNAL_STICKERS = 1 << 37;
+		/// Allows for sending messages in threads
+		const SEND_MESSAGES_IN_THREADS = 1 << 38;
+		/// Allows for using activities in a voice channel
+		const USE_EMBEDDED_ACTIVITIES = 1 << 39;
+		/// Allows for timing out users
+		const MODERATE_MEMBERS = 1 << 40;
+		/// Allows for viewing role subscription insights
+		const VIEW_CREATOR_MONETIZATION_ANALYTICS = 1 << 41;
+		/// Allows for using the soundboard in a voice channel
+		const USE_SOUNDBOARD = 1 << 42;
+		/// Allows for creating emojis, stickers and soundboard sounds, and editing and deleting ones created by the user
+		const CREATE_GUILD_EXPRESSIONS = 1 << 43;
+		/// Allows for creating scheduled events, and editing and deleting ones created by the user
+		const CREATE_EVENTS = 1 << 44;
+		/// Allows the usage of custom soundboard sounds from other guilds
+		const USE_EXTERNAL_SOUNDS = 1 << 45;
+		/// Allows sending voice messages
+		const SEND_VOICE_MESSAGES = 1 << 46;
+		/// Allows setting the status of a voice channel
+		const SET_VOICE_CHANNEL_STATUS = 1 << 48;
+		/// Allows sending polls
+		const SEND_POLLS = 1 << 49;
+		/// Allows user-installed apps to send public responses
+		const USE_EXTERNAL_APPS = 1 << 50;
+		/// Allows pinning and unpinning messages
+		const PIN_MESSAGES = 1 << 51;
+		/// Allows bypassing slowmode restrictions
+		const BYPASS_SLOWMODE = 1 << 52;
+	}
+}
+
+impl Permissions {
+	/// No permissions
+	pub const NONE: Self = Self(0);
+	/// Every permission, including ones that don't exist yet
+	pub const ALL: Self = Self(u64::MAX);
+
+	/// What members that are timed out keep
+	const TIMED_OUT: Self = Self(Self::VIEW_CHANNEL.0 | Self::READ_MESSAGE_HISTORY.0);
+	/// Lost without [`Self::SEND_MESSAGES`], or [`Self::SEND_MESSAGES_IN_THREADS`] in threads
+	const SEND_DEPENDENT: Self = Self(
+		Self::SEND_TTS_MESSAGES.0
+			| Self::MENTION_EVERYONE.0
+			| Self::EMBED_LINKS.0
+			| Self::ATTACH_FILES.0
+			| Self::SEND_VOICE_MESSAGES.0
+			| Self::SEND_POLLS.0,
+	);
+	/// Lost without [`Self::CONNECT`] in voice channels
+	const CONNECT_DEPENDENT: Self = Self(
+		Self::SPEAK.0
+			| Self::MUTE_MEMBERS.0
+			| Self::DEAFEN_MEMBERS.0
+			| Self::MOVE_MEMBERS.0
+			| Self::USE_VAD.0
+			| Self::PRIORITY_SPEAKER.0
+			| Self::STREAM.0
+			| Self::USE_EMBEDDED_ACTIVITIES.0
+			| Self::USE_SOUNDBOARD.0
+			| Self::USE_EXTERNAL_SOUNDS.0
+			| Self::REQUEST_TO_SPEAK.0
+			| Self::SET_VOICE_CHANNEL_STATUS.0,
+	);
+
+	/// Applies an overwrite, denied permissions are removed before allowed ones are added
+	pub const fn overwrite(
+		self,
+		allow: Self,
+		deny: Self,
+	) -> Self {
+		Self(self.0 & !deny.0 | allow.0)
+	}
+}
+
+/// The member's permissions in the guild, from the `@everyone` role and their own roles
+///
+/// Owners and administrators have every permission
+pub fn guild_permissions(
+	guild: &Guild,
+	member: &GuildMember,
+	user_id: Snowflake,
+) -> Permissions {
+	if guild.owner_id == user_id {
+		return Permissions::ALL;
+	}
+
+	// the @everyone role has the same id as the guild
+	let permissions = guild
+		.roles
+		.iter()
+		.filter(|r| r.id == guild.id || member.roles.contains(&r.id))
+		.fold(Permissions::NONE, |acc, r| acc | r.permissions);
+
+	if permissions.contains(Permissions::ADMINISTRATOR) {
+		Permissions::ALL
+	} else {
+		permissions
+	}
+}
+
+/// The member's permissions in the channel, applying its overwrites on top of [`guild_permissions`]
+///
+/// `now` is in unix milliseconds, and decides whether the member's timeout is over. Threads don't have overwrites of their own, `parent` is the channel they're in, whose overwrites are used instead
+pub fn channel_permissions(
+	guild: &Guild,
+	channel: &Channel,
+	parent: Option<&Channel>,
+	member: &GuildMember,
+	user_id: Snowflake,
+	now: u64,
+) -> Permissions {
+	let mut permissions = guild_permissions(guild, member, user_id);
+	if permissions == Permissions::ALL {
+		return permissions;
+	}
+
+	let overwritten = if channel.r#type.is_thread() {
+		parent.unwrap_or(channel)
+	} else {
+		channel
+	};
+	let overwrites = overwritten
+		.permission_overwrites
+		.as_deref()
+		.unwrap_or_default();
+
+	// @everyone first, then every role's overwrite together, then the member's own
+	if let Some(everyone) = overwrites.iter().find(|o| o.id == guild.id) {
+		permissions = permissions.overwrite(everyone.allow, everyone.deny);
+	}
+
+	let (allow, deny) = overwrites
+		.iter()
+		.filter(|o| o.r#type == PermissionOverwriteType::Role && member.roles.contains(&o.id))
+		.fold(
+			(Permissions::NONE, Permissions::NONE),
+			|(allow, deny), o| (allow | o.allow, deny | o.deny),
+		);
+	permissions = permissions.overwrite(allow, deny);
+
+	if let Some(own) = overwrites
+		.iter()
+		.find(|o| o.r#type == PermissionOverwriteType::Member && o.id == user_id)
+	{
+		permissions = permissions.overwrite(own.allow, own.deny);
+	}
+
+	if member.timed_out_until().is_some_and(|until| until > now) {
+		permissions &= Permissions::TIMED_OUT;
+	}
+
+	// permissions that depend on another are lost along with it
+	if !permissions.contains(Permissions::VIEW_CHANNEL) {
+		return Permissions::NONE;
+	}
+	// threads have a send permission of their own
+	let send = if channel.r#type.is_thread() {
+		Permissions::SEND_MESSAGES_IN_THREADS
+	} else {
+		Permissions::SEND_MESSAGES
+	};
+	if !permissions.contains(send) {
+		permissions.remove(Permissions::SEND_DEPENDENT);
+	}
+	if channel.r#type.is_voice() && !permissions.contains(Permissions::CONNECT) {
+		permissions.remove(Permissions::CONNECT_DEPENDENT);
+	}
+
+	permissions
+}
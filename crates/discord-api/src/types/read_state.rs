@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::snowflake::Snowflake;
+
+/// How far the user has read in a channel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReadState {
+	/// The ID of the channel
+	pub id: Snowflake,
+	/// The ID of the last read message (may be `0` or not point to an existing message)
+	#[serde(default)]
+	pub last_message_id: Option<Snowflake>,
+	/// The number of unread messages that mention the user
+	#[serde(default)]
+	pub mention_count: u32,
+	/// When the last pinned message the user saw was pinned
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub last_pin_timestamp: Option<String>,
+}
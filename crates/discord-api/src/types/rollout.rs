@@ -0,0 +1,308 @@
+use crate::endpoints::experiments::{
+	ExperimentPopulation,
+	ExperimentPopulationFilters,
+	ExperimentPopulationRangeFilter,
+	GuildExperiment,
+	UserExperiment,
+};
+use crate::types::guild::Guild;
+use crate::types::snowflake::Snowflake;
+
+/// Rollout positions are in `0..ROLLOUT_POSITIONS`, so each position is 0.01% of guilds
+pub const ROLLOUT_POSITIONS: u32 = 10000;
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// 32-bit murmur3 with a seed of `0`, used for experiment names and rollout positions
+pub fn murmur3(data: &[u8]) -> u32 {
+	const C1: u32 = 0xcc9e2d51;
+	const C2: u32 = 0x1b873593;
+
+	let scramble = |k: u32| k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+
+	let mut hash: u32 = 0;
+	let mut chunks = data.chunks_exact(4);
+	for chunk in &mut chunks {
+		let k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+		hash = (hash ^ scramble(k))
+			.rotate_left(13)
+			.wrapping_mul(5)
+			.wrapping_add(0xe6546b64);
+	}
+
+	let remainder = chunks.remainder();
+	if !remainder.is_empty() {
+		let k = remainder
+			.iter()
+			.rev()
+			.fold(0, |k, b| (k << 8) | u32::from(*b));
+		hash ^= scramble(k);
+	}
+
+	hash ^= data.len() as u32;
+	hash ^= hash >> 16;
+	hash = hash.wrapping_mul(0x85ebca6b);
+	hash ^= hash >> 13;
+	hash = hash.wrapping_mul(0xc2b2ae35);
+	hash ^ (hash >> 16)
+}
+
+/// The hash experiments are identified by
+pub fn experiment_hash(name: &str) -> i64 {
+	i64::from(murmur3(name.as_bytes()))
+}
+
+/// Where the guild is in the rollout of the experiment, `key` is the experiment's hash key or name
+pub fn rollout_position(
+	key: &str,
+	guild_id: Snowflake,
+) -> u32 {
+	murmur3(format!("{key}:{guild_id}").as_bytes()) % ROLLOUT_POSITIONS
+}
+
+/// What population filters check about a guild
+#[derive(Debug, Clone, Copy)]
+pub struct RolloutGuild<'a> {
+	pub id: Snowflake,
+	pub features: &'a [String],
+	pub member_count: Option<u64>,
+	pub hub_type: Option<i64>,
+	pub has_vanity_url: bool,
+}
+
+impl<'a> From<&'a Guild> for RolloutGuild<'a> {
+	fn from(guild: &'a Guild) -> Self {
+		RolloutGuild {
+			id: guild.id,
+			features: &guild.features,
+			member_count: guild.member_count,
+			hub_type: guild.hub_type,
+			has_vanity_url: guild.vanity_url_code.is_some(),
+		}
+	}
+}
+
+/// A guild's bucket in a guild experiment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuildAssignment {
+	/// The bucket the guild is in, `0` is the control bucket
+	pub bucket: i64,
+	/// The revision of the rollout the bucket is from
+	pub revision: i64,
+	/// The guild's rollout position
+	pub position: u32,
+	/// Whether the experiment is an A/A test
+	pub aa_mode: bool,
+}
+
+impl GuildAssignment {
+	/// The bucket to act on, A/A tests always behave like the control bucket
+	pub fn effective_bucket(&self) -> i64 {
+		if self.aa_mode { 0 } else { self.bucket }
+	}
+}
+
+impl UserExperiment {
+	/// The bucket to act on, A/A tests always behave like the control bucket
+	pub fn effective_bucket(&self) -> i64 {
+		if self.aa_mode != 0 { 0 } else { self.bucket }
+	}
+}
+
+impl ExperimentPopulationRangeFilter {
+	/// Whether the value is within the range, both ends are inclusive
+	pub fn contains(
+		&self,
+		value: u64,
+	) -> bool {
+		self.min_id.is_none_or(|min| value >= min) && self.max_id.is_none_or(|max| value <= max)
+	}
+}
+
+impl ExperimentPopulationFilters {
+	/// Whether the guild passes every filter
+	///
+	/// `now` is in unix milliseconds, for the guild's age
+	pub fn matches(
+		&self,
+		guild: &RolloutGuild,
+		now: u64,
+	) -> bool {
+		if let Some(filter) = &self.guild_has_feature
+			&& !filter
+				.guild_features
+				.iter()
+				.any(|f| guild.features.contains(f))
+		{
+			return false;
+		}
+		if let Some(range) = &self.guild_id_range
+			&& !range.contains(guild.id.0)
+		{
+			return false;
+		}
+		if let Some(range) = &self.guild_age_range_days {
+			let age_days = now.saturating_sub(guild.id.timestamp()) / DAY_MS;
+			if !range.contains(age_days) {
+				return false;
+			}
+		}
+		if let Some(range) = &self.guild_member_count_range
+			&& !guild
+				.member_count
+				.is_some_and(|count| range.contains(count))
+		{
+			return false;
+		}
+		if let Some(filter) = &self.guild_ids
+			&& !filter.guild_ids.contains(&guild.id)
+		{
+			return false;
+		}
+		if let Some(filter) = &self.guild_hub_types
+			&& !guild
+				.hub_type
+				.is_some_and(|t| filter.guild_hub_types.contains(&t))
+		{
+			return false;
+		}
+		if let Some(filter) = &self.guild_has_vanity_url
+			&& filter.guild_has_vanity_url != guild.has_vanity_url
+		{
+			return false;
+		}
+		if let Some(filter) = &self.guild_in_range_by_hash {
+			let position = rollout_position(&filter.hash_key.to_string(), guild.id);
+			if i64::from(position) >= filter.target {
+				return false;
+			}
+		}
+
+		true
+	}
+}
+
+impl ExperimentPopulation {
+	/// The bucket whose range has the position, if the guild passes the filters
+	pub fn bucket(
+		&self,
+		guild: &RolloutGuild,
+		position: u32,
+		now: u64,
+	) -> Option<i64> {
+		if !self.filters.matches(guild, now) {
+			return None;
+		}
+
+		let position = i64::from(position);
+		self.ranges
+			.iter()
+			.find(|range| {
+				range
+					.rollout
+					.iter()
+					.any(|r| r.s <= position && position < r.e)
+			})
+			.map(|range| range.bucket)
+	}
+}
+
+/// The first population that has a bucket for the guild, populations that don't are skipped
+fn populations_bucket(
+	populations: &[ExperimentPopulation],
+	guild: &RolloutGuild,
+	position: u32,
+	now: u64,
+) -> Option<i64> {
+	populations
+		.iter()
+		.find_map(|p| p.bucket(guild, position, now))
+}
+
+impl GuildExperiment {
+	/// The guild's bucket, `None` if the guild isn't in the experiment
+	///
+	/// Overrides come first, then the holdout experiment can exclude the guild, then the override populations and the normal populations are checked in order.
+	/// `name` is the experiment's name, only used when it has no hash key, and `experiments` are all of the guild experiments, for looking up the holdout
+	pub fn assign(
+		&self,
+		name: &str,
+		experiments: &[GuildExperiment],
+		guild: &RolloutGuild,
+		now: u64,
+	) -> Option<GuildAssignment> {
+		let position = rollout_position(self.hash_key.as_deref().unwrap_or(name), guild.id);
+		let assignment = |bucket| GuildAssignment {
+			bucket,
+			revision: self.revision,
+			position,
+			aa_mode: self.aa_mode != 0,
+		};
+
+		if let Some(r#override) = self.overrides.iter().find(|o| o.k.contains(&guild.id)) {
+			return Some(assignment(r#override.b));
+		}
+
+		if let (Some(holdout_name), Some(holdout_bucket)) =
+			(&self.holdout_name, self.holdout_bucket)
+			&& !self.holdout_cycle(experiments)
+		{
+			let holdout = guild_assignment(experiments, holdout_name, guild, now);
+			if holdout.is_some_and(|h| h.bucket == holdout_bucket) {
+				return None;
+			}
+		}
+
+		self.overrides_formatted
+			.iter()
+			.find_map(|populations| populations_bucket(populations, guild, position, now))
+			.or_else(|| populations_bucket(&self.populations, guild, position, now))
+			.map(assignment)
+	}
+
+	/// Whether following the holdouts leads back to an experiment already seen, which then can't hold anything out
+	fn holdout_cycle(
+		&self,
+		experiments: &[GuildExperiment],
+	) -> bool {
+		let mut seen = vec![self.hash];
+		let mut current = self;
+		while let Some(name) = &current.holdout_name {
+			let hash = experiment_hash(name);
+			if seen.contains(&hash) {
+				return true;
+			}
+			let Some(holdout) = experiments.iter().find(|e| e.hash == hash) else {
+				return false;
+			};
+			seen.push(hash);
+			current = holdout;
+		}
+		false
+	}
+}
+
+/// The guild's bucket in the experiment with the name, `None` if the experiment doesn't exist or the guild isn't in it
+///
+/// `now` is in unix milliseconds, for the guild's age
+pub fn guild_assignment(
+	experiments: &[GuildExperiment],
+	name: &str,
+	guild: &RolloutGuild,
+	now: u64,
+) -> Option<GuildAssignment> {
+	let hash = experiment_hash(name);
+	experiments
+		.iter()
+		.find(|e| e.hash == hash)?
+		.assign(name, experiments, guild, now)
+}
+
+/// The user's assignment in the experiment with the name, assigned by discord
+pub fn user_assignment<'a>(
+	assignments: &'a [UserExperiment],
+	name: &str,
+) -> Option<&'a UserExperiment> {
+	let hash = experiment_hash(name);
+	assignments.iter().find(|a| a.hash == hash)
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::snowflake::Snowflake;
+
+/// The legacy json user settings, sent in `READY` and `USER_SETTINGS_UPDATE`
+///
+/// Updates only include the settings that changed, so every field is optional
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserSettings {
+	/// The folders the user sorted their guilds into, in the order shown in the guild list
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub guild_folders: Option<Vec<GuildFolder>>,
+	/// The guild IDs in the order shown in the guild list, superseded by `guild_folders`
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub guild_positions: Option<Vec<Snowflake>>,
+}
+
+/// A folder in the guild list
+///
+/// Guilds that aren't in a folder are still sent as a folder with no ID that contains only them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuildFolder {
+	/// The ID of the folder
+	#[serde(default)]
+	pub id: Option<u64>,
+	/// The name of the folder
+	#[serde(default)]
+	pub name: Option<String>,
+	/// Integer representation of the folder's hexadecimal color code
+	#[serde(default)]
+	pub color: Option<u32>,
+	/// The IDs of the guilds in the folder
+	pub guild_ids: Vec<Snowflake>,
+}
+
+impl GuildFolder {
+	/// whether this is an actual folder and not a single guild outside of one
+	pub fn is_folder(&self) -> bool {
+		self.id.is_some()
+	}
+}
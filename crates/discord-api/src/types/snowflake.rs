@@ -0,0 +1,109 @@
+use std::fmt::{self, Display};
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The first second of 2015, in unix milliseconds
+pub const DISCORD_EPOCH: u64 = 1420070400000;
+
+/// Discord's unique ID format
+///
+/// Sent and recieved as a string in json, since most clients cant handle 64-bit integers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Snowflake(pub u64);
+
+impl Snowflake {
+	/// When the snowflake was created, in unix milliseconds
+	pub fn timestamp(&self) -> u64 {
+		(self.0 >> 22) + DISCORD_EPOCH
+	}
+
+	/// The latest timestamp (in unix milliseconds) a snowflake can hold, in 2154
+	pub const MAX_TIMESTAMP: u64 = (u64::MAX >> 22) + DISCORD_EPOCH;
+
+	/// The smallest snowflake that could be created at `timestamp` (in unix milliseconds)
+	///
+	/// Useful as a `before`/`after` pagination cursor for a point in time, timestamps past [`Self::MAX_TIMESTAMP`] wrap around
+	pub fn from_timestamp(timestamp: u64) -> Self {
+		Snowflake(timestamp.saturating_sub(DISCORD_EPOCH) << 22)
+	}
+}
+
+impl Display for Snowflake {
+	fn fmt(
+		&self,
+		f: &mut fmt::Formatter<'_>,
+	) -> fmt::Result {
+		write!(f, "{}", self.0)
+	}
+}
+
+impl FromStr for Snowflake {
+	type Err = ParseIntError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		s.parse().map(Snowflake)
+	}
+}
+
+impl From<u64> for Snowflake {
+	fn from(value: u64) -> Self {
+		Snowflake(value)
+	}
+}
+
+impl Serialize for Snowflake {
+	fn serialize<S>(
+		&self,
+		serializer: S,
+	) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(&self.0.to_string())
+	}
+}
+
+impl<'de> Deserialize<'de> for Snowflake {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		struct SnowflakeVisitor;
+
+		impl<'de> Visitor<'de> for SnowflakeVisitor {
+			type Value = Snowflake;
+
+			fn expecting(
+				&self,
+				formatter: &mut fmt::Formatter,
+			) -> fmt::Result {
+				formatter.write_str("a snowflake as a string or integer")
+			}
+
+			fn visit_u64<E>(
+				self,
+				value: u64,
+			) -> Result<Self::Value, E>
+			where
+				E: de::Error,
+			{
+				Ok(Snowflake(value))
+			}
+
+			fn visit_str<E>(
+				self,
+				value: &str,
+			) -> Result<Self::Value, E>
+			where
+				E: de::Error,
+			{
+				value.parse().map_err(E::custom)
+			}
+		}
+
+		deserializer.deserialize_any(SnowflakeVisitor)
+	}
+}
@@ -0,0 +1,145 @@
+use cowcord_macros::bitflags;
+use serde::{Deserialize, Serialize};
+
+use crate::endpoints::cdn::{DEFAULT_USER_AVATAR, USER_AVATAR};
+use crate::types::snowflake::Snowflake;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct User {
+	/// The ID of the user
+	pub id: Snowflake,
+	/// The user's username, may be unique across the platform (2-32 characters)
+	pub username: String,
+	/// The user's stringified 4-digit Discord tag
+	pub discriminator: String,
+	/// The user's display name (1-32 characters)
+	#[serde(default)]
+	pub global_name: Option<String>,
+	/// The user's avatar hash
+	#[serde(default)]
+	pub avatar: Option<String>,
+	/// Whether the user is a bot account
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub bot: Option<bool>,
+	/// Whether the user is an official Discord System user (part of the urgent message system)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub system: Option<bool>,
+	/// The user's banner hash
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub banner: Option<Option<String>>,
+	/// The user's banner color encoded as an integer representation of a hexadecimal color code
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub accent_color: Option<Option<u32>>,
+	/// The user's public flags
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub public_flags: Option<UserFlags>,
+	/// The user's email address (requires the `email` scope)
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub email: Option<Option<String>>,
+	/// Whether the user has two factor enabled on their account
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub mfa_enabled: Option<bool>,
+	/// The language option chosen by the user
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub locale: Option<String>,
+	/// The type of premium (Nitro) subscription on a user's account
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub premium_type: Option<u8>,
+}
+
+bitflags! {
+	/// Badges and account state of a user, only some of them are public
+	pub struct UserFlags: u64 {
+		/// Discord employee
+		const STAFF = 1 << 0;
+		/// Partnered server owner
+		const PARTNER = 1 << 1;
+		/// HypeSquad events member
+		const HYPESQUAD = 1 << 2;
+		/// Level 1 bug hunter
+		const BUG_HUNTER_LEVEL_1 = 1 << 3;
+		/// SMS is enabled as a multi-factor authentication backup
+		const MFA_SMS = 1 << 4;
+		/// The user dismissed the Nitro promotion
+		const PREMIUM_PROMO_DISMISSED = 1 << 5;
+		/// House Bravery member
+		const HYPESQUAD_ONLINE_HOUSE_1 = 1 << 6;
+		/// House Brilliance member
+		const HYPESQUAD_ONLINE_HOUSE_2 = 1 << 7;
+		/// House Balance member
+		const HYPESQUAD_ONLINE_HOUSE_3 = 1 << 8;
+		/// Early Nitro supporter
+		const PREMIUM_EARLY_SUPPORTER = 1 << 9;
+		/// The user is a team
+		const TEAM_PSEUDO_USER = 1 << 10;
+		/// The user is an internal application
+		const INTERNAL_APPLICATION = 1 << 11;
+		/// The user is the official Discord System account
+		const SYSTEM = 1 << 12;
+		/// The user has unread urgent messages from Discord
+		const HAS_UNREAD_URGENT_MESSAGES = 1 << 13;
+		/// Level 2 bug hunter
+		const BUG_HUNTER_LEVEL_2 = 1 << 14;
+		/// The user is scheduled for deletion for being under 13
+		const UNDERAGE_DELETED = 1 << 15;
+		/// Verified bot
+		const VERIFIED_BOT = 1 << 16;
+		/// Early verified bot developer
+		const VERIFIED_DEVELOPER = 1 << 17;
+		/// Moderator programs alumni
+		const CERTIFIED_MODERATOR = 1 << 18;
+		/// The bot only uses HTTP interactions and is shown in the online member list
+		const BOT_HTTP_INTERACTIONS = 1 << 19;
+		/// The user is marked as a spammer
+		const SPAMMER = 1 << 20;
+		/// Nitro features are disabled for the user
+		const DISABLE_PREMIUM = 1 << 21;
+		/// Active developer
+		const ACTIVE_DEVELOPER = 1 << 22;
+		/// The account was made through a game's social layer integration
+		const PROVISIONAL_ACCOUNT = 1 << 23;
+		/// The user has a higher global rate limit
+		const HIGH_GLOBAL_RATE_LIMIT = 1 << 33;
+		/// The account is deleted
+		const DELETED = 1 << 34;
+		/// The account is disabled for suspicious activity
+		const DISABLED_SUSPICIOUS_ACTIVITY = 1 << 35;
+		/// The user deleted their account
+		const SELF_DELETED = 1 << 36;
+		/// The user has a custom discriminator
+		const PREMIUM_DISCRIMINATOR = 1 << 37;
+		/// The user has used the desktop client
+		const USED_DESKTOP_CLIENT = 1 << 38;
+		/// The user has used the web client
+		const USED_WEB_CLIENT = 1 << 39;
+		/// The user has used the mobile client
+		const USED_MOBILE_CLIENT = 1 << 40;
+		/// The account is disabled
+		const DISABLED = 1 << 41;
+		/// The user has started at least one gateway session
+		const HAS_SESSION_STARTED = 1 << 43;
+		/// The account is quarantined
+		const QUARANTINED = 1 << 44;
+		/// The user can pick a unique username early
+		const PREMIUM_ELIGIBLE_FOR_UNIQUE_USERNAME = 1 << 47;
+		/// Discord collaborator
+		const COLLABORATOR = 1 << 50;
+		/// Restricted Discord collaborator
+		const RESTRICTED_COLLABORATOR = 1 << 51;
+	}
+}
+
+impl User {
+	/// the name shown in the client, the global name if set otherwise the username
+	pub fn display_name(&self) -> &str {
+		self.global_name.as_deref().unwrap_or(&self.username)
+	}
+
+	/// the cdn endpoint of the user's avatar, falls back to the default avatar if they have none
+	pub fn avatar_endpoint(&self) -> String {
+		match &self.avatar {
+			| Some(hash) => USER_AVATAR(&self.id.to_string(), hash),
+			| None => DEFAULT_USER_AVATAR((self.id.0 >> 22) % 6),
+		}
+	}
+}
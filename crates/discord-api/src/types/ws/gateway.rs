@@ -0,0 +1,416 @@
+use cowcord_macros::bitflags;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+use crate::endpoints::experiments::{GuildExperiment, UserExperiment};
+use crate::types::channel::Channel;
+use crate::types::emoji::Emoji;
+use crate::types::guild::{Guild, GuildMember, Role, VoiceState};
+use crate::types::message::Message;
+use crate::types::read_state::ReadState;
+use crate::types::settings::UserSettings;
+use crate::types::snowflake::Snowflake;
+use crate::types::super_properties::ClientProperties;
+use crate::types::user::User;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum GatewayOpCode {
+	/// An event was dispatched
+	Dispatch = 0,
+	/// Fired periodically by the client to keep the connection alive
+	Heartbeat = 1,
+	/// Starts a new session during the initial handshake
+	Identify = 2,
+	/// Update the client's presence
+	PresenceUpdate = 3,
+	/// Used to join/leave or move between voice channels
+	VoiceStateUpdate = 4,
+	/// Resume a previous session that was disconnected
+	Resume = 6,
+	/// You should attempt to reconnect and resume immediately
+	Reconnect = 7,
+	/// Request information about offline guild members in a large guild
+	RequestGuildMembers = 8,
+	/// The session has been invalidated, you should reconnect and identify/resume accordingly
+	InvalidSession = 9,
+	/// Sent immediately after connecting, contains the `heartbeat_interval` to use
+	Hello = 10,
+	/// Sent in response to receiving a heartbeat to acknowledge that it has been received
+	HeartbeatAck = 11,
+}
+
+/// A payload sent by the gateway, `d` is decoded depending on the opcode
+#[derive(Debug, Clone, Deserialize)]
+pub struct GatewayReceivePayload {
+	/// The gateway opcode
+	pub op: GatewayOpCode,
+	/// The event data
+	#[serde(default)]
+	pub d: Value,
+	/// The sequence number of the event, used for resuming sessions and heartbeating
+	#[serde(default)]
+	pub s: Option<u64>,
+	/// The event name, only set for dispatches
+	#[serde(default)]
+	pub t: Option<String>,
+}
+
+/// A payload sent to the gateway
+#[derive(Debug, Clone, Serialize)]
+pub struct GatewaySendPayload<T> {
+	/// The gateway opcode
+	pub op: GatewayOpCode,
+	/// The event data
+	pub d: T,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Hello {
+	/// The minimum interval (in milliseconds) the client should heartbeat at
+	pub heartbeat_interval: u64,
+}
+
+#[derive(Serialize)]
+pub struct Identify {
+	/// The authentication token
+	pub token: String,
+	/// The gateway capabilities, none gets every payload in its original, non-deduplicated format
+	pub capabilities: GatewayCapabilities,
+	/// Connection properties, the same as the `X-Super-Properties` header
+	pub properties: ClientProperties,
+	/// Whether the gateway may compress payloads
+	pub compress: bool,
+}
+
+bitflags! {
+	/// Changes to the gateway's payloads the client supports, sent when identifying
+	pub struct GatewayCapabilities: u64 {
+		/// User notes aren't sent in `READY`
+		const LAZY_USER_NOTES = 1 << 0;
+		/// Affine user IDs aren't sent in `READY`
+		const NO_AFFINE_USER_IDS = 1 << 1;
+		/// Read states are versioned and sent as a partial update when unchanged
+		const VERSIONED_READ_STATES = 1 << 2;
+		/// User guild settings are versioned and sent as a partial update when unchanged
+		const VERSIONED_USER_GUILD_SETTINGS = 1 << 3;
+		/// Users are sent once in `READY` and referenced by ID
+		const DEDUPE_USER_OBJECTS = 1 << 4;
+		/// `READY` is split in two, with `READY_SUPPLEMENTAL` after it
+		const PRIORITIZED_READY_PAYLOAD = 1 << 5;
+		/// Guild experiments can have more than one population
+		const MULTIPLE_GUILD_EXPERIMENT_POPULATIONS = 1 << 6;
+		/// Read states for things other than channels are sent
+		const NON_CHANNEL_READ_STATES = 1 << 7;
+		/// The gateway can send a new token
+		const AUTH_TOKEN_REFRESH = 1 << 8;
+		/// User settings are sent as protobuf
+		const USER_SETTINGS_PROTO = 1 << 9;
+		/// The cached client state is sent when identifying
+		const CLIENT_STATE_V2 = 1 << 10;
+		/// Guilds the user isn't looking at get `PASSIVE_UPDATE_V1` instead of full events
+		const PASSIVE_GUILD_UPDATE = 1 << 11;
+		/// Calls the user is in are connected to automatically
+		const AUTO_CALL_CONNECT = 1 << 12;
+		/// Reaction events are debounced and batched
+		const DEBOUNCE_MESSAGE_REACTIONS = 1 << 13;
+		/// Guilds the user isn't looking at get `PASSIVE_UPDATE_V2` instead of full events
+		const PASSIVE_GUILD_UPDATE_V2 = 1 << 14;
+		/// Lobbies the user is in are connected to automatically
+		const AUTO_LOBBY_CONNECT = 1 << 16;
+	}
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Resume {
+	/// The authentication token
+	pub token: String,
+	/// The session ID to resume
+	pub session_id: String,
+	/// The last sequence number received
+	pub seq: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Ready {
+	/// The gateway version
+	pub v: u8,
+	/// The connected user
+	pub user: User,
+	/// The guilds the user is in
+	#[serde(default)]
+	pub guilds: Vec<Guild>,
+	/// The DMs and group DMs the user is participating in
+	#[serde(default)]
+	pub private_channels: Vec<Channel>,
+	/// The session ID, used for resuming connections
+	pub session_id: String,
+	/// The gateway URL to use for resuming connections
+	pub resume_gateway_url: String,
+	/// The user's settings
+	#[serde(default)]
+	pub user_settings: Option<UserSettings>,
+	/// The read states of the channels the user has read
+	#[serde(default)]
+	pub read_state: Vec<ReadState>,
+	/// The user's experiment assignments
+	#[serde(default)]
+	pub experiments: Vec<UserExperiment>,
+	/// Guild experiment rollouts for the client to assign
+	#[serde(default)]
+	pub guild_experiments: Vec<GuildExperiment>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GuildDelete {
+	/// The ID of the guild
+	pub id: Snowflake,
+	/// Whether the guild is unavailable due to an outage, the user was removed from the guild if not set
+	#[serde(default)]
+	pub unavailable: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GuildRoleUpdate {
+	/// The ID of the guild
+	pub guild_id: Snowflake,
+	/// The created or updated role
+	pub role: Role,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GuildRoleDelete {
+	/// The ID of the guild
+	pub guild_id: Snowflake,
+	/// The ID of the deleted role
+	pub role_id: Snowflake,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GuildEmojisUpdate {
+	/// The ID of the guild
+	pub guild_id: Snowflake,
+	/// All of the guild's emojis
+	pub emojis: Vec<Emoji>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GuildMemberUpdate {
+	/// The ID of the guild
+	pub guild_id: Snowflake,
+	/// The updated member, `user` is always set
+	#[serde(flatten)]
+	pub member: GuildMember,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessageDelete {
+	/// The ID of the deleted message
+	pub id: Snowflake,
+	/// The ID of the channel
+	pub channel_id: Snowflake,
+	/// The ID of the guild
+	#[serde(default)]
+	pub guild_id: Option<Snowflake>,
+}
+
+/// A message was edited or had its embeds resolved
+///
+/// Only `id` and `channel_id` are always sent, the other fields are only the ones that changed
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessageUpdate {
+	/// The ID of the message
+	pub id: Snowflake,
+	/// The ID of the channel
+	pub channel_id: Snowflake,
+	/// The changed fields of the message
+	#[serde(flatten)]
+	pub fields: Map<String, Value>,
+}
+
+impl MessageUpdate {
+	/// The message with the changed fields replaced
+	pub fn apply(
+		&self,
+		message: &Message,
+	) -> Result<Message, serde_json::Error> {
+		let mut merged = serde_json::to_value(message)?;
+		if let Value::Object(merged) = &mut merged {
+			merged.extend(self.fields.clone());
+		}
+		serde_json::from_value(merged)
+	}
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessageReactionAdd {
+	/// The ID of the user that reacted
+	pub user_id: Snowflake,
+	/// The ID of the channel
+	pub channel_id: Snowflake,
+	/// The ID of the message
+	pub message_id: Snowflake,
+	/// The ID of the guild
+	#[serde(default)]
+	pub guild_id: Option<Snowflake>,
+	/// The member that reacted, only sent in guilds
+	#[serde(default)]
+	pub member: Option<GuildMember>,
+	/// The emoji, only `id`, `name` and `animated` are set
+	pub emoji: Emoji,
+	/// Whether this is a super reaction
+	#[serde(default)]
+	pub burst: bool,
+	/// HEX colors used for the super reaction
+	#[serde(default)]
+	pub burst_colors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessageReactionRemove {
+	/// The ID of the user whose reaction was removed
+	pub user_id: Snowflake,
+	/// The ID of the channel
+	pub channel_id: Snowflake,
+	/// The ID of the message
+	pub message_id: Snowflake,
+	/// The ID of the guild
+	#[serde(default)]
+	pub guild_id: Option<Snowflake>,
+	/// The emoji, only `id`, `name` and `animated` are set
+	pub emoji: Emoji,
+	/// Whether this was a super reaction
+	#[serde(default)]
+	pub burst: bool,
+}
+
+/// Sent when all reactions are removed from a message, or all reactions of one emoji if `emoji` is set
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessageReactionRemoveAll {
+	/// The ID of the channel
+	pub channel_id: Snowflake,
+	/// The ID of the message
+	pub message_id: Snowflake,
+	/// The ID of the guild
+	#[serde(default)]
+	pub guild_id: Option<Snowflake>,
+	/// The emoji that was removed, only sent in `MESSAGE_REACTION_REMOVE_EMOJI`
+	#[serde(default)]
+	pub emoji: Option<Emoji>,
+}
+
+/// Sent when the user reads a channel, from this or another session
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessageAck {
+	/// The ID of the channel
+	pub channel_id: Snowflake,
+	/// The ID of the last read message
+	pub message_id: Snowflake,
+	/// The number of unread mentions left in the channel
+	#[serde(default)]
+	pub mention_count: u32,
+	/// Whether the channel was marked as read or unread by the user
+	#[serde(default)]
+	pub manual: bool,
+	/// The version of the read state, increasing with each ack
+	#[serde(default)]
+	pub version: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TypingStart {
+	/// The ID of the channel
+	pub channel_id: Snowflake,
+	/// The ID of the guild
+	#[serde(default)]
+	pub guild_id: Option<Snowflake>,
+	/// The ID of the user that started typing
+	pub user_id: Snowflake,
+	/// Unix time (in seconds) of when the user started typing
+	pub timestamp: u64,
+	/// The member that started typing, only sent in guilds
+	#[serde(default)]
+	pub member: Option<GuildMember>,
+}
+
+/// A dispatched gateway event
+///
+/// Events cowcord doesn't handle yet are kept as [`GatewayEvent::Unknown`]
+#[derive(Debug, Clone)]
+pub enum GatewayEvent {
+	Ready(Box<Ready>),
+	Resumed,
+	GuildCreate(Box<Guild>),
+	/// Doesn't include the guild's channels, members or voice states
+	GuildUpdate(Box<Guild>),
+	GuildDelete(GuildDelete),
+	GuildRoleCreate(GuildRoleUpdate),
+	GuildRoleUpdate(GuildRoleUpdate),
+	GuildRoleDelete(GuildRoleDelete),
+	GuildEmojisUpdate(GuildEmojisUpdate),
+	GuildMemberUpdate(Box<GuildMemberUpdate>),
+	ChannelCreate(Box<Channel>),
+	ChannelUpdate(Box<Channel>),
+	ChannelDelete(Box<Channel>),
+	VoiceStateUpdate(Box<VoiceState>),
+	MessageCreate(Box<Message>),
+	MessageUpdate(Box<MessageUpdate>),
+	MessageDelete(MessageDelete),
+	MessageAck(MessageAck),
+	MessageReactionAdd(Box<MessageReactionAdd>),
+	MessageReactionRemove(Box<MessageReactionRemove>),
+	MessageReactionRemoveAll(MessageReactionRemoveAll),
+	MessageReactionRemoveEmoji(MessageReactionRemoveAll),
+	TypingStart(Box<TypingStart>),
+	UserSettingsUpdate(UserSettings),
+	Unknown {
+		name: String,
+		data: Value,
+	},
+}
+
+impl GatewayEvent {
+	/// Decodes the data of a dispatch payload from its event name
+	pub fn from_dispatch(
+		name: &str,
+		data: Value,
+	) -> Result<Self, serde_json::Error> {
+		Ok(match name {
+			| "READY" => Self::Ready(serde_json::from_value(data)?),
+			| "RESUMED" => Self::Resumed,
+			| "GUILD_CREATE" => Self::GuildCreate(serde_json::from_value(data)?),
+			| "GUILD_UPDATE" => Self::GuildUpdate(serde_json::from_value(data)?),
+			| "GUILD_DELETE" => Self::GuildDelete(serde_json::from_value(data)?),
+			| "GUILD_ROLE_CREATE" => Self::GuildRoleCreate(serde_json::from_value(data)?),
+			| "GUILD_ROLE_UPDATE" => Self::GuildRoleUpdate(serde_json::from_value(data)?),
+			| "GUILD_ROLE_DELETE" => Self::GuildRoleDelete(serde_json::from_value(data)?),
+			| "GUILD_EMOJIS_UPDATE" => Self::GuildEmojisUpdate(serde_json::from_value(data)?),
+			| "GUILD_MEMBER_UPDATE" => Self::GuildMemberUpdate(serde_json::from_value(data)?),
+			| "CHANNEL_CREATE" => Self::ChannelCreate(serde_json::from_value(data)?),
+			| "CHANNEL_UPDATE" => Self::ChannelUpdate(serde_json::from_value(data)?),
+			| "CHANNEL_DELETE" => Self::ChannelDelete(serde_json::from_value(data)?),
+			| "VOICE_STATE_UPDATE" => Self::VoiceStateUpdate(serde_json::from_value(data)?),
+			| "MESSAGE_CREATE" => Self::MessageCreate(serde_json::from_value(data)?),
+			| "MESSAGE_UPDATE" => Self::MessageUpdate(serde_json::from_value(data)?),
+			| "MESSAGE_DELETE" => Self::MessageDelete(serde_json::from_value(data)?),
+			| "MESSAGE_ACK" => Self::MessageAck(serde_json::from_value(data)?),
+			| "MESSAGE_REACTION_ADD" => Self::MessageReactionAdd(serde_json::from_value(data)?),
+			| "MESSAGE_REACTION_REMOVE" => {
+				Self::MessageReactionRemove(serde_json::from_value(data)?)
+			},
+			| "MESSAGE_REACTION_REMOVE_ALL" => {
+				Self::MessageReactionRemoveAll(serde_json::from_value(data)?)
+			},
+			| "MESSAGE_REACTION_REMOVE_EMOJI" => {
+				Self::MessageReactionRemoveEmoji(serde_json::from_value(data)?)
+			},
+			| "TYPING_START" => Self::TypingStart(serde_json::from_value(data)?),
+			| "USER_SETTINGS_UPDATE" => Self::UserSettingsUpdate(serde_json::from_value(data)?),
+			| _ => Self::Unknown {
+				name: name.to_owned(),
+				data,
+			},
+		})
+	}
+}
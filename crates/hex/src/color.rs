@@ -0,0 +1,192 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Hex, HexError};
+
+/// An RGB color, sent as an integer like `16777215` or a string like `"ffffff"`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Hash)]
+pub struct Color {
+	pub r: u8,
+	pub g: u8,
+	pub b: u8,
+}
+
+impl Color {
+	pub const BLACK: Color = Color::from_rgb(0, 0, 0);
+	pub const WHITE: Color = Color::from_rgb(255, 255, 255);
+
+	pub const fn from_rgb(
+		r: u8,
+		g: u8,
+		b: u8,
+	) -> Color {
+		Color {
+			r,
+			g,
+			b,
+		}
+	}
+
+	pub const fn rgb(&self) -> (u8, u8, u8) {
+		(self.r, self.g, self.b)
+	}
+
+	/// The color as `#rrggbb`
+	pub fn to_css(&self) -> String {
+		format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+	}
+
+	/// The color as `#rrggbbaa`, for backgrounds tinted with the color
+	pub fn to_css_alpha(
+		&self,
+		alpha: u8,
+	) -> String {
+		format!("{}{alpha:02x}", self.to_css())
+	}
+
+	/// Hue in degrees (0-360), saturation and lightness (0-1)
+	pub fn to_hsl(&self) -> (f64, f64, f64) {
+		let [r, g, b] = [self.r, self.g, self.b].map(|c| f64::from(c) / 255.0);
+		let max = r.max(g).max(b);
+		let min = r.min(g).min(b);
+		let lightness = (max + min) / 2.0;
+		let delta = max - min;
+
+		if delta == 0.0 {
+			return (0.0, 0.0, lightness);
+		}
+
+		let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+		let hue = if max == r {
+			((g - b) / delta).rem_euclid(6.0)
+		} else if max == g {
+			(b - r) / delta + 2.0
+		} else {
+			(r - g) / delta + 4.0
+		};
+
+		(hue * 60.0, saturation, lightness)
+	}
+
+	/// Hue in degrees, saturation and lightness (0-1), out of range values are wrapped or clamped
+	pub fn from_hsl(
+		hue: f64,
+		saturation: f64,
+		lightness: f64,
+	) -> Color {
+		let hue = hue.rem_euclid(360.0) / 60.0;
+		let saturation = saturation.clamp(0.0, 1.0);
+		let lightness = lightness.clamp(0.0, 1.0);
+
+		let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+		let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+		let (r, g, b) = match hue as u8 {
+			| 0 => (chroma, x, 0.0),
+			| 1 => (x, chroma, 0.0),
+			| 2 => (0.0, chroma, x),
+			| 3 => (0.0, x, chroma),
+			| 4 => (x, 0.0, chroma),
+			| _ => (chroma, 0.0, x),
+		};
+		let m = lightness - chroma / 2.0;
+		let channel = |c: f64| ((c + m) * 255.0).round() as u8;
+
+		Color::from_rgb(channel(r), channel(g), channel(b))
+	}
+
+	/// Relative luminance as defined by WCAG, from 0 for black to 1 for white
+	pub fn luminance(&self) -> f64 {
+		let [r, g, b] = [self.r, self.g, self.b].map(|c| {
+			let c = f64::from(c) / 255.0;
+			if c <= 0.03928 {
+				c / 12.92
+			} else {
+				((c + 0.055) / 1.055).powf(2.4)
+			}
+		});
+
+		0.2126 * r + 0.7152 * g + 0.0722 * b
+	}
+
+	/// WCAG contrast ratio between the colors, from 1 to 21
+	pub fn contrast(
+		&self,
+		other: &Color,
+	) -> f64 {
+		let (a, b) = (self.luminance(), other.luminance());
+		(a.max(b) + 0.05) / (a.min(b) + 0.05)
+	}
+
+	/// Black or white, whichever is easier to read on top of this color
+	pub fn readable_text(&self) -> Color {
+		if self.contrast(&Color::BLACK) >= self.contrast(&Color::WHITE) {
+			Color::BLACK
+		} else {
+			Color::WHITE
+		}
+	}
+}
+
+impl fmt::Display for Color {
+	fn fmt(
+		&self,
+		f: &mut fmt::Formatter<'_>,
+	) -> fmt::Result {
+		write!(f, "{}", self.to_css())
+	}
+}
+
+impl FromStr for Color {
+	type Err = HexError;
+
+	/// Parses `rrggbb`, with or without a leading `#`
+	fn from_str(data: &str) -> Result<Color, HexError> {
+		let hex: Hex = data.strip_prefix('#').unwrap_or(data).parse()?;
+		Color::try_from(&hex)
+	}
+}
+
+impl From<(u8, u8, u8)> for Color {
+	fn from((r, g, b): (u8, u8, u8)) -> Color {
+		Color::from_rgb(r, g, b)
+	}
+}
+
+impl From<Color> for (u8, u8, u8) {
+	fn from(color: Color) -> (u8, u8, u8) {
+		color.rgb()
+	}
+}
+
+impl From<Color> for u32 {
+	fn from(color: Color) -> u32 {
+		u32::from_be_bytes([0, color.r, color.g, color.b])
+	}
+}
+
+impl TryFrom<u64> for Color {
+	type Error = HexError;
+
+	fn try_from(data: u64) -> Result<Color, HexError> {
+		if data > 0xffffff {
+			return Err(HexError::TooLarge);
+		}
+		let [.., r, g, b] = data.to_be_bytes();
+
+		Ok(Color::from_rgb(r, g, b))
+	}
+}
+
+impl TryFrom<&Hex> for Color {
+	type Error = HexError;
+
+	fn try_from(hex: &Hex) -> Result<Color, HexError> {
+		Color::try_from(hex.to_numeric()?)
+	}
+}
+
+impl From<Color> for Hex {
+	fn from(color: Color) -> Hex {
+		Hex::from(vec![color.r, color.g, color.b])
+	}
+}
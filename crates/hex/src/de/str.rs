@@ -1,23 +1,66 @@
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
+use std::hash::{BuildHasher, Hash};
 use std::marker::PhantomData;
 
-use serde::Deserializer;
-use serde::de::Visitor;
+use serde::de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer};
 
-use crate::Hex;
+use crate::{Color, Hex};
 
 pub trait HexDeserialize<'de>: Sized {
-	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	/// `width` is the number of bytes every [`Hex`] is padded to, values that don't fit are an error
+	fn deserialize<D>(
+		deserializer: D,
+		width: Option<usize>,
+	) -> Result<Self, D::Error>
 	where
 		D: Deserializer<'de>;
 }
 
+/// Deserializes a value inside of a collection with [`HexDeserialize`]
+struct Element<T> {
+	width: Option<usize>,
+	marker: PhantomData<T>,
+}
+
+impl<T> Element<T> {
+	fn new(width: Option<usize>) -> Element<T> {
+		Element {
+			width,
+			marker: PhantomData,
+		}
+	}
+}
+
+impl<'de, T> DeserializeSeed<'de> for Element<T>
+where
+	T: HexDeserialize<'de>,
+{
+	type Value = T;
+
+	fn deserialize<D>(
+		self,
+		deserializer: D,
+	) -> Result<T, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		T::deserialize(deserializer, self.width)
+	}
+}
+
 impl<'de> HexDeserialize<'de> for Hex {
-	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	fn deserialize<D>(
+		deserializer: D,
+		width: Option<usize>,
+	) -> Result<Self, D::Error>
 	where
 		D: Deserializer<'de>,
 	{
-		struct HexVisitor;
+		struct HexVisitor {
+			width: Option<usize>,
+		}
 
 		impl<'de> Visitor<'de> for HexVisitor {
 			type Value = Hex;
@@ -36,11 +79,52 @@ impl<'de> HexDeserialize<'de> for Hex {
 			where
 				E: serde::de::Error,
 			{
-				Ok(Hex::from(value))
+				value
+					.parse::<Hex>()
+					.and_then(|hex| hex.fit(self.width))
+					.map_err(E::custom)
+			}
+		}
+
+		deserializer.deserialize_str(HexVisitor {
+			width,
+		})
+	}
+}
+
+impl<'de> HexDeserialize<'de> for Color {
+	/// Colors are always 3 bytes, so the width is ignored
+	fn deserialize<D>(
+		deserializer: D,
+		_width: Option<usize>,
+	) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		struct ColorVisitor;
+
+		impl<'de> Visitor<'de> for ColorVisitor {
+			type Value = Color;
+
+			fn expecting(
+				&self,
+				formatter: &mut fmt::Formatter,
+			) -> fmt::Result {
+				formatter.write_str("a color as a hexadecimal string")
+			}
+
+			fn visit_str<E>(
+				self,
+				value: &str,
+			) -> Result<Self::Value, E>
+			where
+				E: serde::de::Error,
+			{
+				value.parse().map_err(E::custom)
 			}
 		}
 
-		deserializer.deserialize_str(HexVisitor)
+		deserializer.deserialize_str(ColorVisitor)
 	}
 }
 
@@ -48,11 +132,15 @@ impl<'de, T> HexDeserialize<'de> for Option<T>
 where
 	T: HexDeserialize<'de>,
 {
-	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	fn deserialize<D>(
+		deserializer: D,
+		width: Option<usize>,
+	) -> Result<Self, D::Error>
 	where
 		D: Deserializer<'de>,
 	{
 		struct OptionVisitor<T> {
+			width: Option<usize>,
 			marker: PhantomData<T>,
 		}
 
@@ -83,26 +171,39 @@ where
 			where
 				D: Deserializer<'de>,
 			{
-				let hex = T::deserialize(deserializer)?;
+				let hex = T::deserialize(deserializer, self.width)?;
 				Ok(Some(hex))
 			}
 		}
 
 		deserializer.deserialize_option(OptionVisitor {
+			width,
 			marker: PhantomData,
 		})
 	}
 }
 
-impl<'de> HexDeserialize<'de> for Vec<Hex> {
-	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+impl<'de, T> HexDeserialize<'de> for Vec<T>
+where
+	T: HexDeserialize<'de>,
+{
+	fn deserialize<D>(
+		deserializer: D,
+		width: Option<usize>,
+	) -> Result<Self, D::Error>
 	where
 		D: Deserializer<'de>,
 	{
-		struct VecHexVisitor;
+		struct VecVisitor<T> {
+			width: Option<usize>,
+			marker: PhantomData<T>,
+		}
 
-		impl<'de> Visitor<'de> for VecHexVisitor {
-			type Value = Vec<Hex>;
+		impl<'de, T> Visitor<'de> for VecVisitor<T>
+		where
+			T: HexDeserialize<'de>,
+		{
+			type Value = Vec<T>;
 
 			fn expecting(
 				&self,
@@ -116,37 +217,52 @@ impl<'de> HexDeserialize<'de> for Vec<Hex> {
 				mut seq: A,
 			) -> Result<Self::Value, A::Error>
 			where
-				A: serde::de::SeqAccess<'de>,
+				A: SeqAccess<'de>,
 			{
 				let mut items = Vec::new();
 
-				while let Some(value) = seq.next_element::<String>()? {
-					items.push(Hex::from(value));
+				while let Some(value) = seq.next_element_seed(Element::new(self.width))? {
+					items.push(value);
 				}
 
 				Ok(items)
 			}
 		}
 
-		deserializer.deserialize_seq(VecHexVisitor)
+		deserializer.deserialize_seq(VecVisitor {
+			width,
+			marker: PhantomData,
+		})
 	}
 }
 
-impl<'de> HexDeserialize<'de> for (Hex, Hex) {
-	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+impl<'de, T, const N: usize> HexDeserialize<'de> for [T; N]
+where
+	T: HexDeserialize<'de>,
+{
+	fn deserialize<D>(
+		deserializer: D,
+		width: Option<usize>,
+	) -> Result<Self, D::Error>
 	where
 		D: Deserializer<'de>,
 	{
-		struct TupleHexVisitor;
+		struct ArrayVisitor<T, const N: usize> {
+			width: Option<usize>,
+			marker: PhantomData<T>,
+		}
 
-		impl<'de> Visitor<'de> for TupleHexVisitor {
-			type Value = (Hex, Hex);
+		impl<'de, T, const N: usize> Visitor<'de> for ArrayVisitor<T, N>
+		where
+			T: HexDeserialize<'de>,
+		{
+			type Value = [T; N];
 
 			fn expecting(
 				&self,
 				formatter: &mut fmt::Formatter,
 			) -> fmt::Result {
-				formatter.write_str("a tuple of hexadecimal strings")
+				write!(formatter, "an array of {N} hexadecimal strings")
 			}
 
 			fn visit_seq<A>(
@@ -154,19 +270,194 @@ impl<'de> HexDeserialize<'de> for (Hex, Hex) {
 				mut seq: A,
 			) -> Result<Self::Value, A::Error>
 			where
-				A: serde::de::SeqAccess<'de>,
+				A: SeqAccess<'de>,
+			{
+				let mut items = Vec::with_capacity(N);
+
+				for index in 0..N {
+					let value = seq
+						.next_element_seed(Element::new(self.width))?
+						.ok_or_else(|| A::Error::invalid_length(index, &self))?;
+					items.push(value);
+				}
+
+				Ok(items
+					.try_into()
+					.unwrap_or_else(|_| unreachable!("exactly N items were collected")))
+			}
+		}
+
+		deserializer.deserialize_tuple(N, ArrayVisitor {
+			width,
+			marker: PhantomData,
+		})
+	}
+}
+
+impl<'de, A, B> HexDeserialize<'de> for (A, B)
+where
+	A: HexDeserialize<'de>,
+	B: HexDeserialize<'de>,
+{
+	fn deserialize<D>(
+		deserializer: D,
+		width: Option<usize>,
+	) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		struct TupleVisitor<A, B> {
+			width: Option<usize>,
+			marker: PhantomData<(A, B)>,
+		}
+
+		impl<'de, A, B> Visitor<'de> for TupleVisitor<A, B>
+		where
+			A: HexDeserialize<'de>,
+			B: HexDeserialize<'de>,
+		{
+			type Value = (A, B);
+
+			fn expecting(
+				&self,
+				formatter: &mut fmt::Formatter,
+			) -> fmt::Result {
+				formatter.write_str("a tuple of two hexadecimal strings")
+			}
+
+			fn visit_seq<S>(
+				self,
+				mut seq: S,
+			) -> Result<Self::Value, S::Error>
+			where
+				S: SeqAccess<'de>,
 			{
 				let first = seq
-					.next_element::<String>()?
-					.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+					.next_element_seed(Element::new(self.width))?
+					.ok_or_else(|| S::Error::invalid_length(0, &self))?;
 				let second = seq
-					.next_element::<String>()?
-					.ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+					.next_element_seed(Element::new(self.width))?
+					.ok_or_else(|| S::Error::invalid_length(1, &self))?;
 
-				Ok((Hex::from(first), Hex::from(second)))
+				Ok((first, second))
 			}
 		}
 
-		deserializer.deserialize_tuple(2, TupleHexVisitor)
+		deserializer.deserialize_tuple(2, TupleVisitor {
+			width,
+			marker: PhantomData,
+		})
+	}
+}
+
+impl<'de, K, T, H> HexDeserialize<'de> for HashMap<K, T, H>
+where
+	K: Deserialize<'de> + Eq + Hash,
+	T: HexDeserialize<'de>,
+	H: BuildHasher + Default,
+{
+	fn deserialize<D>(
+		deserializer: D,
+		width: Option<usize>,
+	) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		struct HashMapVisitor<K, T, H> {
+			width: Option<usize>,
+			marker: PhantomData<(K, T, H)>,
+		}
+
+		impl<'de, K, T, H> Visitor<'de> for HashMapVisitor<K, T, H>
+		where
+			K: Deserialize<'de> + Eq + Hash,
+			T: HexDeserialize<'de>,
+			H: BuildHasher + Default,
+		{
+			type Value = HashMap<K, T, H>;
+
+			fn expecting(
+				&self,
+				formatter: &mut fmt::Formatter,
+			) -> fmt::Result {
+				formatter.write_str("a map of hexadecimal strings")
+			}
+
+			fn visit_map<M>(
+				self,
+				mut map: M,
+			) -> Result<Self::Value, M::Error>
+			where
+				M: MapAccess<'de>,
+			{
+				let mut items = HashMap::with_hasher(H::default());
+
+				while let Some(key) = map.next_key()? {
+					items.insert(key, map.next_value_seed(Element::new(self.width))?);
+				}
+
+				Ok(items)
+			}
+		}
+
+		deserializer.deserialize_map(HashMapVisitor {
+			width,
+			marker: PhantomData,
+		})
+	}
+}
+
+impl<'de, K, T> HexDeserialize<'de> for BTreeMap<K, T>
+where
+	K: Deserialize<'de> + Ord,
+	T: HexDeserialize<'de>,
+{
+	fn deserialize<D>(
+		deserializer: D,
+		width: Option<usize>,
+	) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		struct BTreeMapVisitor<K, T> {
+			width: Option<usize>,
+			marker: PhantomData<(K, T)>,
+		}
+
+		impl<'de, K, T> Visitor<'de> for BTreeMapVisitor<K, T>
+		where
+			K: Deserialize<'de> + Ord,
+			T: HexDeserialize<'de>,
+		{
+			type Value = BTreeMap<K, T>;
+
+			fn expecting(
+				&self,
+				formatter: &mut fmt::Formatter,
+			) -> fmt::Result {
+				formatter.write_str("a map of hexadecimal strings")
+			}
+
+			fn visit_map<M>(
+				self,
+				mut map: M,
+			) -> Result<Self::Value, M::Error>
+			where
+				M: MapAccess<'de>,
+			{
+				let mut items = BTreeMap::new();
+
+				while let Some(key) = map.next_key()? {
+					items.insert(key, map.next_value_seed(Element::new(self.width))?);
+				}
+
+				Ok(items)
+			}
+		}
+
+		deserializer.deserialize_map(BTreeMapVisitor {
+			width,
+			marker: PhantomData,
+		})
 	}
 }
@@ -1,10 +1,13 @@
 #![allow(non_camel_case_types)]
 
 use std::fmt;
+use std::str::FromStr;
 
-use hex_encode::{decode, encode};
+pub use color::Color;
+use hex_encode::encode;
 use serde::{Deserialize, Serialize};
 
+mod color;
 mod de;
 mod ser;
 mod tests;
@@ -21,12 +24,75 @@ impl Hex {
 		encode(&self.0)
 	}
 
-	pub fn to_numeric(&self) -> u64 {
-		let hex_str = self.to_alphanumeric();
-		u64::from_str_radix(&hex_str, 16).expect("This shouldn't fail")
+	/// Fails if the value doesn't fit in a [`u64`], leading zero bytes don't count
+	pub fn to_numeric(&self) -> Result<u64, HexError> {
+		let significant: Vec<u8> = self.0.iter().copied().skip_while(|b| *b == 0).collect();
+		if significant.len() > 8 {
+			return Err(HexError::TooLarge);
+		}
+
+		Ok(significant
+			.into_iter()
+			.fold(0, |acc, b| (acc << 8) | u64::from(b)))
+	}
+
+	/// Pads or trims leading zero bytes so the value is exactly `width` bytes, fails if it doesn't fit
+	pub fn to_width(
+		&self,
+		width: usize,
+	) -> Result<Hex, HexError> {
+		let significant = self.0.iter().position(|b| *b != 0).unwrap_or(self.0.len());
+		let size = self.0.len() - significant;
+		if size > width {
+			return Err(HexError::TooLarge);
+		}
+
+		let mut bytes = vec![0; width - size];
+		bytes.extend_from_slice(&self.0[significant..]);
+		Ok(Hex(bytes))
+	}
+
+	/// [`Hex::to_width`] if there's a width, for the fixed width (de)serializers
+	pub(crate) fn fit(
+		&self,
+		width: Option<usize>,
+	) -> Result<Hex, HexError> {
+		match width {
+			| Some(width) => self.to_width(width),
+			| None => Ok(self.clone()),
+		}
+	}
+}
+
+/// Why a [`Hex`] couldn't be parsed or converted
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum HexError {
+	/// The string has a character that isn't a hexadecimal digit
+	InvalidCharacter { character: char, index: usize },
+	/// The value is too large for what it's converted to, like a [`u64`] or a [`Color`]
+	TooLarge,
+}
+
+impl fmt::Display for HexError {
+	fn fmt(
+		&self,
+		f: &mut fmt::Formatter<'_>,
+	) -> fmt::Result {
+		match self {
+			| HexError::InvalidCharacter {
+				character,
+				index,
+			} => write!(
+				f,
+				"invalid hexadecimal character {character:?} at index {index}"
+			),
+			| HexError::TooLarge => write!(f, "hexadecimal value is too large"),
+		}
 	}
 }
 
+impl std::error::Error for HexError {}
+
 impl fmt::Display for Hex {
 	fn fmt(
 		&self,
@@ -36,30 +102,59 @@ impl fmt::Display for Hex {
 	}
 }
 
-impl From<&str> for Hex {
-	fn from(data: &str) -> Hex {
-		Hex(decode(data).unwrap())
+impl FromStr for Hex {
+	type Err = HexError;
+
+	/// Odd-length strings are left-padded with a `0`, so `"f"` is the same as `"0f"`
+	fn from_str(data: &str) -> Result<Hex, HexError> {
+		let mut digits = Vec::with_capacity(data.len() + 1);
+		if !data.len().is_multiple_of(2) {
+			digits.push(0);
+		}
+
+		for (index, character) in data.char_indices() {
+			let Some(digit) = character.to_digit(16) else {
+				return Err(HexError::InvalidCharacter {
+					character,
+					index,
+				});
+			};
+			digits.push(digit as u8);
+		}
+
+		Ok(Hex(digits
+			.chunks(2)
+			.map(|pair| (pair[0] << 4) | pair[1])
+			.collect()))
+	}
+}
+
+impl TryFrom<&str> for Hex {
+	type Error = HexError;
+
+	fn try_from(data: &str) -> Result<Hex, HexError> {
+		data.parse()
 	}
 }
 
-impl From<String> for Hex {
-	fn from(data: String) -> Hex {
-		Hex(decode(&data).unwrap())
+impl TryFrom<String> for Hex {
+	type Error = HexError;
+
+	fn try_from(data: String) -> Result<Hex, HexError> {
+		data.parse()
 	}
 }
 
 impl From<u64> for Hex {
 	fn from(data: u64) -> Hex {
-		let hex_string = format!("{:x}", data);
-
-		// fixes panic when trying to convert "f" or similar to a [`Hex`]
-		let padded = if hex_string.len() % 2 != 0 {
-			format!("0{}", hex_string)
-		} else {
-			hex_string
-		};
+		let bytes = data.to_be_bytes();
+		// keeps at least one byte so `0` is `"00"`
+		let start = bytes
+			.iter()
+			.position(|b| *b != 0)
+			.unwrap_or(bytes.len() - 1);
 
-		Hex(decode(padded).unwrap())
+		Hex(bytes[start..].to_vec())
 	}
 }
 
@@ -84,7 +179,7 @@ pub mod as_str {
 		T: HexSerialize,
 		S: Serializer,
 	{
-		HexSerialize::serialize(hex, serializer)
+		HexSerialize::serialize(hex, serializer, None)
 	}
 
 	pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
@@ -92,7 +187,7 @@ pub mod as_str {
 		T: HexDeserialize<'de>,
 		D: Deserializer<'de>,
 	{
-		HexDeserialize::deserialize(deserializer)
+		HexDeserialize::deserialize(deserializer, None)
 	}
 }
 
@@ -111,7 +206,7 @@ pub mod as_num {
 		T: HexSerialize,
 		S: Serializer,
 	{
-		HexSerialize::serialize(hex, serializer)
+		HexSerialize::serialize(hex, serializer, None)
 	}
 
 	pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
@@ -119,6 +214,58 @@ pub mod as_num {
 		T: HexDeserialize<'de>,
 		D: Deserializer<'de>,
 	{
-		HexDeserialize::deserialize(deserializer)
+		HexDeserialize::deserialize(deserializer, None)
+	}
+}
+
+/// [`as_str`], but every [`Hex`] is exactly `N` bytes, keeping leading zeros like in `"000000"`
+///
+/// Used like `#[serde(with = "hex::as_str_fixed::<3>")]`
+pub struct as_str_fixed<const N: usize>;
+
+impl<const N: usize> as_str_fixed<N> {
+	pub fn serialize<T, S>(
+		hex: &T,
+		serializer: S,
+	) -> Result<S::Ok, S::Error>
+	where
+		T: ser::str::HexSerialize,
+		S: serde::Serializer,
+	{
+		ser::str::HexSerialize::serialize(hex, serializer, Some(N))
+	}
+
+	pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+	where
+		T: de::str::HexDeserialize<'de>,
+		D: serde::Deserializer<'de>,
+	{
+		de::str::HexDeserialize::deserialize(deserializer, Some(N))
+	}
+}
+
+/// [`as_num`], but every [`Hex`] is exactly `N` bytes, so `0` is deserialized as `"000000"` instead of `"00"`
+///
+/// Used like `#[serde(with = "hex::as_num_fixed::<3>")]`
+pub struct as_num_fixed<const N: usize>;
+
+impl<const N: usize> as_num_fixed<N> {
+	pub fn serialize<T, S>(
+		hex: &T,
+		serializer: S,
+	) -> Result<S::Ok, S::Error>
+	where
+		T: ser::num::HexSerialize,
+		S: serde::Serializer,
+	{
+		ser::num::HexSerialize::serialize(hex, serializer, Some(N))
+	}
+
+	pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+	where
+		T: de::num::HexDeserialize<'de>,
+		D: serde::Deserializer<'de>,
+	{
+		de::num::HexDeserialize::deserialize(deserializer, Some(N))
 	}
 }
@@ -1,26 +1,68 @@
-use serde::Serializer;
-use serde::ser::SerializeSeq;
+use std::collections::{BTreeMap, HashMap};
 
-use crate::Hex;
+use serde::ser::{Error, SerializeMap, SerializeSeq, SerializeTuple};
+use serde::{Serialize, Serializer};
+
+use crate::{Color, Hex};
 
 pub trait HexSerialize {
+	/// `width` is the number of bytes every [`Hex`] is padded to, values that don't fit are an error
 	fn serialize<S>(
 		&self,
 		serializer: S,
+		width: Option<usize>,
 	) -> Result<S::Ok, S::Error>
 	where
 		S: Serializer;
 }
 
+/// Serializes a value inside of a collection with [`HexSerialize`]
+struct Element<'a, T: ?Sized> {
+	value: &'a T,
+	width: Option<usize>,
+}
+
+impl<T> Serialize for Element<'_, T>
+where
+	T: ?Sized + HexSerialize,
+{
+	fn serialize<S>(
+		&self,
+		serializer: S,
+	) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		HexSerialize::serialize(self.value, serializer, self.width)
+	}
+}
+
 impl HexSerialize for Hex {
 	fn serialize<S>(
 		&self,
 		serializer: S,
+		width: Option<usize>,
 	) -> Result<S::Ok, S::Error>
 	where
 		S: Serializer,
 	{
-		serializer.serialize_str(&self.to_alphanumeric())
+		let hex = self.fit(width).map_err(S::Error::custom)?;
+
+		serializer.serialize_str(&hex.to_alphanumeric())
+	}
+}
+
+impl HexSerialize for Color {
+	/// Colors are always 3 bytes, so the width is ignored
+	fn serialize<S>(
+		&self,
+		serializer: S,
+		_width: Option<usize>,
+	) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(&Hex::from(*self).to_alphanumeric())
 	}
 }
 
@@ -31,48 +73,144 @@ where
 	fn serialize<S>(
 		&self,
 		serializer: S,
+		width: Option<usize>,
 	) -> Result<S::Ok, S::Error>
 	where
 		S: Serializer,
 	{
 		if let Some(hex) = self {
-			HexSerialize::serialize(hex, serializer)
+			HexSerialize::serialize(hex, serializer, width)
 		} else {
 			serializer.serialize_none()
 		}
 	}
 }
 
-impl HexSerialize for Vec<Hex> {
+impl<T> HexSerialize for Vec<T>
+where
+	T: HexSerialize,
+{
 	fn serialize<S>(
 		&self,
 		serializer: S,
+		width: Option<usize>,
 	) -> Result<S::Ok, S::Error>
 	where
 		S: Serializer,
 	{
 		let mut seq = serializer.serialize_seq(Some(self.len()))?;
 
-		for item in self.iter() {
-			seq.serialize_element(&item.to_alphanumeric())?;
+		for value in self.iter() {
+			seq.serialize_element(&Element {
+				value,
+				width,
+			})?;
 		}
 
 		seq.end()
 	}
 }
 
-impl HexSerialize for (Hex, Hex) {
+impl<T, const N: usize> HexSerialize for [T; N]
+where
+	T: HexSerialize,
+{
 	fn serialize<S>(
 		&self,
 		serializer: S,
+		width: Option<usize>,
 	) -> Result<S::Ok, S::Error>
 	where
 		S: Serializer,
 	{
-		let mut seq = serializer.serialize_seq(Some(2))?;
-		seq.serialize_element(&self.0.to_alphanumeric())?;
-		seq.serialize_element(&self.1.to_alphanumeric())?;
-		seq.end()
+		let mut tuple = serializer.serialize_tuple(N)?;
+
+		for value in self.iter() {
+			tuple.serialize_element(&Element {
+				value,
+				width,
+			})?;
+		}
+
+		tuple.end()
+	}
+}
+
+impl<A, B> HexSerialize for (A, B)
+where
+	A: HexSerialize,
+	B: HexSerialize,
+{
+	fn serialize<S>(
+		&self,
+		serializer: S,
+		width: Option<usize>,
+	) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let mut tuple = serializer.serialize_tuple(2)?;
+		tuple.serialize_element(&Element {
+			value: &self.0,
+			width,
+		})?;
+		tuple.serialize_element(&Element {
+			value: &self.1,
+			width,
+		})?;
+		tuple.end()
+	}
+}
+
+impl<K, T, H> HexSerialize for HashMap<K, T, H>
+where
+	K: Serialize,
+	T: HexSerialize,
+{
+	fn serialize<S>(
+		&self,
+		serializer: S,
+		width: Option<usize>,
+	) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let mut map = serializer.serialize_map(Some(self.len()))?;
+
+		for (key, value) in self.iter() {
+			map.serialize_entry(key, &Element {
+				value,
+				width,
+			})?;
+		}
+
+		map.end()
+	}
+}
+
+impl<K, T> HexSerialize for BTreeMap<K, T>
+where
+	K: Serialize,
+	T: HexSerialize,
+{
+	fn serialize<S>(
+		&self,
+		serializer: S,
+		width: Option<usize>,
+	) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let mut map = serializer.serialize_map(Some(self.len()))?;
+
+		for (key, value) in self.iter() {
+			map.serialize_entry(key, &Element {
+				value,
+				width,
+			})?;
+		}
+
+		map.end()
 	}
 }
 
@@ -83,10 +221,11 @@ where
 	fn serialize<S>(
 		&self,
 		serializer: S,
+		width: Option<usize>,
 	) -> Result<S::Ok, S::Error>
 	where
 		S: Serializer,
 	{
-		(**self).serialize(serializer)
+		(**self).serialize(serializer, width)
 	}
 }
@@ -17,7 +17,7 @@ mod serialization {
 
 		let thing = Thing {
 			foo: 16777215.into(),
-			bar: "123abc".into(),
+			bar: "123abc".parse().unwrap(),
 		};
 
 		let thing_json = serde_json::to_string(&thing).unwrap();
@@ -40,7 +40,7 @@ mod serialization {
 		}
 
 		let thing = Thing {
-			foo: Some("123abc".into()),
+			foo: Some("123abc".parse().unwrap()),
 			bar: None,
 			buzz: Some(Some(Some(16777215.into()))),
 		};
@@ -63,7 +63,7 @@ mod serialization {
 		}
 
 		let thing = Thing {
-			foo: (16777215.into(), "000000".into()),
+			foo: (16777215.into(), "000000".parse().unwrap()),
 			bar: Some(None),
 		};
 
@@ -85,7 +85,7 @@ mod serialization {
 		}
 
 		let thing = Thing {
-			foo: vec![16777215.into(), "000000".into()],
+			foo: vec![16777215.into(), "000000".parse().unwrap()],
 			bar: vec![],
 		};
 
@@ -95,4 +95,344 @@ mod serialization {
 
 		assert_eq!(thing_json, json);
 	}
+
+	#[test]
+	fn parsing() {
+		#[derive(Deserialize, Serialize, Debug, PartialEq)]
+		struct Thing {
+			#[serde(with = "as_str")]
+			foo: Hex,
+			#[serde(with = "as_str")]
+			bar: Vec<Hex>,
+		}
+
+		let thing: Thing = serde_json::from_str(r#"{"foo":"f","bar":["abc","0"]}"#).unwrap();
+
+		assert_eq!(thing.foo.to_numeric(), Ok(15));
+		assert_eq!(thing.bar[0].to_alphanumeric(), "0abc");
+		assert_eq!(thing.bar[1].to_numeric(), Ok(0));
+
+		assert!(serde_json::from_str::<Thing>(r#"{"foo":"12xz","bar":[]}"#).is_err());
+		assert!(serde_json::from_str::<Thing>(r#"{"foo":"00","bar":["g"]}"#).is_err());
+	}
+
+	#[test]
+	fn errors() {
+		assert_eq!(
+			"12xz".parse::<Hex>(),
+			Err(HexError::InvalidCharacter {
+				character: 'x',
+				index: 2,
+			})
+		);
+		assert_eq!(Hex::try_from(""), Ok(Hex::from(vec![])));
+
+		let large: Hex = "0000ffffffffffffffff".parse().unwrap();
+		assert_eq!(large.to_numeric(), Ok(u64::MAX));
+
+		let too_large: Hex = "01ffffffffffffffff".parse().unwrap();
+		assert_eq!(too_large.to_numeric(), Err(HexError::TooLarge));
+
+		#[derive(Serialize)]
+		struct Thing {
+			#[serde(with = "as_num")]
+			foo: Hex,
+		}
+		assert!(
+			serde_json::to_string(&Thing {
+				foo: too_large
+			})
+			.is_err()
+		);
+	}
+}
+
+mod collections {
+	use std::collections::{BTreeMap, HashMap};
+
+	use serde::*;
+
+	use crate::*;
+
+	#[test]
+	fn arrays() {
+		#[derive(Deserialize, Serialize, Debug, PartialEq)]
+		struct Thing {
+			#[serde(with = "as_str")]
+			foo: [Hex; 2],
+			#[serde(with = "as_num")]
+			bar: [Hex; 3],
+			#[serde(with = "as_str")]
+			buzz: [Color; 0],
+		}
+
+		let thing = Thing {
+			foo: ["123abc".parse().unwrap(), 0.into()],
+			bar: [1.into(), 16777215.into(), 255.into()],
+			buzz: [],
+		};
+
+		let thing_json = serde_json::to_string(&thing).unwrap();
+
+		let json = r#"{"foo":["123abc","00"],"bar":[1,16777215,255],"buzz":[]}"#;
+
+		assert_eq!(thing_json, json);
+		assert_eq!(serde_json::from_str::<Thing>(json).unwrap(), thing);
+
+		assert!(
+			serde_json::from_str::<Thing>(r#"{"foo":["00"],"bar":[1,2,3],"buzz":[]}"#).is_err()
+		);
+		assert!(
+			serde_json::from_str::<Thing>(r#"{"foo":["00","00"],"bar":[1,2,3,4],"buzz":[]}"#)
+				.is_err()
+		);
+	}
+
+	#[test]
+	fn maps() {
+		#[derive(Deserialize, Serialize, Debug, PartialEq)]
+		struct Thing {
+			#[serde(with = "as_str")]
+			foo: HashMap<String, Hex>,
+			#[serde(with = "as_num")]
+			bar: HashMap<String, Color>,
+			#[serde(with = "as_str")]
+			buzz: BTreeMap<u32, Color>,
+			#[serde(with = "as_num")]
+			fizz: BTreeMap<String, Hex>,
+		}
+
+		let thing = Thing {
+			foo: HashMap::from([("a".to_owned(), "123abc".parse().unwrap())]),
+			bar: HashMap::from([("b".to_owned(), Color::WHITE)]),
+			buzz: BTreeMap::from([(1, Color::BLACK), (2, Color::from_rgb(0x12, 0x3a, 0xbc))]),
+			fizz: BTreeMap::from([("x".to_owned(), 255.into()), ("y".to_owned(), 0.into())]),
+		};
+
+		let thing_json = serde_json::to_string(&thing).unwrap();
+
+		let json = r#"{"foo":{"a":"123abc"},"bar":{"b":16777215},"buzz":{"1":"000000","2":"123abc"},"fizz":{"x":255,"y":0}}"#;
+
+		assert_eq!(thing_json, json);
+		assert_eq!(serde_json::from_str::<Thing>(json).unwrap(), thing);
+
+		assert!(
+			serde_json::from_str::<Thing>(r#"{"foo":{"a":"xyz"},"bar":{},"buzz":{},"fizz":{}}"#)
+				.is_err()
+		);
+		assert!(
+			serde_json::from_str::<Thing>(r#"{"foo":{},"bar":{"b":16777216},"buzz":{},"fizz":{}}"#)
+				.is_err()
+		);
+	}
+
+	#[test]
+	fn nesting() {
+		#[derive(Deserialize, Serialize, Debug, PartialEq)]
+		struct Thing {
+			#[serde(with = "as_str")]
+			foo: Vec<Option<Hex>>,
+			#[serde(with = "as_num")]
+			bar: Vec<Option<Hex>>,
+			#[serde(with = "as_num")]
+			buzz: Option<Vec<[Color; 2]>>,
+			#[serde(with = "as_str")]
+			fizz: HashMap<String, Vec<Option<(Hex, Color)>>>,
+			#[serde(with = "as_num")]
+			bang: Vec<Vec<Hex>>,
+		}
+
+		let thing = Thing {
+			foo: vec![Some("abc".parse().unwrap()), None],
+			bar: vec![None, Some(1194684.into())],
+			buzz: Some(vec![[Color::BLACK, Color::WHITE]]),
+			fizz: HashMap::from([("a".to_owned(), vec![None, Some((255.into(), Color::WHITE))])]),
+			bang: vec![vec![], vec![1.into(), 2.into()]],
+		};
+
+		let thing_json = serde_json::to_string(&thing).unwrap();
+
+		let json = r#"{"foo":["0abc",null],"bar":[null,1194684],"buzz":[[0,16777215]],"fizz":{"a":[null,["ff","ffffff"]]},"bang":[[],[1,2]]}"#;
+
+		assert_eq!(thing_json, json);
+		assert_eq!(serde_json::from_str::<Thing>(json).unwrap(), thing);
+	}
+}
+
+mod fixed_width {
+	use std::collections::HashMap;
+
+	use serde::*;
+
+	use crate::*;
+
+	#[test]
+	fn to_width() {
+		let hex: Hex = "00ff".parse().unwrap();
+
+		assert_eq!(hex.to_width(3).unwrap().to_alphanumeric(), "0000ff");
+		assert_eq!(hex.to_width(1).unwrap().to_alphanumeric(), "ff");
+		assert_eq!(hex.to_width(0), Err(HexError::TooLarge));
+		assert_eq!(Hex::from(0).to_width(0), Ok(Hex::from(vec![])));
+	}
+
+	#[test]
+	fn as_num() {
+		#[derive(Deserialize, Serialize, Debug, PartialEq)]
+		struct Thing {
+			#[serde(with = "as_num_fixed::<3>")]
+			foo: Hex,
+			#[serde(with = "as_num_fixed::<3>")]
+			bar: Option<Vec<Hex>>,
+			#[serde(with = "as_num_fixed::<4>")]
+			buzz: HashMap<String, [Hex; 1]>,
+		}
+
+		let json = r#"{"foo":0,"bar":[255,16777215],"buzz":{"a":[1]}}"#;
+
+		let thing: Thing = serde_json::from_str(json).unwrap();
+
+		assert_eq!(thing.foo.to_alphanumeric(), "000000");
+		let bar = thing.bar.as_ref().unwrap();
+		assert_eq!(bar[0].to_alphanumeric(), "0000ff");
+		assert_eq!(bar[1].to_alphanumeric(), "ffffff");
+		assert_eq!(thing.buzz["a"][0].to_alphanumeric(), "00000001");
+
+		assert_eq!(serde_json::to_string(&thing).unwrap(), json);
+
+		assert!(serde_json::from_str::<Thing>(r#"{"foo":16777216,"bar":null,"buzz":{}}"#).is_err());
+
+		let too_large = Thing {
+			foo: "01000000".parse().unwrap(),
+			bar: None,
+			buzz: HashMap::new(),
+		};
+		assert!(serde_json::to_string(&too_large).is_err());
+	}
+
+	#[test]
+	fn as_str() {
+		#[derive(Deserialize, Serialize, Debug, PartialEq)]
+		struct Thing {
+			#[serde(with = "as_str_fixed::<3>")]
+			foo: Hex,
+			#[serde(with = "as_str_fixed::<3>")]
+			bar: Vec<Option<Hex>>,
+			#[serde(with = "as_str_fixed::<1>")]
+			buzz: (Hex, Color),
+		}
+
+		let thing: Thing = serde_json::from_str(
+			r#"{"foo":"f","bar":["0000abcdef",null],"buzz":["000a","010203"]}"#,
+		)
+		.unwrap();
+
+		assert_eq!(thing.foo.to_alphanumeric(), "00000f");
+		assert_eq!(thing.bar[0].as_ref().unwrap().to_alphanumeric(), "abcdef");
+		assert_eq!(thing.buzz.0.to_alphanumeric(), "0a");
+		assert_eq!(thing.buzz.1, Color::from_rgb(1, 2, 3));
+
+		let thing = Thing {
+			foo: "ff".parse().unwrap(),
+			bar: vec![None],
+			buzz: ("0001".parse().unwrap(), Color::BLACK),
+		};
+
+		let thing_json = serde_json::to_string(&thing).unwrap();
+
+		let json = r#"{"foo":"0000ff","bar":[null],"buzz":["01","000000"]}"#;
+
+		assert_eq!(thing_json, json);
+
+		assert!(
+			serde_json::from_str::<Thing>(r#"{"foo":"01000000","bar":[],"buzz":["00","000000"]}"#)
+				.is_err()
+		);
+		assert!(
+			serde_json::from_str::<Thing>(r#"{"foo":"00","bar":[],"buzz":["0100","000000"]}"#)
+				.is_err()
+		);
+	}
+}
+
+mod color {
+	use serde::*;
+
+	use crate::*;
+
+	#[test]
+	fn conversions() {
+		let color = Color::from_rgb(0x12, 0x3a, 0xbc);
+
+		assert_eq!(color.to_css(), "#123abc");
+		assert_eq!(color.to_css_alpha(0x1a), "#123abc1a");
+		assert_eq!("#123abc".parse(), Ok(color));
+		assert_eq!("123abc".parse(), Ok(color));
+		assert_eq!(Color::try_from(0x123abc), Ok(color));
+		assert_eq!(Color::try_from(0x1000000), Err(HexError::TooLarge));
+		assert_eq!(u32::from(color), 0x123abc);
+		assert_eq!(<(u8, u8, u8)>::from(color), (0x12, 0x3a, 0xbc));
+		assert_eq!(Hex::from(color).to_alphanumeric(), "123abc");
+		assert_eq!(Color::try_from(&Hex::from(0)), Ok(Color::BLACK));
+	}
+
+	#[test]
+	fn hsl() {
+		assert_eq!(Color::from_rgb(255, 0, 0).to_hsl(), (0.0, 1.0, 0.5));
+		assert_eq!(Color::WHITE.to_hsl(), (0.0, 0.0, 1.0));
+		assert_eq!(Color::from_hsl(120.0, 1.0, 0.5), Color::from_rgb(0, 255, 0));
+		assert_eq!(
+			Color::from_hsl(-120.0, 1.0, 0.25),
+			Color::from_rgb(0, 0, 128)
+		);
+
+		for color in [0x5865f2, 0x23a55a, 0xf0b232, 0xda373c, 0x123abc] {
+			let color = Color::try_from(color).unwrap();
+			let (h, s, l) = color.to_hsl();
+			assert_eq!(Color::from_hsl(h, s, l), color);
+		}
+	}
+
+	#[test]
+	fn contrast() {
+		assert_eq!(Color::BLACK.luminance(), 0.0);
+		assert_eq!(Color::WHITE.luminance(), 1.0);
+		assert_eq!(Color::BLACK.contrast(&Color::WHITE), 21.0);
+		assert_eq!(Color::WHITE.contrast(&Color::WHITE), 1.0);
+
+		assert_eq!(
+			Color::from_rgb(0xf0, 0xb2, 0x32).readable_text(),
+			Color::BLACK
+		);
+		assert_eq!(
+			Color::from_rgb(0x58, 0x65, 0xf2).readable_text(),
+			Color::WHITE
+		);
+	}
+
+	#[test]
+	fn serialization() {
+		#[derive(Deserialize, Serialize, Debug, PartialEq)]
+		struct Thing {
+			#[serde(with = "as_num")]
+			foo: Color,
+			#[serde(with = "as_str")]
+			bar: Option<Color>,
+		}
+
+		let thing = Thing {
+			foo: Color::from_rgb(0x12, 0x3a, 0xbc),
+			bar: Some(Color::WHITE),
+		};
+
+		let thing_json = serde_json::to_string(&thing).unwrap();
+
+		let json = r#"{"foo":1194684,"bar":"ffffff"}"#;
+
+		assert_eq!(thing_json, json);
+		assert_eq!(serde_json::from_str::<Thing>(json).unwrap(), thing);
+
+		assert!(serde_json::from_str::<Thing>(r#"{"foo":16777216,"bar":null}"#).is_err());
+		assert!(serde_json::from_str::<Thing>(r#"{"foo":0,"bar":"1ffffff"}"#).is_err());
+	}
 }
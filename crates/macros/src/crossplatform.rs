@@ -1,39 +1,77 @@
-/// Create the same constant with different values for different platforms, linxu and bsds are groups together
-///
-/// # Examples
-///
-/// ```rust
-/// crossplatform_const!(
-/// 	PLATFORM_NAME,
-/// 	&str,
-/// 	"linux/bsd",
-/// 	"macos",
-/// 	"windows",
-/// 	"ios",
-/// 	"android"
-/// );
-/// ```
-#[macro_export]
-macro_rules! crossplatform_const {
-	($name:ident, $type:ty, $linux:expr, $macos:expr, $windows:expr, $ios:expr, $android:expr) => {
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, Ident, Result, Token, Type};
+
+pub struct CrossplatformConst {
+	name: Ident,
+	ty: Type,
+	linux: Expr,
+	macos: Expr,
+	windows: Expr,
+	ios: Expr,
+	android: Expr,
+}
+
+impl Parse for CrossplatformConst {
+	fn parse(input: ParseStream) -> Result<Self> {
+		let name = input.parse()?;
+		input.parse::<Token![,]>()?;
+		let ty = input.parse()?;
+
+		let mut values = Vec::with_capacity(5);
+		for _ in 0..5 {
+			input.parse::<Token![,]>()?;
+			values.push(input.parse::<Expr>()?);
+		}
+		input.parse::<Option<Token![,]>>()?;
+
+		let [linux, macos, windows, ios, android] = values
+			.try_into()
+			.unwrap_or_else(|_| unreachable!("exactly 5 values were parsed"));
+
+		Ok(CrossplatformConst {
+			name,
+			ty,
+			linux,
+			macos,
+			windows,
+			ios,
+			android,
+		})
+	}
+}
+
+pub fn expand(input: CrossplatformConst) -> TokenStream {
+	let CrossplatformConst {
+		name,
+		ty,
+		linux,
+		macos,
+		windows,
+		ios,
+		android,
+	} = input;
+
+	quote! {
 		#[cfg(any(
 			target_os = "linux",
 			target_os = "freebsd",
 			target_os = "openbsd",
 			target_os = "netbsd"
 		))]
-		pub const $name: $type = $linux;
+		pub const #name: #ty = #linux;
 
 		#[cfg(target_os = "macos")]
-		pub const $name: $type = $macos;
+		pub const #name: #ty = #macos;
 
 		#[cfg(target_os = "windows")]
-		pub const $name: $type = $windows;
+		pub const #name: #ty = #windows;
 
 		#[cfg(target_os = "ios")]
-		pub const $name: $type = $ios;
+		pub const #name: #ty = #ios;
 
 		#[cfg(target_os = "android")]
-		pub const $name: $type = $android;
-	};
+		pub const #name: #ty = #android;
+	}
 }
@@ -0,0 +1,193 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{Attribute, Error, Ident, LitStr, Result, Token, Type};
+
+const METHODS: [&str; 5] = ["GET", "POST", "PUT", "PATCH", "DELETE"];
+
+pub struct Endpoint {
+	attrs: Vec<Attribute>,
+	name: Ident,
+	method: Ident,
+	path: LitStr,
+	query: Option<Type>,
+	body: Option<Type>,
+	response: Option<Type>,
+}
+
+impl Parse for Endpoint {
+	fn parse(input: ParseStream) -> Result<Self> {
+		let attrs = input.call(Attribute::parse_outer)?;
+		let name = input.parse()?;
+		input.parse::<Token![:]>()?;
+
+		let method: Ident = input.parse()?;
+		if !METHODS.iter().any(|m| method == m) {
+			return Err(Error::new_spanned(
+				&method,
+				format!("unknown method, expected one of {}", METHODS.join(", ")),
+			));
+		}
+		let path = input.parse()?;
+
+		let mut query = None;
+		let mut body = None;
+		while input.peek(Ident) {
+			let key: Ident = input.parse()?;
+			input.parse::<Token![=]>()?;
+			let slot = if key == "query" {
+				&mut query
+			} else if key == "body" {
+				&mut body
+			} else {
+				return Err(Error::new_spanned(
+					&key,
+					"unknown endpoint option, expected `query` or `body`",
+				));
+			};
+			if slot.replace(input.parse()?).is_some() {
+				return Err(Error::new_spanned(&key, format!("`{key}` is set twice")));
+			}
+		}
+
+		let response = if input.parse::<Option<Token![->]>>()?.is_some() {
+			Some(input.parse()?)
+		} else {
+			None
+		};
+		input.parse::<Option<Token![;]>>()?;
+
+		Ok(Endpoint {
+			attrs,
+			name,
+			method,
+			path,
+			query,
+			body,
+			response,
+		})
+	}
+}
+
+/// A piece of the path, split on its `{param}` placeholders
+enum Segment {
+	Text(String),
+	Param(Ident),
+}
+
+fn segments(path: &LitStr) -> Result<Vec<Segment>> {
+	let value = path.value();
+	if !value.starts_with('/') {
+		return Err(Error::new_spanned(path, "endpoint paths start with `/`"));
+	}
+
+	let mut segments = Vec::new();
+	let mut rest = value.as_str();
+	while let Some(start) = rest.find('{') {
+		let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+			return Err(Error::new_spanned(path, "unclosed `{` in the path"));
+		};
+		if start > 0 {
+			segments.push(Segment::Text(rest[..start].to_owned()));
+		}
+
+		let param = syn::parse_str::<Ident>(&rest[start + 1..end]).map_err(|_| {
+			Error::new_spanned(
+				path,
+				format!("`{}` isn't a parameter name", &rest[start + 1..end]),
+			)
+		})?;
+		segments.push(Segment::Param(Ident::new(&param.to_string(), path.span())));
+		rest = &rest[end + 1..];
+	}
+	if rest.contains('}') {
+		return Err(Error::new_spanned(path, "unopened `}` in the path"));
+	}
+	if !rest.is_empty() {
+		segments.push(Segment::Text(rest.to_owned()));
+	}
+
+	Ok(segments)
+}
+
+fn or_unit(ty: Option<&Type>) -> TokenStream {
+	ty.map_or_else(|| quote!(()), ToTokens::to_token_stream)
+}
+
+pub fn expand(input: Endpoint) -> Result<TokenStream> {
+	let Endpoint {
+		attrs,
+		name,
+		method,
+		path,
+		query,
+		body,
+		response,
+	} = input;
+
+	let segments = segments(&path)?;
+	let params: Vec<&Ident> = segments
+		.iter()
+		.filter_map(|s| match s {
+			| Segment::Param(param) => Some(param),
+			| Segment::Text(_) => None,
+		})
+		.collect();
+
+	let builder = if params.is_empty() && query.is_none() {
+		quote! {
+			#(#attrs)*
+			pub const #name: &str = #path;
+		}
+	} else {
+		// placeholders become `{}` and are filled in with the encoded parameters
+		let mut format = String::new();
+		for segment in &segments {
+			match segment {
+				| Segment::Text(text) => format.push_str(text),
+				| Segment::Param(_) => format.push_str("{}"),
+			}
+		}
+		let mut args: Vec<TokenStream> = params
+			.iter()
+			.map(|param| quote!(crate::encode_path_segment(&#param.to_string())))
+			.collect();
+		let mut query_param = None;
+		if let Some(query) = &query {
+			format.push_str("{}");
+			args.push(quote!(crate::serialize_query_params(query_params)));
+			query_param = Some(quote!(query_params: &#query,));
+		}
+		let format = LitStr::new(&format, Span::call_site());
+
+		quote! {
+			#(#attrs)*
+			pub fn #name(
+				#(#params: impl ::core::fmt::Display,)*
+				#query_param
+			) -> ::std::string::String {
+				::std::format!(#format, #(#args),*)
+			}
+		}
+	};
+
+	let doc = format!("The method and types of the `{name}` endpoint");
+	let query = or_unit(query.as_ref());
+	let body = or_unit(body.as_ref());
+	let response = or_unit(response.as_ref());
+
+	Ok(quote! {
+		#builder
+
+		#[doc = #doc]
+		pub struct #name {}
+
+		impl crate::endpoints::Endpoint for #name {
+			const METHOD: crate::endpoints::Method = crate::endpoints::Method::#method;
+			const PATH: &'static str = #path;
+			type Query = #query;
+			type Request = #body;
+			type Response = #response;
+		}
+	})
+}
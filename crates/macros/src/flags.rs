@@ -0,0 +1,352 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Attribute, Error, Expr, Ident, LitStr, Result, Token, Type, Visibility, braced};
+
+pub struct Bitflags {
+	attrs: Vec<Attribute>,
+	vis: Visibility,
+	name: Ident,
+	ty: Type,
+	/// whether the flags are serialized as a stringified integer instead of a number
+	string: bool,
+	flags: Vec<Flag>,
+}
+
+struct Flag {
+	attrs: Vec<Attribute>,
+	name: Ident,
+	value: Expr,
+}
+
+impl Parse for Bitflags {
+	fn parse(input: ParseStream) -> Result<Self> {
+		let mut attrs = input.call(Attribute::parse_outer)?;
+		let mut string = false;
+		for attr in attrs.iter().filter(|a| a.path().is_ident("flags")) {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("string") {
+					string = true;
+					Ok(())
+				} else {
+					Err(meta.error("unknown flags attribute, expected `string`"))
+				}
+			})?;
+		}
+		attrs.retain(|a| !a.path().is_ident("flags"));
+
+		let vis = input.parse()?;
+		input.parse::<Token![struct]>()?;
+		let name: Ident = input.parse()?;
+		input.parse::<Token![:]>()?;
+		let ty = input.parse()?;
+
+		let content;
+		braced!(content in input);
+		let mut flags = Vec::new();
+		while !content.is_empty() {
+			let attrs = content.call(Attribute::parse_outer)?;
+			content.parse::<Token![const]>()?;
+			let name = content.parse()?;
+			content.parse::<Token![=]>()?;
+			let value = content.parse()?;
+			content.parse::<Token![;]>()?;
+
+			flags.push(Flag {
+				attrs,
+				name,
+				value,
+			});
+		}
+
+		if flags.is_empty() {
+			return Err(Error::new(name.span(), "flags need at least one flag"));
+		}
+
+		Ok(Bitflags {
+			attrs,
+			vis,
+			name,
+			ty,
+			string,
+			flags,
+		})
+	}
+}
+
+pub fn expand(input: Bitflags) -> TokenStream {
+	let Bitflags {
+		attrs,
+		vis,
+		name,
+		ty,
+		string,
+		flags,
+	} = input;
+
+	let consts = flags.iter().map(|flag| {
+		let Flag {
+			attrs,
+			name,
+			value,
+		} = flag;
+		quote! {
+			#(#attrs)*
+			pub const #name: Self = Self(#value);
+		}
+	});
+	let names = flags.iter().map(|f| &f.name);
+	let name_strs = flags
+		.iter()
+		.map(|f| LitStr::new(&f.name.to_string(), f.name.span()));
+
+	let serialize = if string {
+		quote!(serializer.collect_str(&self.0))
+	} else {
+		quote!(::serde::Serialize::serialize(&self.0, serializer))
+	};
+	let expecting = format!("{name} as a string or integer");
+
+	quote! {
+		#(#attrs)*
+		#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+		#vis struct #name(pub #ty);
+
+		impl #name {
+			#(#consts)*
+
+			/// Every known flag with its name, in the order they're declared
+			pub const FLAGS: &'static [(&'static str, Self)] = &[#((#name_strs, Self::#names)),*];
+
+			/// No flags
+			pub const fn empty() -> Self {
+				Self(0)
+			}
+
+			/// Whether every flag in `other` is set
+			pub const fn contains(
+				self,
+				other: Self,
+			) -> bool {
+				self.0 & other.0 == other.0
+			}
+
+			/// Whether any flag in `other` is set
+			pub const fn intersects(
+				self,
+				other: Self,
+			) -> bool {
+				self.0 & other.0 != 0
+			}
+
+			pub const fn is_empty(self) -> bool {
+				self.0 == 0
+			}
+
+			pub fn insert(
+				&mut self,
+				other: Self,
+			) {
+				self.0 |= other.0;
+			}
+
+			pub fn remove(
+				&mut self,
+				other: Self,
+			) {
+				self.0 &= !other.0;
+			}
+
+			/// Inserts the flags in `other` if `value` is true, removes them otherwise
+			pub fn set(
+				&mut self,
+				other: Self,
+				value: bool,
+			) {
+				if value {
+					self.insert(other);
+				} else {
+					self.remove(other);
+				}
+			}
+
+			/// The bits that aren't any of the known flags, kept so they aren't lost when sent back
+			pub fn unknown_bits(self) -> #ty {
+				Self::FLAGS
+					.iter()
+					.fold(self.0, |bits, (_, flag)| bits & !flag.0)
+			}
+
+			/// Only the known flags
+			pub fn truncate(self) -> Self {
+				Self(self.0 & !self.unknown_bits())
+			}
+		}
+
+		impl ::core::ops::BitOr for #name {
+			type Output = Self;
+
+			fn bitor(
+				self,
+				rhs: Self,
+			) -> Self {
+				Self(self.0 | rhs.0)
+			}
+		}
+
+		impl ::core::ops::BitOrAssign for #name {
+			fn bitor_assign(
+				&mut self,
+				rhs: Self,
+			) {
+				self.0 |= rhs.0;
+			}
+		}
+
+		impl ::core::ops::BitAnd for #name {
+			type Output = Self;
+
+			fn bitand(
+				self,
+				rhs: Self,
+			) -> Self {
+				Self(self.0 & rhs.0)
+			}
+		}
+
+		impl ::core::ops::BitAndAssign for #name {
+			fn bitand_assign(
+				&mut self,
+				rhs: Self,
+			) {
+				self.0 &= rhs.0;
+			}
+		}
+
+		impl ::core::ops::Not for #name {
+			type Output = Self;
+
+			fn not(self) -> Self {
+				Self(!self.0)
+			}
+		}
+
+		/// The names of the set flags separated by ` | `, with any unknown bits last in hex
+		impl ::core::fmt::Display for #name {
+			fn fmt(
+				&self,
+				f: &mut ::core::fmt::Formatter<'_>,
+			) -> ::core::fmt::Result {
+				let mut first = true;
+				let mut separator = |f: &mut ::core::fmt::Formatter<'_>| {
+					if ::core::mem::take(&mut first) {
+						::core::fmt::Result::Ok(())
+					} else {
+						f.write_str(" | ")
+					}
+				};
+
+				for (name, flag) in Self::FLAGS {
+					if !flag.is_empty() && self.contains(*flag) {
+						separator(f)?;
+						f.write_str(name)?;
+					}
+				}
+
+				let unknown = self.unknown_bits();
+				if unknown != 0 {
+					separator(f)?;
+					write!(f, "{unknown:#x}")?;
+				}
+
+				::core::fmt::Result::Ok(())
+			}
+		}
+
+		impl ::core::fmt::Debug for #name {
+			fn fmt(
+				&self,
+				f: &mut ::core::fmt::Formatter<'_>,
+			) -> ::core::fmt::Result {
+				write!(f, "{}({})", stringify!(#name), self)
+			}
+		}
+
+		/// Parses the flags as an integer, like they're sent as strings
+		impl ::core::str::FromStr for #name {
+			type Err = ::core::num::ParseIntError;
+
+			fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+				s.parse().map(Self)
+			}
+		}
+
+		impl ::serde::Serialize for #name {
+			fn serialize<S>(
+				&self,
+				serializer: S,
+			) -> ::core::result::Result<S::Ok, S::Error>
+			where
+				S: ::serde::Serializer,
+			{
+				#serialize
+			}
+		}
+
+		impl<'de> ::serde::Deserialize<'de> for #name {
+			fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+			where
+				D: ::serde::Deserializer<'de>,
+			{
+				struct FlagsVisitor;
+
+				impl<'de> ::serde::de::Visitor<'de> for FlagsVisitor {
+					type Value = #name;
+
+					fn expecting(
+						&self,
+						formatter: &mut ::core::fmt::Formatter,
+					) -> ::core::fmt::Result {
+						formatter.write_str(#expecting)
+					}
+
+					fn visit_str<E>(
+						self,
+						value: &str,
+					) -> ::core::result::Result<Self::Value, E>
+					where
+						E: ::serde::de::Error,
+					{
+						value.parse().map_err(E::custom)
+					}
+
+					fn visit_u64<E>(
+						self,
+						value: u64,
+					) -> ::core::result::Result<Self::Value, E>
+					where
+						E: ::serde::de::Error,
+					{
+						::core::convert::TryFrom::try_from(value)
+							.map(#name)
+							.map_err(E::custom)
+					}
+
+					fn visit_i64<E>(
+						self,
+						value: i64,
+					) -> ::core::result::Result<Self::Value, E>
+					where
+						E: ::serde::de::Error,
+					{
+						::core::convert::TryFrom::try_from(value)
+							.map(#name)
+							.map_err(E::custom)
+					}
+				}
+
+				deserializer.deserialize_any(FlagsVisitor)
+			}
+		}
+	}
+}
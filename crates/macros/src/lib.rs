@@ -1,2 +1,164 @@
-#[macro_use]
-pub mod crossplatform;
+use proc_macro::TokenStream;
+use syn::{DeriveInput, Error, parse_macro_input};
+
+use crate::crossplatform::CrossplatformConst;
+use crate::endpoint::Endpoint;
+use crate::flags::Bitflags;
+
+mod crossplatform;
+mod endpoint;
+mod flags;
+mod tuple;
+
+/// Create the same constant with different values for different platforms, linxu and bsds are groups together
+///
+/// # Examples
+///
+/// ```rust
+/// use cowcord_macros::crossplatform_const;
+///
+/// crossplatform_const!(
+/// 	PLATFORM_NAME,
+/// 	&str,
+/// 	"linux/bsd",
+/// 	"macos",
+/// 	"windows",
+/// 	"ios",
+/// 	"android"
+/// );
+/// ```
+#[proc_macro]
+pub fn crossplatform_const(input: TokenStream) -> TokenStream {
+	crossplatform::expand(parse_macro_input!(input as CrossplatformConst)).into()
+}
+
+/// Define a discord api endpoint, only usable inside of `discord-api`
+///
+/// Generates a function with the endpoint's name that builds its path, taking the `{param}` placeholders in order and then the query if it has one.
+/// Parameters can be anything that's [`Display`](std::fmt::Display) and are percent encoded, so they always stay a single path segment.
+/// Endpoints without parameters or a query are a `&str` const instead.
+///
+/// A type with the same name implements `Endpoint`, associating the method with the query, request body and response body types, which are `()` when left out
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use cowcord_macros::endpoint;
+///
+/// endpoint! {
+/// 	/// Returns a list of message objects in the channel
+/// 	GET_MESSAGES: GET "/channels/{channel_id}/messages" query = GetMessagesQuery -> Vec<Message>
+/// }
+///
+/// endpoint! {
+/// 	/// Edits a message sent by the current user, returns the updated message object
+/// 	EDIT_MESSAGE: PATCH "/channels/{channel_id}/messages/{message_id}" body = EditMessageRequest -> Message
+/// }
+///
+/// assert_eq!(
+/// 	GET_MESSAGES(1234, &GetMessagesQuery::default()),
+/// 	"/channels/1234/messages"
+/// );
+/// assert_eq!(<EDIT_MESSAGE as Endpoint>::METHOD, Method::PATCH);
+/// ```
+#[proc_macro]
+pub fn endpoint(input: TokenStream) -> TokenStream {
+	endpoint::expand(parse_macro_input!(input as Endpoint))
+		.unwrap_or_else(Error::into_compile_error)
+		.into()
+}
+
+/// Create a bitflags type with a constant for each flag
+///
+/// The type is a tuple struct around the integer, so unknown bits are kept as they are instead of being dropped or failing to deserialize.
+/// It's serialized as a number, or as a stringified integer with `#[flags(string)]`, and deserialized from either.
+/// [`Display`](std::fmt::Display) lists the names of the set flags, with any unknown bits last in hex
+///
+/// # Examples
+///
+/// ```rust
+/// use cowcord_macros::bitflags;
+///
+/// bitflags! {
+/// 	/// How a message is shown
+/// 	#[flags(string)]
+/// 	pub struct MessageFlags: u64 {
+/// 		/// The message has no embeds
+/// 		const SUPPRESS_EMBEDS = 1 << 2;
+/// 		/// The message doesn't notify anyone
+/// 		const SUPPRESS_NOTIFICATIONS = 1 << 12;
+/// 	}
+/// }
+///
+/// let flags: MessageFlags = serde_json::from_str("4100").unwrap();
+///
+/// assert!(flags.contains(MessageFlags::SUPPRESS_EMBEDS));
+/// assert_eq!(
+/// 	flags.to_string(),
+/// 	"SUPPRESS_EMBEDS | SUPPRESS_NOTIFICATIONS"
+/// );
+/// assert_eq!(serde_json::to_string(&flags).unwrap(), r#""4100""#);
+///
+/// let unknown = MessageFlags(1 << 40) | MessageFlags::SUPPRESS_EMBEDS;
+/// assert_eq!(unknown.to_string(), "SUPPRESS_EMBEDS | 0x10000000000");
+/// ```
+#[proc_macro]
+pub fn bitflags(input: TokenStream) -> TokenStream {
+	flags::expand(parse_macro_input!(input as Bitflags)).into()
+}
+
+/// Serialize a struct with named fields as an array of its fields, in the order they're declared
+///
+/// # Examples
+///
+/// ```rust
+/// use cowcord_macros::SerializeTuple;
+///
+/// #[derive(SerializeTuple)]
+/// struct Range {
+/// 	bucket: i64,
+/// 	name: Option<String>,
+/// }
+///
+/// let range = Range {
+/// 	bucket: 1,
+/// 	name: None,
+/// };
+///
+/// assert_eq!(serde_json::to_string(&range).unwrap(), "[1,null]");
+/// ```
+#[proc_macro_derive(SerializeTuple, attributes(tuple))]
+pub fn serialize_tuple(input: TokenStream) -> TokenStream {
+	tuple::serialize(parse_macro_input!(input as DeriveInput))
+		.unwrap_or_else(Error::into_compile_error)
+		.into()
+}
+
+/// Deserialize a struct with named fields from an array of its fields, in the order they're declared
+///
+/// Fields marked `#[tuple(default)]` can be left out of the end of the array, and extra elements are ignored
+///
+/// # Examples
+///
+/// ```rust
+/// use cowcord_macros::DeserializeTuple;
+///
+/// #[derive(DeserializeTuple)]
+/// struct Experiment {
+/// 	hash: i64,
+/// 	revision: i64,
+/// 	#[tuple(default)]
+/// 	holdout_name: Option<String>,
+/// }
+///
+/// let experiment: Experiment = serde_json::from_str("[1234, 2]").unwrap();
+///
+/// assert_eq!(experiment.hash, 1234);
+/// assert_eq!(experiment.holdout_name, None);
+/// ```
+#[proc_macro_derive(DeserializeTuple, attributes(tuple))]
+pub fn deserialize_tuple(input: TokenStream) -> TokenStream {
+	tuple::deserialize(parse_macro_input!(input as DeriveInput))
+		.unwrap_or_else(Error::into_compile_error)
+		.into()
+}
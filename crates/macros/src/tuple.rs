@@ -0,0 +1,165 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Error, Fields, Ident, Result, Type};
+
+struct Field {
+	name: Ident,
+	ty: Type,
+	/// whether the field can be left out of the end of the array, then it's [`Default::default`]
+	default: bool,
+}
+
+fn fields(input: &DeriveInput) -> Result<Vec<Field>> {
+	if !input.generics.params.is_empty() {
+		return Err(Error::new_spanned(
+			&input.generics,
+			"structs encoded as arrays can't have generics",
+		));
+	}
+	let Data::Struct(data) = &input.data else {
+		return Err(Error::new_spanned(
+			&input.ident,
+			"only structs can be encoded as arrays",
+		));
+	};
+	let Fields::Named(named) = &data.fields else {
+		return Err(Error::new_spanned(
+			&data.fields,
+			"only structs with named fields can be encoded as arrays",
+		));
+	};
+
+	let mut fields: Vec<Field> = Vec::with_capacity(named.named.len());
+	for field in &named.named {
+		let mut default = false;
+		for attr in field.attrs.iter().filter(|a| a.path().is_ident("tuple")) {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("default") {
+					default = true;
+					Ok(())
+				} else {
+					Err(meta.error("unknown tuple attribute, expected `default`"))
+				}
+			})?;
+		}
+
+		// elements can only be left out of the end of the array
+		if !default && fields.last().is_some_and(|f| f.default) {
+			return Err(Error::new_spanned(
+				field,
+				"fields after a `#[tuple(default)]` field must also be `#[tuple(default)]`",
+			));
+		}
+
+		fields.push(Field {
+			name: field.ident.clone().expect("named fields have names"),
+			ty: field.ty.clone(),
+			default,
+		});
+	}
+
+	Ok(fields)
+}
+
+pub fn serialize(input: DeriveInput) -> Result<TokenStream> {
+	let name = &input.ident;
+	let fields = fields(&input)?;
+	let len = fields.len();
+	let names = fields.iter().map(|f| &f.name);
+
+	Ok(quote! {
+		impl ::serde::Serialize for #name {
+			fn serialize<S>(
+				&self,
+				serializer: S,
+			) -> ::core::result::Result<S::Ok, S::Error>
+			where
+				S: ::serde::Serializer,
+			{
+				use ::serde::ser::SerializeTuple;
+
+				let mut tuple = serializer.serialize_tuple(#len)?;
+				#(tuple.serialize_element(&self.#names)?;)*
+				tuple.end()
+			}
+		}
+	})
+}
+
+pub fn deserialize(input: DeriveInput) -> Result<TokenStream> {
+	let name = &input.ident;
+	let fields = fields(&input)?;
+
+	let required = fields.iter().filter(|f| !f.default).count();
+	let expecting = if required == fields.len() {
+		format!("an array of {required} elements")
+	} else {
+		format!("an array of {required} to {} elements", fields.len())
+	};
+
+	let locals: Vec<Ident> = fields
+		.iter()
+		.map(|f| format_ident!("field_{}", f.name))
+		.collect();
+	let elements = fields
+		.iter()
+		.zip(&locals)
+		.enumerate()
+		.map(|(index, (field, local))| {
+			let ty = &field.ty;
+			let missing = if field.default {
+				quote!(::core::default::Default::default())
+			} else {
+				quote!(return ::core::result::Result::Err(::serde::de::Error::invalid_length(#index, &self)))
+			};
+
+			quote! {
+				let #local = match seq.next_element::<#ty>()? {
+					::core::option::Option::Some(value) => value,
+					::core::option::Option::None => #missing,
+				};
+			}
+		});
+	let names = fields.iter().map(|f| &f.name);
+
+	Ok(quote! {
+		impl<'de> ::serde::Deserialize<'de> for #name {
+			fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+			where
+				D: ::serde::Deserializer<'de>,
+			{
+				struct TupleVisitor;
+
+				impl<'de> ::serde::de::Visitor<'de> for TupleVisitor {
+					type Value = #name;
+
+					fn expecting(
+						&self,
+						formatter: &mut ::core::fmt::Formatter,
+					) -> ::core::fmt::Result {
+						formatter.write_str(#expecting)
+					}
+
+					fn visit_seq<A>(
+						self,
+						mut seq: A,
+					) -> ::core::result::Result<Self::Value, A::Error>
+					where
+						A: ::serde::de::SeqAccess<'de>,
+					{
+						#(#elements)*
+
+						// newer versions of the array can have more elements
+						while seq.next_element::<::serde::de::IgnoredAny>()?.is_some() {}
+
+						::core::result::Result::Ok(#name {
+							#(#names: #locals,)*
+						})
+					}
+				}
+
+				deserializer.deserialize_seq(TupleVisitor)
+			}
+		}
+	})
+}
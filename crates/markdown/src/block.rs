@@ -0,0 +1,231 @@
+use crate::Node;
+use crate::inline::parse_inline;
+
+/// Splits the content into block level elements, everything else is parsed inline
+///
+/// `allow_quotes` is false inside block quotes since discord doesnt nest them
+pub(crate) fn parse_blocks(
+	input: &str,
+	allow_quotes: bool,
+) -> Vec<Node> {
+	let mut nodes = Vec::new();
+	let mut paragraph_start = None;
+	let mut in_code_block = false;
+	let mut pos = 0;
+
+	while pos < input.len() {
+		let line_end = input[pos..].find('\n').map_or(input.len(), |i| pos + i);
+		let line = &input[pos..line_end];
+
+		// block syntax inside a code block is just code
+		if !in_code_block && let Some((block, end)) = match_block(input, pos, line, allow_quotes) {
+			if let Some(start) = paragraph_start.take() {
+				let mut paragraph = &input[start..pos];
+				// the last newline only ends the line before the block, unless the paragraph is just blank lines
+				if !paragraph.trim_matches('\n').is_empty() {
+					paragraph = paragraph.strip_suffix('\n').unwrap_or(paragraph);
+				}
+				nodes.extend(parse_inline(paragraph, false));
+			}
+			nodes.push(block);
+			pos = end;
+			continue;
+		}
+
+		paragraph_start.get_or_insert(pos);
+		if line.matches("```").count() % 2 == 1 {
+			in_code_block = !in_code_block;
+		}
+		pos = (line_end + 1).min(input.len());
+	}
+
+	if let Some(start) = paragraph_start {
+		nodes.extend(parse_inline(&input[start..], false));
+	}
+
+	nodes
+}
+
+/// Tries every block rule on the line starting at `pos`, returns the node and where the next line starts
+fn match_block(
+	input: &str,
+	pos: usize,
+	line: &str,
+	allow_quotes: bool,
+) -> Option<(Node, usize)> {
+	let next_line = (pos + line.len() + 1).min(input.len());
+	let trimmed = line.trim_start_matches(' ');
+
+	if let Some(content) = trimmed.strip_prefix("-# ") {
+		let content = content.trim();
+		if !content.is_empty() {
+			return Some((Node::Subtext(parse_inline(content, false)), next_line));
+		}
+	}
+
+	if let Some(heading) = heading(trimmed) {
+		return Some((heading, next_line));
+	}
+
+	if allow_quotes {
+		if let Some(content) = quote_marker(trimmed, ">>>") {
+			let content_start = pos + line.len() - content.len();
+			return Some((
+				Node::BlockQuote(parse_blocks(&input[content_start..], false)),
+				input.len(),
+			));
+		}
+
+		if quote_marker(trimmed, ">").is_some() {
+			return Some(block_quote(input, pos));
+		}
+	}
+
+	if list_item(line).is_some() {
+		return Some(list(input, pos));
+	}
+
+	None
+}
+
+/// `# text` to `### text`, trailing `#`s are left out
+fn heading(line: &str) -> Option<Node> {
+	let level = line.len() - line.trim_start_matches('#').len();
+	if !(1..=3).contains(&level) {
+		return None;
+	}
+
+	let content = &line[level..];
+	if !content.starts_with(char::is_whitespace) {
+		return None;
+	}
+	let content = content.trim().trim_end_matches('#').trim_end();
+	if content.is_empty() || content.starts_with('#') {
+		return None;
+	}
+
+	Some(Node::Heading {
+		level: level as u8,
+		content: parse_inline(content, false),
+	})
+}
+
+/// the content after a `>` or `>>>` marker, which needs at least one space after it
+fn quote_marker<'a>(
+	line: &'a str,
+	marker: &str,
+) -> Option<&'a str> {
+	let content = line.strip_prefix(marker)?;
+	if marker == ">" && content.starts_with(">>") {
+		return None;
+	}
+
+	let without_spaces = content.trim_start_matches(' ');
+	(without_spaces.len() < content.len()).then_some(without_spaces)
+}
+
+/// consecutive `> ` lines are joined into a single quote
+fn block_quote(
+	input: &str,
+	start: usize,
+) -> (Node, usize) {
+	let mut lines = Vec::new();
+	let mut pos = start;
+
+	while pos < input.len() {
+		let line_end = input[pos..].find('\n').map_or(input.len(), |i| pos + i);
+		let Some(content) = quote_marker(input[pos..line_end].trim_start_matches(' '), ">") else {
+			break;
+		};
+
+		lines.push(content);
+		pos = (line_end + 1).min(input.len());
+	}
+
+	(
+		Node::BlockQuote(parse_blocks(&lines.join("\n"), false)),
+		pos,
+	)
+}
+
+struct ListItem<'a> {
+	indent: usize,
+	/// the item number for ordered lists
+	number: Option<u64>,
+	content: &'a str,
+}
+
+/// `- text`, `* text` or `1. text`, optionally indented with spaces
+fn list_item(line: &str) -> Option<ListItem<'_>> {
+	let trimmed = line.trim_start_matches(' ');
+	let indent = line.len() - trimmed.len();
+
+	let (number, rest) = if let Some(rest) = trimmed.strip_prefix(['-', '*']) {
+		(None, rest)
+	} else {
+		let digits = trimmed.len()
+			- trimmed
+				.trim_start_matches(|c: char| c.is_ascii_digit())
+				.len();
+		let rest = trimmed[digits..].strip_prefix('.')?;
+		(Some(trimmed[..digits].parse().ok()?), rest)
+	};
+
+	let content = rest.trim_start_matches(' ');
+	if content.len() == rest.len() || content.trim().is_empty() {
+		return None;
+	}
+
+	Some(ListItem {
+		indent,
+		number,
+		content,
+	})
+}
+
+/// consecutive list item lines, items indented further than the first one are nested under the item above them
+fn list(
+	input: &str,
+	start: usize,
+) -> (Node, usize) {
+	let mut items = Vec::new();
+	let mut pos = start;
+
+	while pos < input.len() {
+		let line_end = input[pos..].find('\n').map_or(input.len(), |i| pos + i);
+		let Some(item) = list_item(&input[pos..line_end]) else {
+			break;
+		};
+
+		items.push(item);
+		pos = (line_end + 1).min(input.len());
+	}
+
+	(build_list(&items), pos)
+}
+
+fn build_list(items: &[ListItem]) -> Node {
+	let indent = items[0].indent;
+	let mut nodes: Vec<Vec<Node>> = Vec::new();
+
+	let mut i = 0;
+	while i < items.len() {
+		let mut children = parse_inline(items[i].content, false);
+
+		let nested = items[i + 1..]
+			.iter()
+			.take_while(|item| item.indent > indent)
+			.count();
+		if nested > 0 {
+			children.push(build_list(&items[i + 1..i + 1 + nested]));
+		}
+
+		nodes.push(children);
+		i += 1 + nested;
+	}
+
+	Node::List {
+		start: items[0].number,
+		items: nodes,
+	}
+}
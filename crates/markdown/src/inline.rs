@@ -0,0 +1,486 @@
+use discord_api::types::snowflake::Snowflake;
+
+use crate::{Node, TimestampStyle};
+
+/// Parses inline formatting, `in_link` disables links so masked link text cant contain another link
+pub(crate) fn parse_inline(
+	input: &str,
+	in_link: bool,
+) -> Vec<Node> {
+	let mut nodes = Vec::new();
+	let mut text = String::new();
+	let mut pos = 0;
+
+	while pos < input.len() {
+		let prev = input[..pos].chars().next_back();
+
+		if let Some((node, len)) = match_inline(&input[pos..], prev, in_link) {
+			match node {
+				| Node::Text(t) => text.push_str(&t),
+				| node => {
+					if !text.is_empty() {
+						nodes.push(Node::Text(std::mem::take(&mut text)));
+					}
+					nodes.push(node);
+				},
+			}
+			pos += len;
+			continue;
+		}
+
+		let c = input[pos..].chars().next().unwrap_or_default();
+		text.push(c);
+		pos += c.len_utf8();
+	}
+
+	if !text.is_empty() {
+		nodes.push(Node::Text(text));
+	}
+
+	nodes
+}
+
+/// Tries every inline rule at the start of `rest`, returns the node and how many bytes it used
+fn match_inline(
+	rest: &str,
+	prev: Option<char>,
+	in_link: bool,
+) -> Option<(Node, usize)> {
+	match rest.as_bytes()[0] {
+		| b'\\' => escape(rest),
+		| b'`' => code_block(rest).or_else(|| inline_code(rest)),
+		| b'*' | b'_' => emphasis(rest, prev, in_link),
+		| b'~' if rest.starts_with("~~") => {
+			let len = find_closing(&rest[2..], "~~", None)?;
+			Some((
+				Node::Strikethrough(parse_inline(&rest[2..2 + len], in_link)),
+				len + 4,
+			))
+		},
+		| b'|' if rest.starts_with("||") => {
+			let len = find_closing(&rest[2..], "||", None)?;
+			Some((
+				Node::Spoiler(parse_inline(&rest[2..2 + len], in_link)),
+				len + 4,
+			))
+		},
+		| b'<' => angle_bracket(rest, in_link),
+		| b'@' => {
+			if rest.starts_with("@everyone") {
+				Some((Node::Everyone, 9))
+			} else if rest.starts_with("@here") {
+				Some((Node::Here, 5))
+			} else {
+				None
+			}
+		},
+		| b'[' if !in_link => masked_link(rest),
+		| b'h' | b's' if !in_link && !prev.is_some_and(|c| c.is_alphanumeric()) => url(rest),
+		| _ => None,
+	}
+}
+
+/// `\*` shows a literal `*`, anything but letters, numbers and whitespace can be escaped
+fn escape(rest: &str) -> Option<(Node, usize)> {
+	let c = rest[1..].chars().next()?;
+	if c.is_alphanumeric() || c.is_whitespace() {
+		return None;
+	}
+
+	Some((Node::Text(c.to_string()), 1 + c.len_utf8()))
+}
+
+/// A fenced ```` ```lang\ncode``` ```` block, the language is only used if a newline follows it
+fn code_block(rest: &str) -> Option<(Node, usize)> {
+	let after = rest.strip_prefix("```")?;
+
+	let lang_len = after
+		.find(|c: char| !(c.is_ascii_alphanumeric() || "_+-.#".contains(c)))
+		.unwrap_or(after.len());
+	if lang_len > 0
+		&& after[lang_len..].starts_with('\n')
+		&& let Some((content, len)) = code_block_body(&after[lang_len + 1..])
+	{
+		return Some((
+			Node::CodeBlock {
+				language: Some(after[..lang_len].to_owned()),
+				content,
+			},
+			3 + lang_len + 1 + len,
+		));
+	}
+
+	let (content, len) = code_block_body(after)?;
+	Some((
+		Node::CodeBlock {
+			language: None,
+			content,
+		},
+		3 + len,
+	))
+}
+
+/// the content of a code block up to the closing fence, without the newlines around it
+fn code_block_body(body: &str) -> Option<(String, usize)> {
+	let start = body.len() - body.trim_start_matches('\n').len();
+	// the content needs at least one character that isnt a newline
+	let first_len = body[start..].chars().next()?.len_utf8();
+	let end = start + first_len + body[start + first_len..].find("```")?;
+
+	Some((body[start..end].trim_end_matches('\n').to_owned(), end + 3))
+}
+
+/// `` `code` `` or ``` `` code with ` backticks `` ```
+fn inline_code(rest: &str) -> Option<(Node, usize)> {
+	let ticks = rest.len() - rest.trim_start_matches('`').len();
+	let fence = &rest[..ticks];
+
+	let mut search = ticks;
+	let end = loop {
+		let found = search + rest[search..].find(fence)?;
+		let run = rest[found..].len() - rest[found..].trim_start_matches('`').len();
+		// the closing run has to be exactly as long as the opening one and the content cant end in a backtick
+		if found > ticks && run == ticks {
+			break found;
+		}
+		search = found + run.max(1);
+	};
+
+	let mut content = &rest[ticks..end];
+	// a single space can separate the content from backticks, eg. `` `foo` ``
+	if content.starts_with(' ') && content[1..].trim_start().starts_with('`') {
+		content = &content[1..];
+	}
+	if content.ends_with(' ') && content[..content.len() - 1].trim_end().ends_with('`') {
+		content = &content[..content.len() - 1];
+	}
+
+	Some((Node::InlineCode(content.to_owned()), end + ticks))
+}
+
+/// `*`, `_`, `**` and `__` all start at the same characters, so every rule is tried and the best match is used
+///
+/// The longest match wins, ties are broken in favour of italic then bold then underline
+fn emphasis(
+	rest: &str,
+	prev: Option<char>,
+	in_link: bool,
+) -> Option<(Node, usize)> {
+	// (quality, node kind, content start, content end)
+	let mut candidates: Vec<(usize, u8, usize, usize)> = Vec::new();
+
+	let italic = if let Some(content) = rest.strip_prefix('*') {
+		italic_star(content)
+	} else if !prev.is_some_and(is_word_char) {
+		italic_underscore(&rest[1..])
+	} else {
+		None
+	};
+	if let Some(len) = italic {
+		candidates.push(((len + 2) * 10 + 2, 0, 1, 1 + len));
+	}
+
+	if rest.starts_with("**")
+		&& let Some(len) = find_closing(&rest[2..], "**", Some(b'*'))
+	{
+		candidates.push(((len + 4) * 10 + 1, 1, 2, 2 + len));
+	}
+	if rest.starts_with("__")
+		&& let Some(len) = find_closing(&rest[2..], "__", Some(b'_'))
+	{
+		candidates.push(((len + 4) * 10, 2, 2, 2 + len));
+	}
+
+	let (_, kind, start, end) = candidates.into_iter().max_by_key(|c| c.0)?;
+	let children = parse_inline(&rest[start..end], in_link);
+	let node = match kind {
+		| 0 => Node::Italic(children),
+		| 1 => Node::Bold(children),
+		| _ => Node::Underline(children),
+	};
+
+	Some((node, end + start))
+}
+
+/// the content length of `*italic*`, the content cant start or end with whitespace
+/// and can only contain `*` as part of a `**` pair
+fn italic_star(content: &str) -> Option<usize> {
+	if content.chars().next()?.is_whitespace() {
+		return None;
+	}
+
+	let mut i = 0;
+	loop {
+		let unit = &content[i..];
+		let whitespace = unit.len() - unit.trim_start().len();
+		let unit = &unit[whitespace..];
+
+		i += whitespace
+			+ if unit.starts_with("**") {
+				2
+			} else if let Some(escaped) = unit.strip_prefix('\\')
+				&& let Some(c) = escaped.chars().next()
+			{
+				1 + c.len_utf8()
+			} else {
+				match unit.chars().next() {
+					| Some(c) if c != '*' && c != '\\' => c.len_utf8(),
+					| _ => return None,
+				}
+			};
+
+		if content[i..].starts_with('*') && !content[i + 1..].starts_with('*') {
+			return Some(i);
+		}
+	}
+}
+
+/// the content length of `_italic_`, the closing `_` cant be followed by a word character
+fn italic_underscore(content: &str) -> Option<usize> {
+	let mut i = 0;
+	loop {
+		let unit = &content[i..];
+		i += if unit.starts_with("__") {
+			2
+		} else if let Some(escaped) = unit.strip_prefix('\\')
+			&& let Some(c) = escaped.chars().next()
+		{
+			1 + c.len_utf8()
+		} else {
+			match unit.chars().next() {
+				| Some(c) if c != '_' && c != '\\' => c.len_utf8(),
+				| _ => return None,
+			}
+		};
+
+		if content[i..].starts_with('_')
+			&& !content[i + 1..].chars().next().is_some_and(is_word_char)
+		{
+			return Some(i);
+		}
+	}
+}
+
+fn is_word_char(c: char) -> bool {
+	c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Finds the first `delim` after at least one character of content, skipping escaped characters
+///
+/// `not_followed_by` rejects a closing delimiter directly followed by that byte, so `**a***` closes on the last two
+fn find_closing(
+	content: &str,
+	delim: &str,
+	not_followed_by: Option<u8>,
+) -> Option<usize> {
+	let mut i = 0;
+	while i < content.len() {
+		if i > 0
+			&& content[i..].starts_with(delim)
+			&& not_followed_by.is_none_or(|b| content.as_bytes().get(i + delim.len()) != Some(&b))
+		{
+			return Some(i);
+		}
+
+		if content[i..].starts_with('\\') && i + 1 < content.len() {
+			i += 1;
+		}
+		i += content[i..].chars().next()?.len_utf8();
+	}
+
+	None
+}
+
+/// mentions, emojis, slash commands, timestamps and `<https://embed.suppressed>` urls
+fn angle_bracket(
+	rest: &str,
+	in_link: bool,
+) -> Option<(Node, usize)> {
+	let end = rest.find('>')?;
+	let inner = &rest[1..end];
+	if inner.is_empty() || inner.contains(['<', '\n']) {
+		return None;
+	}
+	let len = end + 1;
+	let snowflake = |id: &str| -> Option<Snowflake> {
+		if id.is_empty() || !id.bytes().all(|b| b.is_ascii_digit()) {
+			return None;
+		}
+		id.parse().ok()
+	};
+
+	let node = if let Some(id) = inner.strip_prefix("@&") {
+		Node::RoleMention(snowflake(id)?)
+	} else if let Some(id) = inner.strip_prefix("@!").or(inner.strip_prefix('@')) {
+		Node::UserMention(snowflake(id)?)
+	} else if let Some(id) = inner.strip_prefix('#') {
+		Node::ChannelMention(snowflake(id)?)
+	} else if let Some(emoji) = inner.strip_prefix(':').or(inner.strip_prefix("a:")) {
+		let (name, id) = emoji.split_once(':')?;
+		if name.is_empty() || !name.chars().all(is_word_char) {
+			return None;
+		}
+
+		Node::CustomEmoji {
+			name: name.to_owned(),
+			id: snowflake(id)?,
+			animated: inner.starts_with('a'),
+		}
+	} else if let Some(command) = inner.strip_prefix('/') {
+		let (name, id) = command.rsplit_once(':')?;
+		let words = name.split(' ').collect::<Vec<_>>();
+		if words.len() > 3
+			|| words.iter().any(|w| {
+				w.is_empty() || w.contains(|c: char| c.is_whitespace() || c == ':' || c == '/')
+			}) {
+			return None;
+		}
+
+		Node::SlashCommand {
+			name: name.to_owned(),
+			id: snowflake(id)?,
+		}
+	} else if let Some(timestamp) = inner.strip_prefix("t:") {
+		let (timestamp, style) = match timestamp.split_once(':') {
+			| Some((timestamp, style)) => {
+				let mut chars = style.chars();
+				match (
+					chars.next().and_then(TimestampStyle::from_char),
+					chars.next(),
+				) {
+					| (Some(style), None) => (timestamp, style),
+					| _ => return None,
+				}
+			},
+			| None => (timestamp, TimestampStyle::default()),
+		};
+		let digits = timestamp.strip_prefix('-').unwrap_or(timestamp);
+		if digits.is_empty() || digits.len() > 17 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+			return None;
+		}
+
+		Node::Timestamp {
+			timestamp: timestamp.parse().ok()?,
+			style,
+		}
+	} else if !in_link && is_url(inner) && !inner.contains(' ') {
+		Node::Url {
+			url: inner.to_owned(),
+			suppress_embed: true,
+		}
+	} else {
+		return None;
+	};
+
+	Some((node, len))
+}
+
+/// `[text](url)`, `[text](<url>)` or `[text](url "title")`
+fn masked_link(rest: &str) -> Option<(Node, usize)> {
+	// the text can contain balanced brackets
+	let mut depth = 0;
+	let mut text_end = None;
+	for (i, c) in rest.char_indices() {
+		match c {
+			| '[' => depth += 1,
+			| ']' => {
+				depth -= 1;
+				if depth == 0 {
+					text_end = Some(i);
+					break;
+				}
+			},
+			| _ => {},
+		}
+	}
+	let text_end = text_end?;
+	let text = &rest[1..text_end];
+	if text.trim().is_empty() {
+		return None;
+	}
+
+	let target_start = text_end + 2;
+	if !rest[text_end + 1..].starts_with('(') {
+		return None;
+	}
+
+	let mut depth = 1;
+	let target_len = rest[target_start..].char_indices().find_map(|(i, c)| {
+		match c {
+			| '(' => depth += 1,
+			| ')' => depth -= 1,
+			| _ => {},
+		}
+		(depth == 0).then_some(i)
+	})?;
+	let target = rest[target_start..target_start + target_len].trim();
+
+	let (url, title) = match target.split_once(char::is_whitespace) {
+		| Some((url, title)) => {
+			let title = title.trim();
+			let title = title
+				.strip_prefix('"')
+				.and_then(|t| t.strip_suffix('"'))
+				.or_else(|| title.strip_prefix('\'').and_then(|t| t.strip_suffix('\'')))?;
+			(url, Some(title.to_owned()))
+		},
+		| None => (target, None),
+	};
+	let (url, suppress_embed) = match url.strip_prefix('<').and_then(|u| u.strip_suffix('>')) {
+		| Some(url) => (url, true),
+		| None => (url, false),
+	};
+	if !is_url(url) {
+		return None;
+	}
+
+	Some((
+		Node::Link {
+			content: parse_inline(text, true),
+			url: url.to_owned(),
+			title,
+			suppress_embed,
+		},
+		target_start + target_len + 1,
+	))
+}
+
+/// A bare url, trailing punctuation is left out unless it closes a bracket in the url
+fn url(rest: &str) -> Option<(Node, usize)> {
+	if !is_url(rest) {
+		return None;
+	}
+
+	let full = rest
+		.find(|c: char| c.is_whitespace() || c == '<')
+		.unwrap_or(rest.len());
+	let mut len = rest[..full]
+		.trim_end_matches(['.', ',', ':', ';', '"', '\'', ')', ']'])
+		.len();
+
+	// keep closing parentheses that are part of the url, eg. wikipedia links
+	while rest[len..full].starts_with(')')
+		&& rest[..len].matches('(').count() > rest[..len].matches(')').count()
+	{
+		len += 1;
+	}
+
+	if !is_url(&rest[..len]) {
+		return None;
+	}
+
+	Some((
+		Node::Url {
+			url: rest[..len].to_owned(),
+			suppress_embed: false,
+		},
+		len,
+	))
+}
+
+/// whether `text` starts with a scheme discord links and has something after it
+fn is_url(text: &str) -> bool {
+	["https://", "http://", "steam://"]
+		.iter()
+		.any(|scheme| text.len() > scheme.len() && text.starts_with(scheme))
+}
@@ -0,0 +1,146 @@
+//! Parser for the markdown flavor Discord uses in message content
+//!
+//! [`parse`] turns message content into a tree of [`Node`]s that can be rendered into anything
+//! (the message view, html exports, plain text notifications) without reparsing
+//!
+//! Precedence follows Discord's client:
+//! - code blocks and inline code are never parsed further
+//! - headers, subtext, lists and block quotes only start at the beginning of a line, quotes cant be nested
+//! - `*`, `**` and `__` starting at the same spot pick the longest match, ties go to italic then bold
+//! - `_` italics need a word boundary on both sides, so `snake_case_names` stay as they are
+//! - links cant be nested inside masked link text
+
+use discord_api::types::snowflake::Snowflake;
+
+mod block;
+mod inline;
+mod tests;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+	/// Plain text, may contain newlines
+	Text(String),
+	/// `**text**`
+	Bold(Vec<Node>),
+	/// `*text*` or `_text_`
+	Italic(Vec<Node>),
+	/// `__text__`
+	Underline(Vec<Node>),
+	/// `~~text~~`
+	Strikethrough(Vec<Node>),
+	/// `||text||`
+	Spoiler(Vec<Node>),
+	/// `` `code` ``
+	InlineCode(String),
+	/// A fenced code block, eg. ```` ```lang\ncode``` ````
+	CodeBlock {
+		language: Option<String>,
+		content: String,
+	},
+	/// `> text` for a single line or `>>> text` for the rest of the message
+	BlockQuote(Vec<Node>),
+	/// `# text`, `## text` or `### text`
+	Heading {
+		/// 1 to 3
+		level: u8,
+		content: Vec<Node>,
+	},
+	/// `-# text`
+	Subtext(Vec<Node>),
+	/// consecutive lines starting with `- `, `* ` or `1. `, indented items are nested lists
+	List {
+		/// the number of the first item for ordered lists, `None` for unordered lists
+		start: Option<u64>,
+		items: Vec<Vec<Node>>,
+	},
+	/// `[text](https://example.com "title")`
+	Link {
+		content: Vec<Node>,
+		url: String,
+		title: Option<String>,
+		/// the url was wrapped in `<>` so it should not be embedded
+		suppress_embed: bool,
+	},
+	/// A bare `https://example.com` or `<https://example.com>`
+	Url {
+		url: String,
+		/// the url was wrapped in `<>` so it should not be embedded
+		suppress_embed: bool,
+	},
+	/// `<@id>` or `<@!id>`
+	UserMention(Snowflake),
+	/// `<@&id>`
+	RoleMention(Snowflake),
+	/// `<#id>`
+	ChannelMention(Snowflake),
+	/// `@everyone`
+	Everyone,
+	/// `@here`
+	Here,
+	/// `<:name:id>` or `<a:name:id>`
+	CustomEmoji {
+		name: String,
+		id: Snowflake,
+		animated: bool,
+	},
+	/// `</name:id>`, subcommands are included in the name separated by spaces
+	SlashCommand { name: String, id: Snowflake },
+	/// `<t:unix>` or `<t:unix:style>`
+	Timestamp {
+		/// unix seconds
+		timestamp: i64,
+		style: TimestampStyle,
+	},
+}
+
+/// How a `<t:unix:style>` timestamp should be displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimestampStyle {
+	/// `t`, eg. 16:20
+	ShortTime,
+	/// `T`, eg. 16:20:30
+	LongTime,
+	/// `d`, eg. 20/04/2021
+	ShortDate,
+	/// `D`, eg. 20 April 2021
+	LongDate,
+	/// `f`, eg. 20 April 2021 16:20
+	#[default]
+	ShortDateTime,
+	/// `F`, eg. Tuesday, 20 April 2021 16:20
+	LongDateTime,
+	/// `R`, eg. 2 months ago
+	Relative,
+}
+
+impl TimestampStyle {
+	pub fn from_char(c: char) -> Option<Self> {
+		match c {
+			| 't' => Some(Self::ShortTime),
+			| 'T' => Some(Self::LongTime),
+			| 'd' => Some(Self::ShortDate),
+			| 'D' => Some(Self::LongDate),
+			| 'f' => Some(Self::ShortDateTime),
+			| 'F' => Some(Self::LongDateTime),
+			| 'R' => Some(Self::Relative),
+			| _ => None,
+		}
+	}
+
+	pub fn as_char(&self) -> char {
+		match self {
+			| Self::ShortTime => 't',
+			| Self::LongTime => 'T',
+			| Self::ShortDate => 'd',
+			| Self::LongDate => 'D',
+			| Self::ShortDateTime => 'f',
+			| Self::LongDateTime => 'F',
+			| Self::Relative => 'R',
+		}
+	}
+}
+
+/// Parses message content into its syntax tree
+pub fn parse(content: &str) -> Vec<Node> {
+	block::parse_blocks(content, true)
+}
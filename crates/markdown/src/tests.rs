@@ -0,0 +1,261 @@
+#![cfg(test)]
+
+fn text(t: &str) -> crate::Node {
+	crate::Node::Text(t.to_owned())
+}
+
+mod inline {
+	use super::text;
+	use crate::*;
+
+	#[test]
+	fn formatting() {
+		assert_eq!(
+			parse("**bold** *italic* __underline__ ~~strike~~ ||spoiler||"),
+			vec![
+				Node::Bold(vec![text("bold")]),
+				text(" "),
+				Node::Italic(vec![text("italic")]),
+				text(" "),
+				Node::Underline(vec![text("underline")]),
+				text(" "),
+				Node::Strikethrough(vec![text("strike")]),
+				text(" "),
+				Node::Spoiler(vec![text("spoiler")]),
+			]
+		);
+	}
+
+	#[test]
+	fn nested_emphasis() {
+		assert_eq!(parse("***both***"), vec![Node::Italic(vec![Node::Bold(
+			vec![text("both")]
+		)])]);
+		assert_eq!(parse("__*underlined italic*__"), vec![Node::Underline(
+			vec![Node::Italic(vec![text("underlined italic")])]
+		)]);
+		assert_eq!(parse("**a***"), vec![Node::Bold(vec![text("a*")])]);
+	}
+
+	#[test]
+	fn underscore_word_boundaries() {
+		assert_eq!(parse("snake_case_name"), vec![text("snake_case_name")]);
+		assert_eq!(parse("an _italic_ word"), vec![
+			text("an "),
+			Node::Italic(vec![text("italic")]),
+			text(" word")
+		]);
+	}
+
+	#[test]
+	fn unclosed_and_spaced_delimiters() {
+		assert_eq!(parse("**not bold"), vec![text("**not bold")]);
+		assert_eq!(parse("2 * 3 * 4"), vec![text("2 * 3 * 4")]);
+		assert_eq!(parse("||||"), vec![text("||||")]);
+	}
+
+	#[test]
+	fn escapes() {
+		assert_eq!(parse(r"\*\*not bold\*\*"), vec![text("**not bold**")]);
+		assert_eq!(parse(r"\<@123>"), vec![text("<@123>")]);
+		assert_eq!(parse(r"\n"), vec![text(r"\n")]);
+	}
+
+	#[test]
+	fn code_is_not_parsed() {
+		assert_eq!(parse("`**code**` **`bold code`**"), vec![
+			Node::InlineCode("**code**".to_owned()),
+			text(" "),
+			Node::Bold(vec![Node::InlineCode("bold code".to_owned())]),
+		]);
+		assert_eq!(parse("`` `ticks` ``"), vec![Node::InlineCode(
+			"`ticks`".to_owned()
+		)]);
+	}
+
+	#[test]
+	fn code_blocks() {
+		assert_eq!(parse("```rust\nfn main() {}\n```"), vec![Node::CodeBlock {
+			language: Some("rust".to_owned()),
+			content: "fn main() {}".to_owned(),
+		}]);
+		assert_eq!(parse("```rust```"), vec![Node::CodeBlock {
+			language: None,
+			content: "rust".to_owned(),
+		}]);
+		assert_eq!(parse("```\n# not a heading\n> or a quote\n```"), vec![
+			Node::CodeBlock {
+				language: None,
+				content: "# not a heading\n> or a quote".to_owned(),
+			}
+		]);
+	}
+
+	#[test]
+	fn mentions() {
+		assert_eq!(parse("<@1> <@!2> <@&3> <#4> @everyone @here"), vec![
+			Node::UserMention(1.into()),
+			text(" "),
+			Node::UserMention(2.into()),
+			text(" "),
+			Node::RoleMention(3.into()),
+			text(" "),
+			Node::ChannelMention(4.into()),
+			text(" "),
+			Node::Everyone,
+			text(" "),
+			Node::Here,
+		]);
+		assert_eq!(parse("<@abc>"), vec![text("<@abc>")]);
+	}
+
+	#[test]
+	fn emojis_commands_and_timestamps() {
+		assert_eq!(
+			parse("<:cow:10><a:party_cow:20></settings user:30><t:1618953630><t:-5:R>"),
+			vec![
+				Node::CustomEmoji {
+					name: "cow".to_owned(),
+					id: 10.into(),
+					animated: false,
+				},
+				Node::CustomEmoji {
+					name: "party_cow".to_owned(),
+					id: 20.into(),
+					animated: true,
+				},
+				Node::SlashCommand {
+					name: "settings user".to_owned(),
+					id: 30.into(),
+				},
+				Node::Timestamp {
+					timestamp: 1618953630,
+					style: TimestampStyle::ShortDateTime,
+				},
+				Node::Timestamp {
+					timestamp: -5,
+					style: TimestampStyle::Relative,
+				},
+			]
+		);
+		assert_eq!(parse("<t:1:x>"), vec![text("<t:1:x>")]);
+	}
+
+	#[test]
+	fn links() {
+		assert_eq!(
+			parse("[**docs**](<https://docs.discord.food> \"title\")"),
+			vec![Node::Link {
+				content: vec![Node::Bold(vec![text("docs")])],
+				url: "https://docs.discord.food".to_owned(),
+				title: Some("title".to_owned()),
+				suppress_embed: true,
+			}]
+		);
+		assert_eq!(parse("[https://a.com](https://b.com)"), vec![Node::Link {
+			content: vec![text("https://a.com")],
+			url: "https://b.com".to_owned(),
+			title: None,
+			suppress_embed: false,
+		}]);
+		assert_eq!(parse("[text](not a url)"), vec![text("[text](not a url)")]);
+	}
+
+	#[test]
+	fn urls() {
+		assert_eq!(
+			parse("see https://en.wikipedia.org/wiki/Cow_(disambiguation)."),
+			vec![
+				text("see "),
+				Node::Url {
+					url: "https://en.wikipedia.org/wiki/Cow_(disambiguation)".to_owned(),
+					suppress_embed: false,
+				},
+				text("."),
+			]
+		);
+		assert_eq!(parse("<https://discord.com>"), vec![Node::Url {
+			url: "https://discord.com".to_owned(),
+			suppress_embed: true,
+		}]);
+		assert_eq!(parse("xhttps://a.com"), vec![text("xhttps://a.com")]);
+	}
+}
+
+mod block {
+	use super::text;
+	use crate::*;
+
+	#[test]
+	fn headings_and_subtext() {
+		assert_eq!(
+			parse("# big\n### *small* ##\n#### not a heading\n-# subtext"),
+			vec![
+				Node::Heading {
+					level: 1,
+					content: vec![text("big")],
+				},
+				Node::Heading {
+					level: 3,
+					content: vec![Node::Italic(vec![text("small")])],
+				},
+				text("#### not a heading"),
+				Node::Subtext(vec![text("subtext")]),
+			]
+		);
+		assert_eq!(parse("#nospace"), vec![text("#nospace")]);
+		assert_eq!(parse("text # not heading"), vec![text(
+			"text # not heading"
+		)]);
+	}
+
+	#[test]
+	fn block_quotes() {
+		assert_eq!(parse("> one\n> # two\nafter"), vec![
+			Node::BlockQuote(vec![text("one"), Node::Heading {
+				level: 1,
+				content: vec![text("two")],
+			},]),
+			text("after"),
+		]);
+		assert_eq!(parse(">>> rest\n> of the\nmessage"), vec![
+			Node::BlockQuote(vec![text("rest\n> of the\nmessage")])
+		]);
+		assert_eq!(parse(">no space"), vec![text(">no space")]);
+	}
+
+	#[test]
+	fn lists() {
+		assert_eq!(
+			parse("- one\n  - nested\n- **two**\n\n3. three\n4. four"),
+			vec![
+				Node::List {
+					start: None,
+					items: vec![
+						vec![text("one"), Node::List {
+							start: None,
+							items: vec![vec![text("nested")]],
+						},],
+						vec![Node::Bold(vec![text("two")])],
+					],
+				},
+				text("\n"),
+				Node::List {
+					start: Some(3),
+					items: vec![vec![text("three")], vec![text("four")]],
+				},
+			]
+		);
+		assert_eq!(parse("*not a list*"), vec![Node::Italic(vec![text(
+			"not a list"
+		)])]);
+	}
+
+	#[test]
+	fn paragraphs_keep_newlines() {
+		assert_eq!(parse("**bold\nacross lines**\nplain"), vec![
+			Node::Bold(vec![text("bold\nacross lines")]),
+			text("\nplain"),
+		]);
+	}
+}